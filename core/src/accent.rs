@@ -0,0 +1,487 @@
+//! Accent Restoration
+//!
+//! Proposes accented forms for Vietnamese typed without an IME:
+//! "khong co gi" → "không có gì".
+//!
+//! ## Algorithm
+//! 1. Split text into syllables (runs of ASCII letters)
+//! 2. Enumerate every legal accented form of each syllable: vowel modifiers
+//!    checked by `validation::is_valid_with_tones`, marks placed by phonology
+//!    rules, stop finals (c, ch, p, t) limited to sắc/nặng
+//! 3. Rank with the bundled syllable/bigram frequencies (`data::frequency`),
+//!    choosing the best sequence over whitespace-separated syllables
+//!
+//! Syllables without a legal Vietnamese form (English, numbers, already
+//! accented text) are kept unchanged.
+
+use crate::data::{
+    chars::{self, mark, tone},
+    dictionary, frequency, keys,
+    vowel::Phonology,
+};
+use crate::engine::buffer::{Buffer, Char};
+use crate::engine::syllable;
+use crate::engine::validation::is_valid_with_tones;
+use crate::utils;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Accent candidates for one syllable of the input
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyllableCandidates {
+    /// Byte offset of the syllable in the input text
+    pub start: usize,
+    /// Byte offset past the end of the syllable
+    pub end: usize,
+    /// Accented forms, best first (just the original text if none)
+    pub candidates: Vec<String>,
+}
+
+/// Restoration result
+#[derive(Debug, Clone, PartialEq)]
+pub struct Restoration {
+    /// Best-guess text
+    pub text: String,
+    /// Candidates per syllable, in input order
+    pub syllables: Vec<SyllableCandidates>,
+}
+
+/// Restore accents in unaccented text
+pub fn restore(text: &str) -> Restoration {
+    let mut syllables = Vec::new();
+    // Candidates of the current run of whitespace-separated syllables
+    let mut run: Vec<Vec<(String, Signature)>> = Vec::new();
+    let mut run_start = 0;
+    let mut pos = 0;
+
+    for (start, end) in split_words(text) {
+        let word = &text[start..end];
+        let candidates = syllable_candidates(word);
+
+        // Punctuation or an unknown word breaks the context
+        let gap = &text[pos..start];
+        if candidates.is_empty() || !gap.chars().all(char::is_whitespace) {
+            flush_run(&mut run, &mut syllables[run_start..]);
+            run_start = syllables.len();
+        }
+        pos = end;
+
+        if candidates.is_empty() {
+            syllables.push(SyllableCandidates {
+                start,
+                end,
+                candidates: vec![word.to_string()],
+            });
+            run_start = syllables.len();
+        } else {
+            syllables.push(SyllableCandidates {
+                start,
+                end,
+                candidates: candidates.iter().map(|(s, _)| s.clone()).collect(),
+            });
+            run.push(candidates);
+        }
+    }
+    flush_run(&mut run, &mut syllables[run_start..]);
+
+    // Rebuild text with chosen candidates
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut pos = 0;
+    for s in &syllables {
+        out.push_str(&text[pos..s.start]);
+        out.push_str(&s.candidates[0]);
+        pos = s.end;
+    }
+    out.push_str(&text[pos..]);
+
+    Restoration {
+        text: out,
+        syllables,
+    }
+}
+
+/// Accented forms of a single syllable, most frequent first
+///
+/// Existing diacritics are ignored: "dược" gives the same list as "duoc".
+/// Only attested syllables (`data::dictionary`) are listed, so legal but
+/// unused spellings ("duóc") don't push out real words. Empty if the word
+/// has no attested Vietnamese form.
+pub fn word_candidates(word: &str) -> Vec<String> {
+    let plain: String = word
+        .chars()
+        .map(|c| match chars::parse_char(c) {
+            Some(p) if p.stroke => {
+                if p.caps {
+                    'D'
+                } else {
+                    'd'
+                }
+            }
+            Some(p) if keys::is_vowel(p.key) => chars::to_char(p.key, p.caps, 0, 0).unwrap_or(c),
+            _ => c,
+        })
+        .collect();
+    syllable_candidates(&plain)
+        .into_iter()
+        .map(|(s, _)| s)
+        .filter(|s| dictionary::contains(s))
+        .collect()
+}
+
+/// Pick the best sequence for a run and move the choices to the front
+fn flush_run(run: &mut Vec<Vec<(String, Signature)>>, syllables: &mut [SyllableCandidates]) {
+    if run.is_empty() {
+        return;
+    }
+    let best = best_sequence(run);
+    for (s, choice) in syllables.iter_mut().zip(best) {
+        s.candidates.swap(0, choice);
+        // Keep the remaining candidates in frequency order
+        s.candidates[1..].sort_by_key(|c| std::cmp::Reverse(rank(&signature(c))));
+    }
+    run.clear();
+}
+
+/// Viterbi over syllable candidates: unigram + bigram log frequencies
+fn best_sequence(run: &[Vec<(String, Signature)>]) -> Vec<usize> {
+    let score = |f: u32| ((f + 1) as f32).ln();
+    // (total score, back pointer) per candidate
+    let mut table: Vec<Vec<(f32, usize)>> = Vec::with_capacity(run.len());
+
+    for (i, candidates) in run.iter().enumerate() {
+        let row = candidates
+            .iter()
+            .map(|(_, sig)| {
+                let uni = score(unigram(sig));
+                if i == 0 {
+                    return (uni, 0);
+                }
+                table[i - 1]
+                    .iter()
+                    .zip(&run[i - 1])
+                    .enumerate()
+                    .map(|(j, (&(prev, _), (_, prev_sig)))| {
+                        (prev + uni + 2.0 * score(bigram(prev_sig, sig)), j)
+                    })
+                    // First maximum wins ties (keeps frequency order)
+                    .fold(
+                        (f32::MIN, 0),
+                        |best, cur| if cur.0 > best.0 { cur } else { best },
+                    )
+            })
+            .collect();
+        table.push(row);
+    }
+
+    let mut choice = table.last().map_or(0, |row| {
+        row.iter()
+            .enumerate()
+            .fold(
+                (f32::MIN, 0),
+                |best, (j, &(s, _))| if s > best.0 { (s, j) } else { best },
+            )
+            .1
+    });
+    let mut best = vec![0; run.len()];
+    for i in (0..run.len()).rev() {
+        best[i] = choice;
+        choice = table[i][choice].1;
+    }
+    best
+}
+
+/// Byte ranges of words (alphanumeric runs)
+fn split_words(text: &str) -> Vec<(usize, usize)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                words.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        words.push((s, text.len()));
+    }
+    words
+}
+
+// ============================================================
+// CANDIDATE ENUMERATION
+// ============================================================
+
+/// Placement-independent syllable key: lowercase letters with modifiers, mark
+type Signature = (String, u8);
+
+/// Signature of an accented syllable
+fn signature(s: &str) -> Signature {
+    let mut base = String::with_capacity(s.len());
+    let mut m = mark::NONE;
+    for c in s.chars() {
+        match chars::parse_char(c) {
+            Some(p) if p.stroke => base.push(chars::get_d(false)),
+            Some(p) if keys::is_vowel(p.key) => {
+                if p.mark != mark::NONE {
+                    m = p.mark;
+                }
+                base.extend(chars::to_char(p.key, false, p.tone, mark::NONE));
+            }
+            _ => base.extend(c.to_lowercase()),
+        }
+    }
+    (base, m)
+}
+
+fn unigram(sig: &Signature) -> u32 {
+    static INDEX: OnceLock<HashMap<Signature, u32>> = OnceLock::new();
+    let index = INDEX.get_or_init(|| {
+        frequency::SYLLABLES
+            .iter()
+            .map(|&(s, f)| (signature(s), f))
+            .collect()
+    });
+    index.get(sig).copied().unwrap_or(0)
+}
+
+/// Rhyme of a syllable: everything from the first vowel, with its mark
+fn rhyme(sig: &Signature) -> Signature {
+    let start = sig
+        .0
+        .char_indices()
+        .find(|&(_, c)| chars::parse_char(c).is_some_and(|p| keys::is_vowel(p.key)))
+        .map_or(sig.0.len(), |(i, _)| i);
+    (sig.0[start..].to_string(), sig.1)
+}
+
+/// Total frequency of listed syllables sharing the rhyme
+///
+/// Ranks unlisted forms: "dược" (rhyme of được, nước) before "duóc".
+fn rhyme_frequency(sig: &Signature) -> u32 {
+    static INDEX: OnceLock<HashMap<Signature, u32>> = OnceLock::new();
+    let index = INDEX.get_or_init(|| {
+        let mut index = HashMap::new();
+        for &(s, f) in frequency::SYLLABLES {
+            *index.entry(rhyme(&signature(s))).or_insert(0) += f;
+        }
+        index
+    });
+    index.get(&rhyme(sig)).copied().unwrap_or(0)
+}
+
+/// Ranking key: syllable frequency, then rhyme frequency
+fn rank(sig: &Signature) -> (u32, u32) {
+    (unigram(sig), rhyme_frequency(sig))
+}
+
+fn bigram(prev: &Signature, cur: &Signature) -> u32 {
+    static INDEX: OnceLock<HashMap<(Signature, Signature), u32>> = OnceLock::new();
+    let index = INDEX.get_or_init(|| {
+        frequency::BIGRAMS
+            .iter()
+            .map(|&(a, b, f)| ((signature(a), signature(b)), f))
+            .collect()
+    });
+    index
+        .get(&(prev.clone(), cur.clone()))
+        .copied()
+        .unwrap_or(0)
+}
+
+/// Tone modifiers a vowel key can take
+fn tone_options(key: u16) -> &'static [u8] {
+    match key {
+        keys::A | keys::O => &[tone::NONE, tone::CIRCUMFLEX, tone::HORN],
+        keys::E => &[tone::NONE, tone::CIRCUMFLEX],
+        keys::U => &[tone::NONE, tone::HORN],
+        _ => &[tone::NONE],
+    }
+}
+
+/// Legal accented forms of an ASCII word, most frequent first
+///
+/// Letter case follows the input per position. Empty if the word is not
+/// plain ASCII or has no legal Vietnamese form.
+fn syllable_candidates(word: &str) -> Vec<(String, Signature)> {
+    if !word.chars().all(|c| c.is_ascii_alphabetic()) {
+        return vec![];
+    }
+    let Some(word_keys) = word
+        .chars()
+        .map(|c| keys::from_char(c.to_ascii_lowercase()).map(|(k, _)| k))
+        .collect::<Option<Vec<u16>>>()
+    else {
+        return vec![];
+    };
+    let caps: Vec<bool> = word.chars().map(|c| c.is_ascii_uppercase()).collect();
+
+    let vowel_positions: Vec<usize> = (0..word_keys.len())
+        .filter(|&i| keys::is_vowel(word_keys[i]))
+        .collect();
+    if vowel_positions.is_empty() || vowel_positions.len() > 3 {
+        return vec![];
+    }
+
+    // Stop finals only take sắc or nặng
+    let parsed = syllable::parse(&word_keys);
+    let final_keys: Vec<u16> = parsed.final_c.iter().map(|&i| word_keys[i]).collect();
+    let stop_final = matches!(
+        final_keys.as_slice(),
+        [keys::C] | [keys::C, keys::H] | [keys::P] | [keys::T]
+    );
+
+    let mut results = Vec::new();
+    let mut tones = vec![tone::NONE; word_keys.len()];
+    enumerate_tones(&word_keys, &vowel_positions, 0, &mut tones, &mut |tones| {
+        if !is_valid_with_tones(&word_keys, tones) {
+            return;
+        }
+        let mut buf = Buffer::new();
+        for (i, &key) in word_keys.iter().enumerate() {
+            let mut c = Char::new(key, caps[i]);
+            c.tone = tones[i];
+            buf.push(c);
+        }
+        let vowels = utils::collect_vowels(&buf);
+        let last_vowel = vowels.last().map_or(0, |v| v.pos);
+        let mark_pos = Phonology::find_tone_position(
+            &vowels,
+            utils::has_final_consonant(&buf, last_vowel),
+            true,
+            utils::has_qu_initial(&buf),
+            utils::has_gi_initial(&buf),
+        );
+
+        let strokes: &[bool] = if word_keys[0] == keys::D {
+            &[false, true]
+        } else {
+            &[false]
+        };
+        for &stroke in strokes {
+            for m in mark::NONE..=mark::NANG {
+                if stop_final && !matches!(m, mark::SAC | mark::NANG) {
+                    continue;
+                }
+                let s: String = buf
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, c)| {
+                        if i == 0 && stroke {
+                            Some(chars::get_d(c.caps))
+                        } else if keys::is_vowel(c.key) {
+                            let m = if i == mark_pos { m } else { mark::NONE };
+                            chars::to_char(c.key, c.caps, c.tone, m)
+                        } else {
+                            utils::key_to_char(c.key, c.caps)
+                        }
+                    })
+                    .collect();
+                let sig = signature(&s);
+                results.push((s, sig));
+            }
+        }
+    });
+
+    // Stable: equally ranked forms keep enumeration order (plain letters first)
+    results.sort_by_key(|(_, sig)| std::cmp::Reverse(rank(sig)));
+    results
+}
+
+/// Call `f` with every combination of vowel modifiers
+fn enumerate_tones(
+    word_keys: &[u16],
+    vowel_positions: &[usize],
+    idx: usize,
+    tones: &mut Vec<u8>,
+    f: &mut dyn FnMut(&[u8]),
+) {
+    let Some(&pos) = vowel_positions.get(idx) else {
+        f(tones);
+        return;
+    };
+    for &t in tone_options(word_keys[pos]) {
+        tones[pos] = t;
+        enumerate_tones(word_keys, vowel_positions, idx + 1, tones, f);
+    }
+    tones[pos] = tone::NONE;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn best(text: &str) -> String {
+        restore(text).text
+    }
+
+    #[test]
+    fn test_best_guess() {
+        assert_eq!(best("khong co gi"), "không có gì");
+        assert_eq!(best("Viet Nam"), "Việt Nam");
+        assert_eq!(best("nguoi Viet"), "người Việt");
+        assert_eq!(best("duoc khong?"), "được không?");
+        assert_eq!(best("cam on ban"), "cảm ơn bạn");
+    }
+
+    #[test]
+    fn test_bigram_context() {
+        // "co" alone is "có", but "co the" → "có thể"; "the gioi" → "thế giới"
+        assert_eq!(best("co the"), "có thể");
+        assert_eq!(best("the gioi"), "thế giới");
+    }
+
+    #[test]
+    fn test_keeps_non_vietnamese() {
+        assert_eq!(best("hello world 2024"), "hello world 2024");
+        assert_eq!(best("đã co"), "đã có");
+    }
+
+    #[test]
+    fn test_candidates() {
+        let r = restore("co");
+        let list = &r.syllables[0].candidates;
+        assert_eq!(list[0], "có");
+        assert!(list.contains(&"cô".to_string()));
+        assert!(list.contains(&"cơ".to_string()));
+        assert!(list.contains(&"co".to_string()));
+        // Stop final: only sắc and nặng
+        let r = restore("viet");
+        assert!(r.syllables[0].candidates.iter().all(|c| !c.contains('ề')));
+        assert_eq!(r.syllables[0].start, 0);
+        assert_eq!(r.syllables[0].end, 4);
+    }
+
+    #[test]
+    fn test_word_candidates() {
+        let list = word_candidates("duoc");
+        assert_eq!(list[0], "được");
+        assert!(list.contains(&"dược".to_string()));
+        assert!(list.contains(&"đuốc".to_string()));
+        // Legal spellings that are not words are left out
+        assert!(!list.contains(&"duóc".to_string()));
+        assert!(list.iter().all(|s| dictionary::contains(s)), "{:?}", list);
+        assert_eq!(word_candidates("Dược"), word_candidates("Duoc"));
+        assert!(word_candidates("xyz").is_empty());
+    }
+
+    #[test]
+    fn test_signature_ignores_placement() {
+        assert_eq!(signature("hoà"), signature("hòa"));
+        assert_eq!(signature("Đường"), ("đương".to_string(), mark::HUYEN));
+    }
+
+    #[test]
+    fn test_frequency_table_is_valid() {
+        let mut seen = std::collections::HashSet::new();
+        for &(s, _) in frequency::SYLLABLES {
+            assert!(seen.insert(signature(s)), "duplicate {}", s);
+        }
+        for &(a, b, _) in frequency::BIGRAMS {
+            assert!(unigram(&signature(a)) > 0, "{} not in SYLLABLES", a);
+            assert!(unigram(&signature(b)) > 0, "{} not in SYLLABLES", b);
+        }
+    }
+}
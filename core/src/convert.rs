@@ -0,0 +1,345 @@
+//! Charset Converter
+//!
+//! Converts whole strings between Vietnamese charsets (like UniKey's toolkit):
+//! text is decoded to Unicode precomposed (NFC), then encoded to the target.
+//!
+//! Legacy 8-bit charsets use the same convention as engine output: each byte
+//! is carried by its Windows-1252 character (see `data::encoding`).
+//!
+//! ```
+//! use gonhanh_core::convert::{convert, Charset};
+//! assert_eq!(convert("Việt", Charset::Unicode, Charset::Viqr), "Vie^.t");
+//! ```
+
+use crate::data::chars::{self, mark, tone};
+use crate::data::encoding::{self, Encoding};
+use crate::data::keys;
+
+/// Text charset
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Charset {
+    /// Unicode precomposed (NFC)
+    Unicode = 0,
+    /// Unicode decomposed (NFD)
+    UnicodeNfd = 1,
+    /// TCVN3 / ABC
+    Tcvn3 = 2,
+    /// VNI-Windows
+    VniWin = 3,
+    /// VISCII
+    Viscii = 4,
+    /// VIQR (RFC 1456): Vie^.t
+    Viqr = 5,
+    /// Windows-1258: base letter + combining tone mark where not precomposed
+    Cp1258 = 6,
+}
+
+impl Charset {
+    /// Get charset by id (ids 0-4 match output encodings)
+    pub fn from_id(id: u8) -> Option<Self> {
+        Some(match id {
+            0 => Charset::Unicode,
+            1 => Charset::UnicodeNfd,
+            2 => Charset::Tcvn3,
+            3 => Charset::VniWin,
+            4 => Charset::Viscii,
+            5 => Charset::Viqr,
+            6 => Charset::Cp1258,
+            _ => return None,
+        })
+    }
+
+    /// Matching engine output encoding
+    fn encoding(self) -> Option<Encoding> {
+        match self {
+            Charset::Unicode => Some(Encoding::Unicode),
+            Charset::UnicodeNfd => Some(Encoding::UnicodeNfd),
+            Charset::Tcvn3 => Some(Encoding::Tcvn3),
+            Charset::VniWin => Some(Encoding::VniWin),
+            Charset::Viscii => Some(Encoding::Viscii),
+            Charset::Viqr | Charset::Cp1258 => None,
+        }
+    }
+}
+
+/// Convert text between charsets
+pub fn convert(text: &str, from: Charset, to: Charset) -> String {
+    if from == to {
+        return text.to_string();
+    }
+    encode(&decode(text, from), to)
+}
+
+/// Decode text to Unicode precomposed (NFC)
+pub fn decode(text: &str, from: Charset) -> String {
+    match from {
+        Charset::Viqr => decode_viqr(text),
+        Charset::Cp1258 => decode_cp1258(text),
+        _ => encoding::decode(text, from.encoding().unwrap_or_default()),
+    }
+}
+
+/// Encode Unicode precomposed (NFC) text
+pub fn encode(text: &str, to: Charset) -> String {
+    match to {
+        Charset::Viqr => encode_viqr(text),
+        Charset::Cp1258 => encode_cp1258(text),
+        _ => {
+            let enc = to.encoding().unwrap_or_default();
+            let mut out = Vec::with_capacity(text.len());
+            for c in text.chars() {
+                encoding::encode(c, enc, &mut out);
+            }
+            out.into_iter().collect()
+        }
+    }
+}
+
+// ============================================================
+// VIQR
+// ============================================================
+
+/// VIQR mark symbols: [sắc, huyền, hỏi, ngã, nặng]
+const VIQR_MARKS: [char; 5] = ['\'', '`', '?', '~', '.'];
+
+/// Escape character for literal symbols (to\. = "to.")
+const VIQR_ESCAPE: char = '\\';
+
+fn is_viqr_symbol(c: char) -> bool {
+    VIQR_MARKS.contains(&c) || matches!(c, '^' | '(' | '+')
+}
+
+/// VIQR symbol for a tone modifier on a base key
+fn viqr_tone(key: u16, t: u8) -> Option<char> {
+    match t {
+        tone::CIRCUMFLEX => Some('^'),
+        tone::HORN if key == keys::A => Some('('),
+        tone::HORN => Some('+'),
+        _ => None,
+    }
+}
+
+fn encode_viqr(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() * 2);
+    // Whether the last emitted letter could absorb a following symbol / 'd'
+    let mut prev: Option<chars::ParsedChar> = None;
+
+    for c in text.chars() {
+        let parsed = chars::parse_char(c);
+        match parsed {
+            Some(p) if p.stroke => out.push_str(if p.caps { "DD" } else { "dd" }),
+            Some(p) if keys::is_vowel(p.key) => {
+                out.extend(chars::to_char(p.key, p.caps, 0, 0));
+                out.extend(viqr_tone(p.key, p.tone));
+                if p.mark > mark::NONE {
+                    out.push(VIQR_MARKS[(p.mark - 1) as usize]);
+                }
+            }
+            _ => {
+                let after_vowel = prev.is_some_and(|p| keys::is_vowel(p.key) && !p.stroke);
+                let after_d = prev.is_some_and(|p| p.key == keys::D && !p.stroke);
+                if (after_vowel && is_viqr_symbol(c))
+                    || (after_d && matches!(c, 'd' | 'D'))
+                    || c == VIQR_ESCAPE
+                {
+                    out.push(VIQR_ESCAPE);
+                }
+                out.push(c);
+            }
+        }
+        prev = parsed;
+    }
+    out
+}
+
+fn decode_viqr(text: &str) -> String {
+    let input: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+
+    while i < input.len() {
+        let c = input[i];
+        i += 1;
+
+        if c == VIQR_ESCAPE {
+            match input.get(i) {
+                Some(&next) if is_viqr_symbol(next) || matches!(next, 'd' | 'D' | '\\') => {
+                    out.push(next);
+                    i += 1;
+                }
+                _ => out.push(c),
+            }
+            continue;
+        }
+
+        let Some(p) = chars::parse_char(c).filter(|p| p.tone == 0 && p.mark == 0) else {
+            out.push(c);
+            continue;
+        };
+
+        if p.key == keys::D && !p.stroke {
+            if matches!(input.get(i), Some('d' | 'D')) {
+                out.push(chars::get_d(p.caps));
+                i += 1;
+            } else {
+                out.push(c);
+            }
+            continue;
+        }
+        if !keys::is_vowel(p.key) {
+            out.push(c);
+            continue;
+        }
+
+        let mut t = tone::NONE;
+        if let Some(&sym) = input.get(i) {
+            if let Some(candidate) = [tone::CIRCUMFLEX, tone::HORN]
+                .into_iter()
+                .find(|&t| viqr_tone(p.key, t) == Some(sym))
+            {
+                if chars::to_char(p.key, p.caps, candidate, 0)
+                    .and_then(chars::parse_char)
+                    .is_some_and(|q| q.tone == candidate)
+                {
+                    t = candidate;
+                    i += 1;
+                }
+            }
+        }
+
+        let mut m = mark::NONE;
+        if let Some(pos) = input
+            .get(i)
+            .and_then(|s| VIQR_MARKS.iter().position(|m| m == s))
+        {
+            m = pos as u8 + 1;
+            i += 1;
+        }
+
+        out.extend(chars::to_char(p.key, p.caps, t, m));
+    }
+    out
+}
+
+// ============================================================
+// WINDOWS-1258
+// ============================================================
+
+/// Bytes where Windows-1258 differs from Latin-1 in the Vietnamese range
+const CP1258_DIFF: [(u8, char); 14] = [
+    (0xC3, 'Ă'),
+    (0xCC, '\u{0300}'), // huyền
+    (0xD0, 'Đ'),
+    (0xD2, '\u{0309}'), // hỏi
+    (0xD5, 'Ơ'),
+    (0xDD, 'Ư'),
+    (0xDE, '\u{0303}'), // ngã
+    (0xE3, 'ă'),
+    (0xEC, '\u{0301}'), // sắc
+    (0xF0, 'đ'),
+    (0xF2, '\u{0323}'), // nặng
+    (0xF5, 'ơ'),
+    (0xFD, 'ư'),
+    (0xFE, '₫'),
+];
+
+/// Windows-1258 byte for a character, if it has a single-byte form
+fn cp1258_byte(c: char) -> Option<u8> {
+    if let Some(&(b, _)) = CP1258_DIFF.iter().find(|&&(_, ch)| ch == c) {
+        return Some(b);
+    }
+    let b = encoding::char_to_byte(c)?;
+    // Latin-1 positions reused by Windows-1258 are not available
+    (!CP1258_DIFF.iter().any(|&(d, _)| d == b)).then_some(b)
+}
+
+fn encode_cp1258(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if let Some(b) = cp1258_byte(c) {
+            out.push(encoding::byte_to_char(b));
+            continue;
+        }
+        // Letter not precomposed: base letter + combining tone mark
+        let split = chars::parse_char(c)
+            .filter(|p| p.mark > mark::NONE)
+            .and_then(|p| {
+                let base = chars::to_char(p.key, p.caps, p.tone, mark::NONE)?;
+                let combining = encoding::mark_combining(p.mark)?;
+                Some((cp1258_byte(base)?, cp1258_byte(combining)?))
+            });
+        match split {
+            Some((base, combining)) => {
+                out.push(encoding::byte_to_char(base));
+                out.push(encoding::byte_to_char(combining));
+            }
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+fn decode_cp1258(text: &str) -> String {
+    let unicode: String = text
+        .chars()
+        .map(|c| {
+            encoding::char_to_byte(c)
+                .and_then(|b| CP1258_DIFF.iter().find(|&&(d, _)| d == b))
+                .map_or(c, |&(_, ch)| ch)
+        })
+        .collect();
+    encoding::compose(&unicode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "Cộng hoà xã hội chủ nghĩa Việt Nam. ĐỘC LẬP - TỰ DO? Đâu, ạ!";
+
+    #[test]
+    fn test_roundtrip_all_charsets() {
+        for id in 0..=6 {
+            let cs = Charset::from_id(id).unwrap();
+            if cs == Charset::Tcvn3 {
+                continue; // marked capitals lose case
+            }
+            let encoded = convert(TEXT, Charset::Unicode, cs);
+            assert_eq!(convert(&encoded, cs, Charset::Unicode), TEXT, "{:?}", cs);
+        }
+        assert_eq!(Charset::from_id(7), None);
+    }
+
+    #[test]
+    fn test_viqr() {
+        assert_eq!(encode("Việt Nam", Charset::Viqr), "Vie^.t Nam");
+        assert_eq!(encode("đường", Charset::Viqr), "ddu+o+`ng");
+        assert_eq!(encode("Ăn", Charset::Viqr), "A(n");
+        // Literal symbols after vowels are escaped
+        assert_eq!(encode("to.", Charset::Viqr), "to\\.");
+        assert_eq!(encode("add", Charset::Viqr), "ad\\d");
+        assert_eq!(decode("to\\. ngu+o+`i", Charset::Viqr), "to. người");
+        assert_eq!(decode("DDa^?u", Charset::Viqr), "Đẩu");
+        // Tone symbol that doesn't apply is kept
+        assert_eq!(decode("i^", Charset::Viqr), "i^");
+    }
+
+    #[test]
+    fn test_cp1258() {
+        // Precomposed where available, combining mark otherwise
+        assert_eq!(encode("á", Charset::Cp1258), "á");
+        assert_eq!(encode("ấ", Charset::Cp1258), "âì");
+        assert_eq!(encode("ì", Charset::Cp1258), "iÌ");
+        assert_eq!(encode("đ", Charset::Cp1258), "ð");
+        assert_eq!(decode("Viêòt", Charset::Cp1258), "Việt");
+    }
+
+    #[test]
+    fn test_legacy_to_legacy() {
+        let vni = convert("Tiếng Việt", Charset::Unicode, Charset::VniWin);
+        assert_eq!(vni, "Tieáng Vieät");
+        let viqr = convert(&vni, Charset::VniWin, Charset::Viqr);
+        assert_eq!(viqr, "Tie^'ng Vie^.t");
+    }
+}
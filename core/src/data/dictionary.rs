@@ -0,0 +1,372 @@
+//! Vietnamese Syllable Dictionary
+//!
+//! Attested Vietnamese syllables, used to tell real syllables ("tiếng")
+//! from phonotactically legal but non-existent ones ("tiểng").
+//!
+//! The list holds the syllables found in the Vietnamese gettext catalogs
+//! shipped with common Linux packages, plus a hand-checked pass over every
+//! legal initial + rhyme + mark combination (reduplicatives, southern and
+//! central speech, loanwords). Tone-placement variants (hoà/hòa) count once.
+//! Rare syllables may still be missing, so validation is off by default.
+//!
+//! Stored compactly, one rhyme per line: the rhyme (with tone modifiers,
+//! without mark) followed by `<initial><marks>` groups, where `_` is the
+//! empty initial and marks are digits: 0=ngang 1=sắc 2=huyền 3=hỏi 4=ngã 5=nặng.
+//!
+//! ```text
+//! ương _0 th0235 tr0123 ...   →  ương thương thường thưởng thượng trương trướng trường trưởng
+//! ```
+//!
+//! Syllables are spelled in full (`ngh`, `gh`, `k` before e/ê/i/y, `qu`);
+//! for `gi` the rhyme's leading `i` is dropped (gi + iêng → giêng).
+//! Lookups compare letters, modifiers and mark, not mark position.
+
+use super::chars::{self, mark};
+use super::frequency;
+use super::keys;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+const SYLLABLES: &str = "\
+a _012345 b012345 c01235 ch02345 d0245 đ012345 g012345 gi012345 h01235 kh0123 l012345 m012345 n012345 ng02345 nh01234 p0 ph0123 qu01235 r012345 s012345 t012345 th0123 tr01234 v012345 x012345
+e _012345 b012345 ch01234 d023 đ0234 gh012345 gi3 h0125 k01234 kh024 l012345 m012345 n1235 ngh012 nh012345 ph0123 qu03 r1234 s0234 t01234 th0123 tr035 v01234 x013
+ê _01235 b012345 ch0125 d0124 đ01235 gh0125 gi0 h02345 k01235 kh0125 l02345 m01245 n0235 ngh025 nh34 p0 ph015 qu015 r01234 s025 t012345 th01235 tr0245 v0125 x015
+i _012345 b012345 ch01235 d01245 đ024 gh02 gi0123 h0123 k012345 kh0123 l01235 m012345 n023 ngh0345 nh012345 p0 ph01235 qu012345 r0234 s02345 t01235 th01235 tr01245 v012345 x01235
+o _012345 b012345 c01235 ch01234 d0125 đ012345 g024 gi123 h0125 kh012 l012345 m012345 n01345 ng12345 nh0135 ph012 r02345 s0235 t023 th01235 tr0235 v012345 x013
+ô _01235 b012345 c012345 ch24 d04 đ012345 g0234 gi4 h012345 kh013 l012345 m012345 n0345 ng012345 nh013 p0 ph013 r02345 s012345 t01235 th013 tr0123 v0124 x023
+ơ _012345 b012345 c0124 ch01235 d01234 đ01245 g234 gi023 h0123 kh02 l012345 m012345 n01345 ng01245 nh01245 ph0123 qu023 r01245 s012345 t012 th01235 tr01235 v012345 x0134
+u _012345 b0125 c012345 ch0123 d025 đ01235 g025 gi4 h012345 kh01235 l012345 m012345 n025 ng02345 nh0134 ph012345 r01234 s0125 t01235 th01235 tr0125 v01245 x0125
+ư _0125 b5 c012345 ch0124 d0145 đ123 g2 gi4 h01235 kh0123 l02345 n4 ng01245 nh023 s0135 t01235 th01235 tr124 x13
+y _013 h013 k012345 l0125 m245 qu012345 s4 t01235 v4 x0
+ai _013 b012345 c01234 ch01234 d012345 đ01245 g0124 gi034 h012345 kh013 l012345 m012345 n01235 ng012345 nh012345 ph013 qu01 r01234 s01234 t01235 th01234 tr01345 v012345 x12
+ao _0123 b012345 c01235 ch012345 d0125 đ01235 g0125 gi013 h012345 kh01235 l012345 m012345 n01245 ng01245 nh01245 ph012 qu2 r01235 s01235 t01235 th01235 tr0125 v12 x01235
+au _0 b123 c01235 ch01 d2 đ01 g2 gi2 h012 kh1 l0123 m012 n1 ng2 nh023 ph0 qu25 r0 s01 t012 th01 tr0 x2
+ay _01 b023 c0125 ch01235 d01245 đ0123 g01234 gi24 h014 kh013 l0135 m0123 n012345 ng012 nh0135 ph0 qu02 r0123 s03 t012 th023 tr3 v01235 x03
+ây _0123 b012345 c0125 ch012 d012345 đ012345 g02345 gi0124 h023 kh3 l012345 m01235 n1235 ng0125 nh0234 ph03 qu012345 r0234 s135 t013 th0123 tr023 v012345 x03
+âu _0123 b0125 c01235 ch01245 d012345 đ01235 g012 gi125 h01235 kh013 l0235 m012345 n0145 ng0124 nh0235 ph4 r02 s012 t01235 th012 tr0123 v13 x01
+eo _012345 b012345 ch012 d35 đ0124 gh5 gi0 h0123 k012345 kh01 l012345 m0125 n03 ngh245 nh012345 ph2 qu0125 r013 s5 t05 th05 tr01235 v0125 x123
+êu _0 b025 đ23 gi4 h1 k02 kh02 l0123 m1 n01 ngh0 r05 s1 t01 th023 tr05
+ia _3 b025 ch024 d24 đ2345 h0 k02 kh15 l025 m13 n04 ngh14 ph15 r0235 t013 th012 tr5 v13 x123
+iu _03 b1245 ch05 d125 đ25 h0 k4 kh0 l125 m1 n15 ngh5 nh15 ph05 r012 t123 th03 tr24 v1 x1235
+iêu b13 ch0123 d0245 đ01235 h135 k01235 kh01 l0245 m013 n05 ngh0 nh024 ph01 r0 s0 t01234 th01235 tr025 x0
+yêu _013
+oa _02 ch01 d5 đ15 g1 h01235 kh013 l01234 ng0 nh2 s0 t01235 th03 x0124
+oai _013 ch024 đ12 h235 kh013 l025 ng125 nh02 s12 t125 th0135 x23
+oay h01 kh1 l0 ng13 t1 x01
+oe _015 ch125 h02 kh013 l012 ng01 nh2 t012 x02
+oi _013 b1 c01234 ch0125 d0245 đ125 g135 gi23 h013 kh013 l012345 m01235 n012 ng012 nh1 ph02 r01235 s0123 t0123 th01235 tr15 v012 x01
+ôi _013 b0125 c01245 ch12345 d01245 đ012345 g15 gi5 h01235 kh01 l01245 m0124 n012345 ng02 nh12 ph013 r124 s02 t0125 th013 tr012345 v015 x013
+ơi _012 b0123 c0234 ch012 d02 đ125 g35 gi12 h02345 kh03 l0235 m12 n01 ng025 nh2 ph01 qu1 r02 s135 t012 th012 tr2 v0125 x0123
+ua _01235 b123 c03 ch012 d045 đ024 gi45 h2 kh0 l125 m012 n0 nh5 r235 s35 t013 th023 v0 x0
+uê _13 d5 h0125 kh0 nh5 t15 th01 x023
+ui _013 b125 c12345 ch0124 d125 đ0234 g24 h135 kh0 l012345 m01234 n12 ng23 nh235 ph03 r035 s023 t0135 th0135 tr0245 v02 x012
+uy _013 ch2 d0 h013 kh05 l45 ng05 nh5 ph0 s0 t01235 th1235 tr05 x1
+ưa _0125 b12345 c0135 ch01234 d0125 đ01 gi4 h1 kh1 l012345 m013 n134 ng1235 nh25 ph15 r1345 s0134 t0125 th023 tr0 v245 x03
+ưi ch3 g3 ng3
+ưu _0 b03 c012345 h045 kh1 l05 m0 ng0 s03 t35 tr2
+ươi b03 c124 d1 l124 m02 ng02 r012345 s3 t013
+ươu b01 h0 kh1 r5
+uôi b23 c15 ch0124 d4 đ013 m01245 n01 ng05 r23 s1 t3 x0
+uya kh0
+uây kh01 ng35
+oeo _5 kh02 ng1235
+uyu kh35
+ac _1 b15 c15 ch15 d15 đ15 g15 gi1 h15 kh15 l15 m15 n15 ng15 nh15 ph15 qu15 r15 s15 t15 th15 tr15 v15 x15
+ach _15 b15 c15 ch15 đ15 g5 h15 kh15 l15 m15 n1 ng15 nh1 ph15 qu15 r15 s15 t15 th15 tr15 v15 x15
+am _013 b015 c01235 ch25 d15 đ01235 g0 gi013 h01245 kh013 l02345 n015 ng2 nh0123 ph25 r0125 s0125 t0125 th013 tr1235 v25 x01235
+an _01 b01235 c01235 ch015 d01245 đ01235 g0125 gi01234 h01245 kh0123 l01245 m01245 n01235 ng0125 nh0245 ph01235 qu0123 r0125 s01235 t0123 th013 tr012 v01245 x015
+ang _01 b0123 c0123 ch025 d0125 đ01234 g02 gi0135 h01245 kh01235 l012345 m012345 n025 ng012 nh0124 ph023 qu01234 r01235 s0123 t01235 th013 tr01235 v012345 x012
+anh _013 b01235 c01235 ch01235 d023 đ0123 g012 gi02 h0245 kh013 l012345 m012345 n025 ng25 nh0123 p0 ph0 qu015 r0234 s0123 t0125 th01235 tr012 v0125 x02
+ap _1 c15 ch5 đ15 g1 gi1 h5 kh5 l15 m5 n5 ng1 nh1 ph1 r15 s15 t15 th15 tr1 v1 x1
+at _15 b15 c1 ch15 d15 đ15 g5 gi15 h15 kh1 l15 m15 n15 ng15 nh15 ph15 qu15 r15 s15 t15 th1 tr1 v15 x1
+ăc _1 b1 c15 ch15 d15 đ15 g5 gi15 h15 kh15 l1 m15 n15 ng1 nh1 ph1 qu15 r1 s15 t15 th1 tr15 v15 x1
+ăm _04 b0245 c0125 ch02 d025 đ012 g0125 gi02 h02 kh01 l01 m1 n012 ng01 nh012 qu15 r012 s01 t012 th013 tr01 v2 x0
+ăn _0 b013 c0125 ch01245 d25 đ15 g12 h123 kh0 l0235 m125 n0125 ng01 nh01245 ph0 qu0125 r012 s014 t12 th02 tr02 v0125 x01
+ăng _013 b012 c03 ch0235 d0235 đ012345 g01 gi02 h012 kh023 l01235 m01 n01245 nh01235 ph03 qu035 r025 s0124 t025 th0123 tr01 v0123 x02
+ăp _1 b1 c15 ch15 đ1 g15 kh1 l15 n1 nh1 qu15 r1 s1 t1 th1
+ăt _1 b15 c1 ch15 d15 đ15 g15 gi15 h1 kh1 l15 m15 ng15 nh15 ph1 qu15 r15 s1 t1 th1 v15 x1
+âc b15 c1 ch5 g1 gi1 l1 n1 nh1 t1 x1
+âm _01235 b12345 c0123 ch01235 d01245 đ01245 g1245 gi01245 h0235 kh03 l012345 m0234 n15 ng012345 nh01235 ph3 r01245 s012345 t0123 th012345 tr024 v0 x023
+ân _013 b1235 c01235 ch01235 d01245 đ245 g02 gi25 h015 kh13 l012345 m012345 n12 ng0123 nh01345 ph01245 qu012345 r025 s012 t01235 th02345 tr0125 v012345
+âng b0 c0 d0 đ1 h4 l0 n01 ng3 nh0 qu2 t02 v02
+âp _15 b15 c15 ch15 d15 đ5 g15 gi5 h15 kh15 l15 m15 n15 ng15 nh15 ph15 qu5 r5 s15 t15 th15 tr5 v15 x15
+ât _1 b15 c15 ch15 d5 đ15 g5 gi5 h1 kh15 l15 m15 ng15 nh15 ph15 qu15 r15 s15 t15 th15 tr5 v15
+ec _1 h1 m1 s1 t1 v1
+em _013 b3 ch12 d12 đ0 gh1 gi2 h023 k0124 kh0 l01235 m1 n01 nh0235 r2 s1 t012 th2 v5 x01
+en _013 b1245 ch01245 đ02 gh02 gi0 h0125 k012 kh02 l01234 m02 n1 ngh0145 nh05 ph2 qu0 r012 s0 t03 th05 tr4 v015 x01
+eng b01 k03 l03 ph2 r3 x23
+ep _15 b15 ch15 d15 đ5 gh1 h5 k15 kh1 l15 m1 n15 nh15 ph1 t1 th1 x15
+et _1 b15 ch15 d5 đ15 gh1 h1 k15 kh1 l15 m15 n15 ngh15 nh1 ph1 qu15 r1 s15 t15 th1 tr1 v15 x15
+êch _1 b5 ch15 đ1 gh1 h1 k15 l15 m1 ngh15 nh1 ph1 qu5 s5 t1 th1 tr5 x15
+êm _01 ch04 đ0125 gi1 k2 m2 n015 t0 th02
+ên b01235 d2 đ12 h013 k02 l0 m12 n0125 ngh3 nh5 ph0 qu01 r02 s01 t0 tr03 v5
+ênh _24 b025 ch0123 d2 đ03 gh2 h03 k023 kh02345 l025 m015 ngh024 ph2 s23 t034 th0 v03 x0
+êp _1 b15 n1 r5 s15 t5 x15
+êt b15 ch1 d5 gi1 h15 k1 l15 m15 n1 ph1 qu15 r15 s5 t1 th1 tr5 v15
+ich _15 b15 ch15 d15 đ15 h15 k15 kh15 l15 m15 n15 ngh5 nh1 ph15 r15 s1 t15 th15 tr15 v1 x15
+im _03 b123 ch012 d02 gh02 gi2 h1 k02 l0235 m134 nh1 ph01 r0 s0 t0123 th1
+in _03 b05 ch013 d4 gi2 k12 m25 n1 ngh2 nh25 p0 ph0 r5 s0 t01 th2 v05 x035
+inh _02 b01235 ch01234 d014 đ012345 h234 k0123 kh03 l012345 m02 n05 ngh04 nh3 ph123 r023 s012 t012345 th01235 tr0245 v045 x0124
+ip b5 ch1 d5 h1 k15 l1 m1 nh15 r5 t1
+it _15 b15 ch15 đ15 h1 k5 kh15 l1 m15 n15 ngh5 nh15 p1 qu15 r15 s15 t15 th15 tr5 v15 x15
+oc _15 b15 c15 ch15 d15 đ5 g1 h15 kh1 l15 m15 n15 ng15 nh15 ph1 r15 s15 t15 th15 tr15 v15 x15
+om _03 b0134 c02 ch23 d023 đ0123 g0 h0124 kh0125 l02345 m134 n0 ng123 nh01234 r12 s25 t0124 th23 v2 x15
+on _03 b01235 c0235 ch05 d25 đ012 g25 gi2 h023 kh0 l0235 m0125 n014 ng0135 nh135 r1 s0 t023 th0 tr25 v01 x0
+ong _01234 b01235 c01245 ch01234 d01234 đ01235 g35 gi01235 h01235 l012345 m01235 n0125 ng135 nh01234 ph0123 r02 s012 t0235 th0234 tr0235 v0245 x0
+op _15 b1 c15 ch1 g1 h15 l1 m15 ng5 nh1 t15 th1
+ot _15 b15 c15 ch1 đ15 g15 gi5 h1 l15 m15 n5 ng15 nh15 ph5 r1 s15 t15 th15 tr15 v15 x1
+ôc _15 b15 c15 ch1 d1 đ15 g15 h15 kh1 l15 m15 n15 ng1 nh1 ph1 qu1 r5 s15 t15 th15 tr1 v1 x15
+ôm _012 b02 c125 ch0234 đ1 g012 h03 l123 m2 n025 ng2 nh023 r0 s2 t0 tr5 x023
+ôn _023 b01235 c012 ch0125 d2 đ0125 g0 h0234 kh01 l1235 m02 n0 ng01235 nh135 ph2 r135 s2 t0123 th013 tr015 v12 x015
+ông _013 b012345 c01235 ch0123 d025 đ0125 g02 gi012 h0123 kh013 l0235 m0125 n02 ng0234 nh025 ph0235 r01245 s0123 t0123 th01 tr012 v012 x013
+ôp _15 b15 c15 ch5 đ15 g5 gi5 h5 l15 n5 ng5 ph1 r5 s5 t1 th5 x1
+ôt b15 c15 ch15 d15 đ15 g5 h15 l15 m15 n1 ng15 nh15 ph1 r15 s15 t15 th1 x1
+ơm _4 b025 c0124 ch0123 d5 đ02 g12 h25 l35 m1 n0 ng5 nh1 r013 s1 t3 th01 x2
+ơn _01 b45 c014 ch012 d12 đ0123 g25 gi4 h012 l1235 m013 ng15 nh0123 ph034 r2345 s023 t135 tr015 v23 x1
+ơp b5 ch1 đ1 h15 kh1 l15 n1 ng5 nh1 r5 t5
+ơt _15 b15 c5 ch15 đ5 h15 l15 n1 ng1 nh15 ph1 qu1 r1 s1 th1 tr1 v15 x1
+uc _15 b5 c15 ch15 d5 đ15 g5 gi5 h15 kh15 l15 m15 n15 ng5 nh15 ph15 r15 s15 t15 th15 tr15 v5 x15
+um _012 b5 c125 ch01245 d125 đ12 gi2 h2 kh012 l0125 m134 n1 ng35 nh1 r0125 s05 t012345 th03 tr2 x0125
+un _23 b123 c01234 ch023 d01 đ025 g2 gi0 h02 l12345 m0235 ng12 nh14 ph012 r0123 s15 t3 th0 tr2 v05 x0
+ung _0123 b01235 c012345 ch0123 d0245 đ012345 g2 h012 kh0235 l012345 m234 n0124 ng12 nh0124 ph0125 r01235 s01234 t0125 th012345 tr01245 v0245 x0123
+up _15 b15 c15 ch5 đ15 gi1 h15 l15 m1 n1 ng5 r15 s15 t15 th5 x15
+ut _15 b15 c15 ch15 đ15 g1 h15 kh5 l15 m15 n1 ng15 nh15 ph15 r15 s15 t15 th15 tr1 v15 x1
+ưc _15 b15 c5 ch15 đ15 h15 kh1 l5 m15 n15 ng5 nh1 ph1 r15 s15 t1 th15 tr5 v5 x15
+ưng _013 b0123 c013 ch012345 d0235 đ125 g2 h01234 kh1245 l02345 m02 n15 ng023 nh04 ph02 r0235 s02345 t023 th024 tr012 v24 x013
+ưt b1 c1 d15 đ1 gi5 l1 m1 n1 nh15 ph15 r1 s1 v1
+iêc b1 ch1 d15 đ1 gh1 gi1 l1 nh1 t15 th1 v5 x15
+iêm b1 ch01 d0124 đ123 h123 k1235 kh01 l01245 n025 ngh045 nh145 ph1 t0125 th23 v0 x03
+iên b01235 ch012 d045 đ0235 h01235 k015 kh013 l0124 m01245 n0 ngh0125 nh0 ph012 t01245 th01235 tr235 v02345 x03
+iêng b1 ch0 đ1 gi012 k01234 kh045 l0135 m135 n24 ngh0 r02 s0 t1 th0 v1 x23
+iêp ch1 d15 đ5 h15 k1 kh1 l1 ngh5 nh1 t15 th15
+iêt b15 ch1 d15 đ1 h1 k15 kh1 l5 m15 n1 ngh5 nh5 ph5 r15 s1 t15 th15 tr15 v15 x1
+yêm _13
+yên _01 qu01235
+yêt _1 qu15
+yt qu15
+uôc _1 b5 c15 ch15 đ1 g15 l15 n5 nh1 r1 t15 th15
+uôm _0 b2 ch0 l5 m4 nh15 th5
+uôn _1 b02 c125 ch2 đ2 kh0 l02 m015 ng2 s0 t02 th04
+uông _13 b02 c12 ch025 đ0 h1 kh0 l12 m01234 n0 r25 s02 t02 th23 tr02 v0 x12
+uôt b15 ch15 đ5 m1 n15 r5 s1 t15 v15
+ươc _1 b1 c15 ch15 d5 đ15 kh1 l5 m1 n1 ng15 nh5 ph1 r1 t1 th15 tr15 x15
+ươm _01 b01 c2 ch2 đ5 g025 h2 l25 n2 r12 t01 th5
+ươn _024 b0 l025 m15 ph4 r1 s2 th2 tr2 v025
+ương _0 b01 c024 ch0123 d0245 đ02 g05 gi02 h0123 kh04 l0245 m02 n01 ng345 nh1245 ph025 r02 s015 t01235 th0235 tr012345 v0135 x013
+ươp _1 c1 m1 n5 t1
+ươt _1 kh1 l15 m15 ph5 r5 s15 th15 tr5 v5
+oac _1 ch5 h15 kh1 l5 ng15 t15 x5
+oach _15 h5 l5 x5
+oan _013 ch1 d4 đ01235 h01245 kh013 l025 n4 ng045 s15 t012 v0 x02
+oang _0 ch01235 đ23 h023 kh013 l012345 nh1 s5 t012 th013 x0235
+oanh _0 d0 h25 kh03 l0 ng3 t0 x2
+oat _1 đ5 h5 kh1 l15 s15 t1 th15
+oăc h5 ng15
+oăn _2 kh01 ng2 th0 x01
+oăng h4 l2 ng2 th1
+oăt _15 ch1 h1 l1 ng15 th1
+oen h0 kh0 nh3
+oet ch15 kh1 l15 nh15 t15 x5
+uân _3 ch3 h01 kh03 l035 nh25 t0124 th245 tr0 x03
+uâng kh0
+uât _1 kh1 l5 s1 t1 th5 tr1 x1
+uyên _03 ch01235 d0 h0245 kh013 l015 ng0245 nh4 t0123 th02 tr25 x01
+uyêt d5 h15 kh1 ng5 t15 th1 x1
+uynh h02 kh02
+uych _5 h15
+uyt b1 h1 s15 t1 x15
+oong b0 g2 l0 t0 x0
+uêch ch5 h1 kh1 ng5 t1 x5
+ưm _2 h23
+uênh ch1 h0 kh2 t2 x2
+ynh qu12
+oăm _0 h1
+ooc h1 m1 s1
+uyp t1
+uơ h0 th3
+oam nh2
+";
+
+struct Index {
+    /// Base syllable (no mark) → bitmask of attested marks
+    marks: HashMap<String, u8>,
+    /// Every prefix of every base, with đ written as d
+    prefixes: HashSet<String>,
+}
+
+fn index() -> &'static Index {
+    static INDEX: OnceLock<Index> = OnceLock::new();
+    INDEX.get_or_init(|| {
+        let mut marks = HashMap::new();
+        for line in SYLLABLES.lines() {
+            let mut groups = line.split_whitespace();
+            let Some(rhyme) = groups.next() else {
+                continue;
+            };
+            for group in groups {
+                let split = group
+                    .find(|c: char| c.is_ascii_digit())
+                    .unwrap_or(group.len());
+                let (initial, digits) = group.split_at(split);
+                let base = match initial {
+                    "_" => rhyme.to_string(),
+                    "gi" if rhyme.starts_with('i') => format!("g{}", rhyme),
+                    _ => format!("{}{}", initial, rhyme),
+                };
+                let bits = digits.bytes().fold(0u8, |bits, d| bits | 1 << (d - b'0'));
+                *marks.entry(base).or_insert(0) |= bits;
+            }
+        }
+
+        let mut prefixes = HashSet::new();
+        for base in marks.keys() {
+            let letters = without_stroke(base);
+            for (i, _) in letters.char_indices().skip(1) {
+                prefixes.insert(letters[..i].to_string());
+            }
+            prefixes.insert(letters);
+        }
+
+        Index { marks, prefixes }
+    })
+}
+
+/// Split syllable into lowercase base (letters with modifiers) and mark
+///
+/// None if it has non-letters or more than one mark.
+fn split(syllable: &str) -> Option<(String, u8)> {
+    let mut base = String::with_capacity(syllable.len());
+    let mut m = mark::NONE;
+    for c in syllable.chars() {
+        match chars::parse_char(c) {
+            Some(p) if p.stroke => base.push(chars::get_d(false)),
+            Some(p) if keys::is_vowel(p.key) => {
+                if p.mark != mark::NONE {
+                    if m != mark::NONE {
+                        return None;
+                    }
+                    m = p.mark;
+                }
+                base.push(chars::to_char(p.key, false, p.tone, mark::NONE)?);
+            }
+            _ if c.is_ascii_alphabetic() => base.push(c.to_ascii_lowercase()),
+            _ => return None,
+        }
+    }
+    Some((base, m))
+}
+
+fn without_stroke(s: &str) -> String {
+    s.replace(chars::get_d(false), "d")
+}
+
+/// Check if a syllable is attested (any case, either tone placement)
+pub fn contains(syllable: &str) -> bool {
+    split(syllable).is_some_and(|(base, m)| {
+        index()
+            .marks
+            .get(&base)
+            .is_some_and(|&bits| bits & (1 << m) != 0)
+    })
+}
+
+/// Check if an attested syllable starts with these letters
+///
+/// Marks are ignored and d may still become đ, so partially typed
+/// syllables ("tiê" → tiếng, "duô" → đuốc) are accepted.
+pub fn is_prefix(letters: &str) -> bool {
+    split(letters).is_some_and(|(base, _)| index().prefixes.contains(&without_stroke(&base)))
+}
+
+/// Check if a syllable is in the common-syllable frequency list
+/// (any case, either tone placement)
+pub fn is_common(syllable: &str) -> bool {
+    static COMMON: OnceLock<HashSet<(String, u8)>> = OnceLock::new();
+    let common = COMMON.get_or_init(|| {
+        frequency::SYLLABLES
+            .iter()
+            .filter_map(|&(s, _)| split(s))
+            .collect()
+    });
+    split(syllable).is_some_and(|s| common.contains(&s))
+}
+
+/// Number of attested syllables
+pub fn syllable_count() -> usize {
+    index()
+        .marks
+        .values()
+        .map(|bits| bits.count_ones() as usize)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains() {
+        for s in [
+            "tiếng",
+            "Việt",
+            "người",
+            "giếng",
+            "gì",
+            "quyển",
+            "khuya",
+            "khuỷu",
+            "ĐƯỜNG",
+        ] {
+            assert!(contains(s), "{}", s);
+        }
+        // Either tone placement
+        assert!(contains("hoà") && contains("hòa"));
+        // Legal shape, not a word
+        for s in ["tiểng", "lẩng", "kừ", "dứt1", "a\u{0301}", "ốá"] {
+            assert!(!contains(s), "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_is_prefix() {
+        assert!(is_prefix("tiê"));
+        assert!(is_prefix("ngh"));
+        assert!(is_prefix("duô")); // đuốc
+        assert!(is_prefix("tiế")); // mark ignored
+        assert!(!is_prefix("ngha"));
+        assert!(!is_prefix("tiêx"));
+    }
+
+    #[test]
+    fn test_is_common() {
+        assert!(is_common("có"));
+        assert!(is_common("Khoẻ"));
+        assert!(is_common("khỏe")); // either tone placement
+                                    // Attested, but rare
+        assert!(contains("dóc"));
+        assert!(!is_common("dóc"));
+    }
+
+    #[test]
+    fn test_frequency_syllables_attested() {
+        let missing: Vec<&str> = frequency::SYLLABLES
+            .iter()
+            .map(|&(s, _)| s)
+            .filter(|s| !contains(s))
+            .collect();
+        assert!(missing.is_empty(), "{:?}", missing);
+    }
+
+    #[test]
+    fn test_size() {
+        let n = syllable_count();
+        assert!((5500..7000).contains(&n), "{}", n);
+        // Less common, but real words
+        for s in [
+            "choàng", "thuở", "mép", "gợn", "gỉ", "tóp", "boong", "loét", "quặp", "nhuốm", "bẹp",
+            "cóp", "toẹt", "tuýp", "mấp",
+        ] {
+            assert!(contains(s), "{}", s);
+        }
+    }
+}
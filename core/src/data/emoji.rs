@@ -0,0 +1,193 @@
+//! Emoji and Symbol Shortcodes
+//!
+//! Built-in `:name:` table for the emoji shortcode option: each symbol has
+//! Vietnamese aliases (no diacritics, words joined by `-`) and English ones,
+//! e.g. `:tim:` / `:heart:` → ❤️, `:mui-ten:` / `:arrow:` → →.
+//!
+//! Aliases are lowercase ASCII letters, digits and `-`, matched against the
+//! raw keystrokes, so Telex/VNI transforms while typing the name don't matter.
+
+use std::sync::OnceLock;
+
+/// Symbol → aliases (Vietnamese first)
+const EMOJI: &[(&str, &[&str])] = &[
+    // Faces
+    ("😀", &["cuoi", "smile", "grin"]),
+    ("😂", &["cuoi-ra-nuoc-mat", "joy", "lol"]),
+    ("🤣", &["cuoi-lan", "rofl"]),
+    ("😊", &["mim-cuoi", "blush"]),
+    ("😉", &["nhay-mat", "wink"]),
+    ("😍", &["me", "heart-eyes"]),
+    ("😘", &["hon", "kiss"]),
+    ("😎", &["ngau", "cool", "sunglasses"]),
+    ("🤔", &["suy-nghi", "thinking"]),
+    ("😐", &["binh-thuong", "neutral"]),
+    ("😴", &["ngu", "sleep"]),
+    ("😢", &["buon", "sad", "cry"]),
+    ("😭", &["khoc", "sob"]),
+    ("😡", &["gian", "angry"]),
+    ("😱", &["so", "scream"]),
+    ("😅", &["cuoi-trua", "sweat-smile"]),
+    ("🙄", &["dao-mat", "eye-roll"]),
+    ("🤗", &["om", "hug"]),
+    ("🥳", &["tiec", "party"]),
+    ("😇", &["thien-than", "angel"]),
+    // Hands and people
+    ("👍", &["like", "thumbs-up", "ok"]),
+    ("👎", &["dislike", "thumbs-down"]),
+    ("👏", &["vo-tay", "clap"]),
+    ("🙏", &["cam-on", "cau-nguyen", "pray", "thanks"]),
+    ("👋", &["chao", "wave", "hi"]),
+    ("💪", &["manh-me", "muscle", "strong"]),
+    ("🤝", &["bat-tay", "handshake"]),
+    ("✌️", &["chien-thang", "victory", "peace"]),
+    ("👀", &["mat", "eyes"]),
+    // Hearts and symbols
+    ("❤️", &["tim", "heart", "love"]),
+    ("💔", &["tim-vo", "broken-heart"]),
+    ("💯", &["tram-diem", "100"]),
+    ("🔥", &["lua", "fire", "hot"]),
+    ("⭐", &["ngoi-sao", "sao", "star"]),
+    ("✨", &["lap-lanh", "sparkles"]),
+    ("🎉", &["chuc-mung", "tada", "congrats"]),
+    ("🎂", &["banh-sinh-nhat", "sinh-nhat", "birthday", "cake"]),
+    ("🎁", &["qua", "gift"]),
+    ("💡", &["y-tuong", "idea", "bulb"]),
+    ("⚠️", &["canh-bao", "warning"]),
+    ("✅", &["xong", "dung", "check", "done"]),
+    ("❌", &["sai", "huy", "cross", "x"]),
+    ("❓", &["hoi", "question"]),
+    ("❗", &["chu-y", "exclamation"]),
+    ("📌", &["ghim", "pin"]),
+    ("📞", &["dien-thoai", "phone"]),
+    ("📧", &["thu", "email"]),
+    ("💰", &["tien", "money"]),
+    ("🚀", &["ten-lua", "rocket"]),
+    ("🐛", &["loi", "bug"]),
+    // Nature, food, places
+    ("☀️", &["mat-troi", "nang", "sun"]),
+    ("🌙", &["mat-trang", "trang", "moon"]),
+    ("🌧️", &["mua", "rain"]),
+    ("🌸", &["hoa-dao", "hoa", "blossom", "flower"]),
+    ("🍜", &["pho", "bun", "noodles", "ramen"]),
+    ("🍚", &["com", "rice"]),
+    ("☕", &["ca-phe", "coffee"]),
+    ("🍺", &["bia", "beer"]),
+    ("🧧", &["li-xi", "red-envelope"]),
+    ("🐶", &["cho", "dog"]),
+    ("🐱", &["meo", "cat"]),
+    ("🇻🇳", &["viet-nam", "vn", "vietnam"]),
+    // Arrows and typography
+    ("→", &["mui-ten", "mui-ten-phai", "arrow", "right"]),
+    ("←", &["mui-ten-trai", "left"]),
+    ("↑", &["mui-ten-len", "up"]),
+    ("↓", &["mui-ten-xuong", "down"]),
+    ("↔", &["hai-chieu", "left-right"]),
+    ("⇒", &["suy-ra", "implies"]),
+    ("⇔", &["tuong-duong", "iff"]),
+    ("≈", &["xap-xi", "approx"]),
+    ("≠", &["khac", "not-equal"]),
+    ("≤", &["nho-hon-bang", "le"]),
+    ("≥", &["lon-hon-bang", "ge"]),
+    ("±", &["cong-tru", "plus-minus"]),
+    ("×", &["nhan", "times"]),
+    ("÷", &["chia", "divide"]),
+    ("∞", &["vo-cuc", "infinity"]),
+    ("°", &["do", "degree"]),
+    ("•", &["cham", "bullet"]),
+    ("…", &["ba-cham", "ellipsis"]),
+    ("—", &["gach-dai", "em-dash"]),
+    ("–", &["gach-ngang", "en-dash"]),
+    ("«", &["ngoac-kep-trai", "laquo"]),
+    ("»", &["ngoac-kep-phai", "raquo"]),
+    ("©", &["ban-quyen", "copyright"]),
+    ("®", &["dang-ky", "registered"]),
+    ("™", &["thuong-hieu", "tm", "trademark"]),
+    ("₫", &["dong", "vnd"]),
+    ("€", &["euro"]),
+    ("✓", &["dau-tich", "tick"]),
+];
+
+/// (alias, symbol) sorted by alias
+fn aliases() -> &'static [(&'static str, &'static str)] {
+    static SORTED: OnceLock<Vec<(&'static str, &'static str)>> = OnceLock::new();
+    SORTED.get_or_init(|| {
+        let mut all: Vec<_> = EMOJI
+            .iter()
+            .flat_map(|&(symbol, names)| names.iter().map(move |&name| (name, symbol)))
+            .collect();
+        all.sort_unstable();
+        all
+    })
+}
+
+/// Symbol for an alias (case-insensitive)
+pub fn lookup(name: &str) -> Option<&'static str> {
+    let name = name.to_ascii_lowercase();
+    let all = aliases();
+    all.binary_search_by(|&(alias, _)| alias.cmp(name.as_str()))
+        .ok()
+        .map(|i| all[i].1)
+}
+
+/// Aliases starting with `prefix` (case-insensitive) and their symbols,
+/// in alias order, at most `limit`
+pub fn search(prefix: &str, limit: usize) -> Vec<(&'static str, &'static str)> {
+    let prefix = prefix.to_ascii_lowercase();
+    let all = aliases();
+    let start = all.partition_point(|&(alias, _)| alias < prefix.as_str());
+    all[start..]
+        .iter()
+        .take_while(|(alias, _)| alias.starts_with(prefix.as_str()))
+        .take(limit)
+        .copied()
+        .collect()
+}
+
+/// Char allowed in an alias
+pub fn is_alias_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aliases_valid_and_unique() {
+        let all = aliases();
+        for pair in all.windows(2) {
+            assert_ne!(pair[0].0, pair[1].0, "duplicate alias");
+        }
+        for (alias, _) in all {
+            assert!(alias.chars().all(is_alias_char), "{alias}");
+            assert_eq!(*alias, alias.to_ascii_lowercase());
+        }
+    }
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(lookup("tim"), Some("❤️"));
+        assert_eq!(lookup("HEART"), Some("❤️"));
+        assert_eq!(lookup("mui-ten"), Some("→"));
+        assert_eq!(lookup("khong-co"), None);
+    }
+
+    #[test]
+    fn test_search() {
+        let names: Vec<&str> = search("mui", 10).iter().map(|(a, _)| *a).collect();
+        assert_eq!(
+            names,
+            [
+                "mui-ten",
+                "mui-ten-len",
+                "mui-ten-phai",
+                "mui-ten-trai",
+                "mui-ten-xuong"
+            ]
+        );
+        assert_eq!(search("mui", 2).len(), 2);
+        assert!(search("zzz", 5).is_empty());
+        assert_eq!(search("", 3).len(), 3);
+    }
+}
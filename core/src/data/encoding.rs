@@ -0,0 +1,640 @@
+//! Output Encodings
+//!
+//! Maps composed Vietnamese characters to the form sent to the platform:
+//! - Unicode precomposed (NFC): á = U+00E1
+//! - Unicode decomposed (NFD): á = a + U+0301
+//! - TCVN3 (ABC): á = 0xB8
+//! - VNI-Windows: á = "aù" (base letter + mark byte)
+//! - VISCII: á = 0xE1
+//!
+//! Backspace counts depend on the encoding, so every encoder also reports
+//! how many units a character occupies on screen.
+
+use super::chars::{self, mark, tone};
+use super::keys;
+
+/// Output encoding for engine results
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Encoding {
+    /// Unicode precomposed (NFC)
+    #[default]
+    Unicode = 0,
+    /// Unicode decomposed (NFD)
+    UnicodeNfd = 1,
+    /// TCVN3 / ABC (.VnTime fonts)
+    Tcvn3 = 2,
+    /// VNI-Windows (VNI-Times fonts), up to 2 bytes per letter
+    VniWin = 3,
+    /// VISCII (RFC 1456)
+    Viscii = 4,
+}
+
+impl Encoding {
+    /// Get encoding by id (unknown ids fall back to Unicode)
+    pub fn from_id(id: u8) -> Self {
+        match id {
+            1 => Encoding::UnicodeNfd,
+            2 => Encoding::Tcvn3,
+            3 => Encoding::VniWin,
+            4 => Encoding::Viscii,
+            _ => Encoding::Unicode,
+        }
+    }
+}
+
+/// Combining marks (dấu thanh)
+pub const COMBINING_GRAVE: char = '\u{0300}'; // huyền
+pub const COMBINING_ACUTE: char = '\u{0301}'; // sắc
+pub const COMBINING_HOOK: char = '\u{0309}'; // hỏi
+pub const COMBINING_TILDE: char = '\u{0303}'; // ngã
+pub const COMBINING_DOT_BELOW: char = '\u{0323}'; // nặng
+
+/// Combining vowel modifiers (dấu phụ)
+pub const COMBINING_CIRCUMFLEX: char = '\u{0302}'; // â, ê, ô
+pub const COMBINING_HORN: char = '\u{031B}'; // ơ, ư
+pub const COMBINING_BREVE: char = '\u{0306}'; // ă
+
+/// Combining character for a mark
+pub fn mark_combining(m: u8) -> Option<char> {
+    match m {
+        mark::SAC => Some(COMBINING_ACUTE),
+        mark::HUYEN => Some(COMBINING_GRAVE),
+        mark::HOI => Some(COMBINING_HOOK),
+        mark::NGA => Some(COMBINING_TILDE),
+        mark::NANG => Some(COMBINING_DOT_BELOW),
+        _ => None,
+    }
+}
+
+/// Combining character for a tone modifier (horn on 'a' is breve)
+fn tone_combining(key: u16, t: u8) -> Option<char> {
+    match t {
+        tone::CIRCUMFLEX => Some(COMBINING_CIRCUMFLEX),
+        tone::HORN if key == keys::A => Some(COMBINING_BREVE),
+        tone::HORN => Some(COMBINING_HORN),
+        _ => None,
+    }
+}
+
+/// Decompose a Vietnamese character into base letter + combining marks (NFD)
+///
+/// Combining marks follow canonical order: horn (class 216) and dot below (220)
+/// come before circumflex, breve and the other marks (230).
+/// Example: ậ → a + U+0323 + U+0302, ợ → o + U+031B + U+0323
+pub fn decompose(c: char, out: &mut Vec<char>) {
+    let parsed = match chars::parse_char(c) {
+        Some(p) if !p.stroke && (p.tone > 0 || p.mark > 0) => p,
+        _ => {
+            out.push(c);
+            return;
+        }
+    };
+
+    let base = chars::to_char(parsed.key, parsed.caps, 0, 0).unwrap_or(c);
+    out.push(base);
+
+    let modifier = tone_combining(parsed.key, parsed.tone);
+    let mark = mark_combining(parsed.mark);
+    match (modifier, mark) {
+        (Some(m), Some(COMBINING_DOT_BELOW)) if m != COMBINING_HORN => {
+            out.push(COMBINING_DOT_BELOW);
+            out.push(m);
+        }
+        _ => out.extend(modifier.into_iter().chain(mark)),
+    }
+}
+
+/// Compose base letters + combining marks into precomposed characters (NFC)
+///
+/// Only Vietnamese combinations are composed; other text is left unchanged.
+pub fn compose(s: &str) -> String {
+    let mut out: Vec<char> = Vec::with_capacity(s.len());
+    for c in s.chars() {
+        let composed = out
+            .last()
+            .and_then(|&prev| chars::parse_char(prev))
+            .filter(|p| !p.stroke)
+            .and_then(|mut p| {
+                match c {
+                    COMBINING_CIRCUMFLEX if p.tone == 0 => p.tone = tone::CIRCUMFLEX,
+                    COMBINING_BREVE if p.tone == 0 && p.key == keys::A => p.tone = tone::HORN,
+                    COMBINING_HORN if p.tone == 0 && p.key != keys::A => p.tone = tone::HORN,
+                    _ => {
+                        let m = (mark::SAC..=mark::NANG).find(|&m| mark_combining(m) == Some(c))?;
+                        if p.mark != 0 {
+                            return None;
+                        }
+                        p.mark = m;
+                    }
+                }
+                // Combination must exist (e.g. no circumflex on 'i')
+                chars::to_char(p.key, p.caps, p.tone, p.mark)
+                    .filter(|&ch| chars::parse_char(ch) == Some(p))
+            });
+
+        match composed {
+            Some(ch) => {
+                out.pop();
+                out.push(ch);
+            }
+            None => out.push(c),
+        }
+    }
+    out.into_iter().collect()
+}
+
+// ============================================================
+// LEGACY 8-BIT CHARSETS
+// ============================================================
+//
+// Legacy fonts render raw bytes, so bytes are emitted as the Windows-1252
+// characters that carry them (0xA0-0xFF map to the same code points).
+
+/// Vowel rows used by the legacy tables: a, ă, â, e, ê, i, o, ô, ơ, u, ư, y
+fn vowel_row(key: u16, t: u8) -> Option<usize> {
+    Some(match (key, t) {
+        (keys::A, tone::NONE) => 0,
+        (keys::A, tone::HORN) => 1,
+        (keys::A, tone::CIRCUMFLEX) => 2,
+        (keys::E, tone::NONE) => 3,
+        (keys::E, tone::CIRCUMFLEX) => 4,
+        (keys::I, tone::NONE) => 5,
+        (keys::O, tone::NONE) => 6,
+        (keys::O, tone::CIRCUMFLEX) => 7,
+        (keys::O, tone::HORN) => 8,
+        (keys::U, tone::NONE) => 9,
+        (keys::U, tone::HORN) => 10,
+        (keys::Y, tone::NONE) => 11,
+        _ => return None,
+    })
+}
+
+/// Windows-1252 characters for bytes 0x80-0x9F (undefined bytes keep their C1 code point)
+const CP1252_HIGH: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ',
+];
+
+/// Character carrying a legacy byte
+pub fn byte_to_char(b: u8) -> char {
+    match b {
+        0x80..=0x9F => CP1252_HIGH[(b - 0x80) as usize],
+        _ => b as char,
+    }
+}
+
+/// Legacy byte carried by a character (inverse of [`byte_to_char`])
+pub fn char_to_byte(c: char) -> Option<u8> {
+    match c as u32 {
+        0x00..=0x7F | 0xA0..=0xFF => Some(c as u8),
+        _ => CP1252_HIGH
+            .iter()
+            .position(|&h| h == c)
+            .map(|i| 0x80 + i as u8),
+    }
+}
+
+/// TCVN3 (ABC, .VnTime) lowercase bytes: [none, sắc, huyền, hỏi, ngã, nặng]
+///
+/// TCVN3 has no uppercase letters with marks: uppercase fonts (.VnTimeH)
+/// render the lowercase codes as capitals, so Á is encoded like á.
+const TCVN3_LOWER: [[u8; 6]; 12] = [
+    [b'a', 0xB8, 0xB5, 0xB6, 0xB7, 0xB9], // a
+    [0xA8, 0xBE, 0xBB, 0xBC, 0xBD, 0xC6], // ă
+    [0xA9, 0xCA, 0xC7, 0xC8, 0xC9, 0xCB], // â
+    [b'e', 0xD0, 0xCC, 0xCE, 0xCF, 0xD1], // e
+    [0xAA, 0xD5, 0xD2, 0xD3, 0xD4, 0xD6], // ê
+    [b'i', 0xDD, 0xD7, 0xD8, 0xDC, 0xDE], // i
+    [b'o', 0xE3, 0xDF, 0xE1, 0xE2, 0xE4], // o
+    [0xAB, 0xE8, 0xE5, 0xE6, 0xE7, 0xE9], // ô
+    [0xAC, 0xED, 0xEA, 0xEB, 0xEC, 0xEE], // ơ
+    [b'u', 0xF3, 0xEF, 0xF1, 0xF2, 0xF4], // u
+    [0xAD, 0xF8, 0xF5, 0xF6, 0xF7, 0xF9], // ư
+    [b'y', 0xFD, 0xFA, 0xFB, 0xFC, 0xFE], // y
+];
+
+/// TCVN3 uppercase bytes without mark
+const TCVN3_UPPER: [u8; 12] = [
+    b'A', 0xA1, 0xA2, b'E', 0xA3, b'I', b'O', 0xA4, 0xA5, b'U', 0xA6, b'Y',
+];
+
+const TCVN3_D: (u8, u8) = (0xAE, 0xA7); // (đ, Đ)
+
+/// VNI-Windows lowercase sequences: [none, sắc, huyền, hỏi, ngã, nặng]
+///
+/// A base letter followed by a modifier byte; â/ă/ê/ô carry a combined
+/// modifier+mark byte, ơ/ư/i and ỵ have single-byte forms.
+/// Uppercase is the Latin-1 uppercase of each byte (Ấ = "AÁ").
+const VNI_LOWER: [[&str; 6]; 12] = [
+    ["a", "aù", "aø", "aû", "aõ", "aï"],  // a
+    ["aê", "aé", "aè", "aú", "aü", "aë"], // ă
+    ["aâ", "aá", "aà", "aå", "aã", "aä"], // â
+    ["e", "eù", "eø", "eû", "eõ", "eï"],  // e
+    ["eâ", "eá", "eà", "eå", "eã", "eä"], // ê
+    ["i", "í", "ì", "æ", "ó", "ò"],       // i
+    ["o", "où", "oø", "oû", "oõ", "oï"],  // o
+    ["oâ", "oá", "oà", "oå", "oã", "oä"], // ô
+    ["ô", "ôù", "ôø", "ôû", "ôõ", "ôï"],  // ơ
+    ["u", "uù", "uø", "uû", "uõ", "uï"],  // u
+    ["ö", "öù", "öø", "öû", "öõ", "öï"],  // ư
+    ["y", "yù", "yø", "yû", "yõ", "î"],   // y
+];
+
+const VNI_D: (char, char) = ('ñ', 'Ñ'); // (đ, Đ)
+
+/// VISCII (RFC 1456) bytes for every Vietnamese letter outside ASCII
+const VISCII: [(char, u8); 134] = [
+    ('Ẳ', 0x02),
+    ('Ẵ', 0x05),
+    ('Ẫ', 0x06),
+    ('Ỷ', 0x14),
+    ('Ỹ', 0x19),
+    ('Ỵ', 0x1E),
+    ('Ạ', 0x80),
+    ('Ắ', 0x81),
+    ('Ằ', 0x82),
+    ('Ặ', 0x83),
+    ('Ấ', 0x84),
+    ('Ầ', 0x85),
+    ('Ẩ', 0x86),
+    ('Ậ', 0x87),
+    ('Ẽ', 0x88),
+    ('Ẹ', 0x89),
+    ('Ế', 0x8A),
+    ('Ề', 0x8B),
+    ('Ể', 0x8C),
+    ('Ễ', 0x8D),
+    ('Ệ', 0x8E),
+    ('Ố', 0x8F),
+    ('Ồ', 0x90),
+    ('Ổ', 0x91),
+    ('Ỗ', 0x92),
+    ('Ộ', 0x93),
+    ('Ợ', 0x94),
+    ('Ớ', 0x95),
+    ('Ờ', 0x96),
+    ('Ở', 0x97),
+    ('Ị', 0x98),
+    ('Ỏ', 0x99),
+    ('Ọ', 0x9A),
+    ('Ỉ', 0x9B),
+    ('Ủ', 0x9C),
+    ('Ũ', 0x9D),
+    ('Ụ', 0x9E),
+    ('Ỳ', 0x9F),
+    ('Õ', 0xA0),
+    ('ắ', 0xA1),
+    ('ằ', 0xA2),
+    ('ặ', 0xA3),
+    ('ấ', 0xA4),
+    ('ầ', 0xA5),
+    ('ẩ', 0xA6),
+    ('ậ', 0xA7),
+    ('ẽ', 0xA8),
+    ('ẹ', 0xA9),
+    ('ế', 0xAA),
+    ('ề', 0xAB),
+    ('ể', 0xAC),
+    ('ễ', 0xAD),
+    ('ệ', 0xAE),
+    ('ố', 0xAF),
+    ('ồ', 0xB0),
+    ('ổ', 0xB1),
+    ('ỗ', 0xB2),
+    ('Ỡ', 0xB3),
+    ('Ơ', 0xB4),
+    ('ộ', 0xB5),
+    ('ờ', 0xB6),
+    ('ở', 0xB7),
+    ('ị', 0xB8),
+    ('Ự', 0xB9),
+    ('Ứ', 0xBA),
+    ('Ừ', 0xBB),
+    ('Ử', 0xBC),
+    ('ơ', 0xBD),
+    ('ớ', 0xBE),
+    ('Ư', 0xBF),
+    ('À', 0xC0),
+    ('Á', 0xC1),
+    ('Â', 0xC2),
+    ('Ã', 0xC3),
+    ('Ả', 0xC4),
+    ('Ă', 0xC5),
+    ('ẳ', 0xC6),
+    ('ẵ', 0xC7),
+    ('È', 0xC8),
+    ('É', 0xC9),
+    ('Ê', 0xCA),
+    ('Ẻ', 0xCB),
+    ('Ì', 0xCC),
+    ('Í', 0xCD),
+    ('Ĩ', 0xCE),
+    ('ỳ', 0xCF),
+    ('Đ', 0xD0),
+    ('ứ', 0xD1),
+    ('Ò', 0xD2),
+    ('Ó', 0xD3),
+    ('Ô', 0xD4),
+    ('ạ', 0xD5),
+    ('ỷ', 0xD6),
+    ('ừ', 0xD7),
+    ('ử', 0xD8),
+    ('Ù', 0xD9),
+    ('Ú', 0xDA),
+    ('ỹ', 0xDB),
+    ('ỵ', 0xDC),
+    ('Ý', 0xDD),
+    ('ỡ', 0xDE),
+    ('ư', 0xDF),
+    ('à', 0xE0),
+    ('á', 0xE1),
+    ('â', 0xE2),
+    ('ã', 0xE3),
+    ('ả', 0xE4),
+    ('ă', 0xE5),
+    ('ữ', 0xE6),
+    ('ẫ', 0xE7),
+    ('è', 0xE8),
+    ('é', 0xE9),
+    ('ê', 0xEA),
+    ('ẻ', 0xEB),
+    ('ì', 0xEC),
+    ('í', 0xED),
+    ('ĩ', 0xEE),
+    ('ỉ', 0xEF),
+    ('đ', 0xF0),
+    ('ự', 0xF1),
+    ('ò', 0xF2),
+    ('ó', 0xF3),
+    ('ô', 0xF4),
+    ('õ', 0xF5),
+    ('ỏ', 0xF6),
+    ('ọ', 0xF7),
+    ('ụ', 0xF8),
+    ('ù', 0xF9),
+    ('ú', 0xFA),
+    ('ũ', 0xFB),
+    ('ủ', 0xFC),
+    ('ý', 0xFD),
+    ('ợ', 0xFE),
+    ('Ữ', 0xFF),
+];
+
+/// Encode a Vietnamese letter as TCVN3 bytes
+fn encode_tcvn3(c: char, out: &mut Vec<char>) {
+    let Some(p) = chars::parse_char(c).filter(|p| p.tone > 0 || p.mark > 0 || p.stroke) else {
+        out.push(c);
+        return;
+    };
+    let byte = if p.stroke {
+        if p.caps {
+            TCVN3_D.1
+        } else {
+            TCVN3_D.0
+        }
+    } else {
+        let Some(row) = vowel_row(p.key, p.tone) else {
+            out.push(c);
+            return;
+        };
+        if p.caps && p.mark == 0 {
+            TCVN3_UPPER[row]
+        } else {
+            TCVN3_LOWER[row][p.mark as usize]
+        }
+    };
+    out.push(byte_to_char(byte));
+}
+
+/// Encode a Vietnamese letter as a VNI-Windows byte sequence
+fn encode_vni(c: char, out: &mut Vec<char>) {
+    let Some(p) = chars::parse_char(c).filter(|p| p.tone > 0 || p.mark > 0 || p.stroke) else {
+        out.push(c);
+        return;
+    };
+    if p.stroke {
+        out.push(if p.caps { VNI_D.1 } else { VNI_D.0 });
+        return;
+    }
+    let Some(row) = vowel_row(p.key, p.tone) else {
+        out.push(c);
+        return;
+    };
+    for ch in VNI_LOWER[row][p.mark as usize].chars() {
+        if p.caps {
+            out.extend(ch.to_uppercase());
+        } else {
+            out.push(ch);
+        }
+    }
+}
+
+/// Encode a Vietnamese letter as a VISCII byte
+fn encode_viscii(c: char, out: &mut Vec<char>) {
+    match VISCII.iter().find(|(ch, _)| *ch == c) {
+        Some(&(_, b)) => out.push(byte_to_char(b)),
+        None => out.push(c),
+    }
+}
+
+/// Append a character in the given encoding
+pub fn encode(c: char, encoding: Encoding, out: &mut Vec<char>) {
+    match encoding {
+        Encoding::Unicode => out.push(c),
+        Encoding::UnicodeNfd => decompose(c, out),
+        Encoding::Tcvn3 => encode_tcvn3(c, out),
+        Encoding::VniWin => encode_vni(c, out),
+        Encoding::Viscii => encode_viscii(c, out),
+    }
+}
+
+/// Decode text in the given encoding back to Unicode precomposed (NFC)
+///
+/// Legacy charsets are matched longest-first against the encoded form of
+/// every Vietnamese letter (VNI-Windows uses up to 2 units per letter).
+/// Ambiguous TCVN3 codes decode to lowercase. Other characters are kept.
+pub fn decode(text: &str, encoding: Encoding) -> String {
+    match encoding {
+        Encoding::Unicode => text.to_string(),
+        Encoding::UnicodeNfd => compose(text),
+        Encoding::Tcvn3 | Encoding::VniWin | Encoding::Viscii => decode_legacy(text, encoding),
+    }
+}
+
+fn decode_legacy(text: &str, encoding: Encoding) -> String {
+    // Lowercase first so ambiguous TCVN3 codes resolve to lowercase
+    let letters = VISCII
+        .iter()
+        .map(|&(c, _)| c)
+        .filter(|c| c.is_lowercase())
+        .chain(VISCII.iter().map(|&(c, _)| c).filter(|c| c.is_uppercase()));
+    let mut table: Vec<(Vec<char>, char)> = Vec::with_capacity(VISCII.len());
+    for letter in letters {
+        let mut seq = Vec::with_capacity(2);
+        encode(letter, encoding, &mut seq);
+        if !table.iter().any(|(s, _)| *s == seq) {
+            table.push((seq, letter));
+        }
+    }
+
+    let input: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < input.len() {
+        let matched = [2, 1].iter().find_map(|&len| {
+            let seq = input.get(i..i + len)?;
+            table
+                .iter()
+                .find(|(s, _)| s.as_slice() == seq)
+                .map(|&(_, c)| (c, len))
+        });
+        match matched {
+            Some((c, len)) => {
+                out.push(c);
+                i += len;
+            }
+            None => {
+                out.push(input[i]);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Number of units a character occupies on screen in the given encoding
+pub fn encoded_len(c: char, encoding: Encoding) -> usize {
+    let mut out = Vec::with_capacity(4);
+    encode(c, encoding, &mut out);
+    out.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nfd(s: &str) -> String {
+        let mut out = Vec::new();
+        for c in s.chars() {
+            decompose(c, &mut out);
+        }
+        out.into_iter().collect()
+    }
+
+    #[test]
+    fn test_decompose() {
+        assert_eq!(nfd("á"), "a\u{0301}");
+        assert_eq!(nfd("ă"), "a\u{0306}");
+        assert_eq!(nfd("Ư"), "U\u{031B}");
+        assert_eq!(nfd("ấ"), "a\u{0302}\u{0301}");
+        assert_eq!(nfd("ậ"), "a\u{0323}\u{0302}");
+        assert_eq!(nfd("ợ"), "o\u{031B}\u{0323}");
+        assert_eq!(nfd("đ"), "đ");
+        assert_eq!(nfd("Việt"), "Vie\u{0323}\u{0302}t");
+    }
+
+    #[test]
+    fn test_compose_roundtrip() {
+        for word in ["Việt Nam", "người", "đường", "ặc", "Ợ", "thuở", "abc → xyz"]
+        {
+            assert_eq!(compose(&nfd(word)), word);
+        }
+        // Marks that can't combine are kept as-is
+        assert_eq!(compose("b\u{0301}"), "b\u{0301}");
+        assert_eq!(compose("i\u{0302}"), "i\u{0302}");
+    }
+
+    #[test]
+    fn test_encoded_len() {
+        assert_eq!(encoded_len('ệ', Encoding::Unicode), 1);
+        assert_eq!(encoded_len('ệ', Encoding::UnicodeNfd), 3);
+        assert_eq!(encoded_len('a', Encoding::UnicodeNfd), 1);
+        assert_eq!(encoded_len('ệ', Encoding::Tcvn3), 1);
+        assert_eq!(encoded_len('ệ', Encoding::VniWin), 2);
+        assert_eq!(encoded_len('ị', Encoding::VniWin), 1);
+        assert_eq!(encoded_len('ệ', Encoding::Viscii), 1);
+    }
+
+    fn encode_str(s: &str, encoding: Encoding) -> String {
+        let mut out = Vec::new();
+        for c in s.chars() {
+            encode(c, encoding, &mut out);
+        }
+        out.into_iter().collect()
+    }
+
+    #[test]
+    fn test_tcvn3() {
+        assert_eq!(encode_str("Việt Nam", Encoding::Tcvn3), "Vi\u{D6}t Nam");
+        assert_eq!(encode_str("đường", Encoding::Tcvn3), "\u{AE}\u{AD}\u{EA}ng");
+        // Uppercase with mark shares the lowercase code
+        assert_eq!(
+            encode_str("Á", Encoding::Tcvn3),
+            encode_str("á", Encoding::Tcvn3)
+        );
+        assert_eq!(encode_str("ĐƯ", Encoding::Tcvn3), "\u{A7}\u{A6}");
+    }
+
+    #[test]
+    fn test_vni_windows() {
+        assert_eq!(encode_str("Việt Nam", Encoding::VniWin), "Vieät Nam");
+        assert_eq!(encode_str("VIỆT NAM", Encoding::VniWin), "VIEÄT NAM");
+        assert_eq!(encode_str("đường", Encoding::VniWin), "ñöôøng");
+        assert_eq!(encode_str("chị ỵ", Encoding::VniWin), "chò î");
+        assert_eq!(encode_str("ắ", Encoding::VniWin), "aé");
+    }
+
+    #[test]
+    fn test_viscii() {
+        assert_eq!(encode_str("Việt", Encoding::Viscii), "Vi\u{AE}t");
+        // 0x80-0x9F are carried by Windows-1252 characters
+        assert_eq!(encode_str("Ạ", Encoding::Viscii), "€");
+        assert_eq!(encode_str("Ẵ", Encoding::Viscii), "\u{05}");
+    }
+
+    #[test]
+    fn test_viscii_covers_all_letters() {
+        let mut letters = std::collections::HashSet::new();
+        for key in [keys::A, keys::E, keys::I, keys::O, keys::U, keys::Y] {
+            for caps in [false, true] {
+                for t in [tone::NONE, tone::CIRCUMFLEX, tone::HORN] {
+                    for m in mark::NONE..=mark::NANG {
+                        letters.extend(chars::to_char(key, caps, t, m).filter(|c| !c.is_ascii()));
+                    }
+                }
+            }
+        }
+        letters.extend(['đ', 'Đ']);
+        assert_eq!(letters.len(), VISCII.len());
+
+        let bytes: std::collections::HashSet<u8> = VISCII.iter().map(|&(_, b)| b).collect();
+        assert_eq!(bytes.len(), VISCII.len());
+        for c in letters {
+            assert!(VISCII.iter().any(|(ch, _)| *ch == c), "{} missing", c);
+        }
+    }
+
+    #[test]
+    fn test_decode_roundtrip() {
+        let text = "Cộng hoà xã hội chủ nghĩa Việt Nam. ĐỘC LẬP - TỰ DO, ỵ ở đâu?";
+        for enc in [Encoding::UnicodeNfd, Encoding::VniWin, Encoding::Viscii] {
+            assert_eq!(decode(&encode_str(text, enc), enc), text, "{:?}", enc);
+        }
+        // TCVN3 loses case of marked capitals
+        assert_eq!(
+            decode(
+                &encode_str("Việt NAM ĐỘC", Encoding::Tcvn3),
+                Encoding::Tcvn3
+            ),
+            "Việt NAM ĐộC"
+        );
+    }
+
+    #[test]
+    fn test_byte_roundtrip() {
+        for b in 0..=255u8 {
+            assert_eq!(char_to_byte(byte_to_char(b)), Some(b));
+        }
+    }
+}
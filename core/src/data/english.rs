@@ -0,0 +1,171 @@
+//! English Word List
+//!
+//! Common English words consulted by auto-restore: when the raw keystrokes
+//! of a word spell a known English word ("days", "seem"), the typed
+//! Vietnamese transforms are undone unless the result is a common
+//! Vietnamese syllable (`frequency::SYLLABLES`: "cos" stays "có").
+//!
+//! The bundled list is compiled in. Larger lists can be loaded at runtime
+//! (whitespace-separated words, `#` starts a comment line).
+
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Bundled words (lowercase, whitespace-separated)
+const WORDS: &str = "
+a able about above accept access account across act action add address admin after again against age ago
+agree all allow almost alone along already also always am among amount and android angry animal another
+answer any anyone anything api app apple apply april are area argue arm army around arrive art article ask
+assert assets async at attack august author auto available average avoid away awesome axis
+baby back background backup bad bag balance ball bank bar base basic basis bass be bear beat beautiful because
+become bed beer before begin behind being believe below better between beyond big bill bit black
+blank block blog blood blue board boat body book books boost boot border born boss both bottom box boxes boy
+brain branch brand break bridge bring broken brother brown browser buffer bug bugs build building bus business
+busy but buy by byte bytes
+cache call called camera campus can cancel cannot car card cards care career carry cars case cases cash cat
+catch cause center certain chair chance change chapter char charge chart chat cheap check chief child choice
+choose chrome church circle city class classes clean clear click client close cloud club code coffee cold
+collect college color column come comment commit common company compare compile complete computer config confirm
+connect consider console const contact contain content context continue contract control convert cool copy core
+correct could count country course cover create credit cross css cursor custom customer cut
+daily damage dark data database date day days dead deal dear debug december decide deep default define delete
+deliver demo deploy describe design desk desktop detail dev develop device dialog did die diff different dinner
+direct director disable discuss disk display do doc docker docs does dog doing done door double down download
+draft draw dream dress drink drive driver drop during
+each early earth east easy eat edge edit editor effect effort either else email empty enable end energy engine
+enjoy enough enter entire entry environment error errors event events ever every exact example except exist
+exit expect expert explain export express extra eye
+face fact fail fair fall false family far farm fast father fax fear feature february fee feel few field
+file files fill film filter final find fine finish fire first fish fit five fix fixed fixes flag floor focus
+follow food foot for force form format forward found four fox free friday friend from front full fun function
+future
+game garden gas general get girl git give glass go goal god goes gold good got great green group grow guess guy
+had hair half hand handle happen happy hard has hash have he head header health hear heart heavy hello help
+her here high him his history hit hold home hope hot hotel hour house however html http huge human
+icon id idea if image import in include index info input insert inside install instead into is issue issues it
+item items its
+january java job jobs join json july jump june just
+keep key keys kid kill kind king kiss know
+label lake land language large last late later law lead learn least leave left legal less let letter level
+lib life light like limit line link linux list listen little live load local lock log login long look loop
+lose loss lot love low
+mac machine made mail main major make man manage many map march mark market master match matter may maybe
+me mean media meet member memory menu merge message meta method middle might mind minor minute miss mix mobile
+mode model modern moment monday money month more morning mother mouse move movie much music must my
+name native near need never new news next nice night no node none nor normal north not note notes nothing
+notice november null number
+object october of off offer office often oh ok old on once one only open option or order other our out output
+over owner
+package page pain paper parent park parse part party pass password past patch path pay people per perfect
+person phone photo pick picture piece place plan play player please plus point police policy pool poor pop
+port power press price print private pro problem process product program project push put python
+query question queue quick quite
+race radio raise random range rate raw reach read ready real really reason receive record red refresh release
+remove render repeat reply report repo request reset result return review rich right risk river road
+rock role room root rule run rust
+safe sale same sample saturday save say school score screen script search season seat second secret section see
+seem select sell send senior sense september serve server service session set setting settings seven several
+sex shall share she ship shop short should show side sign simple since single sister site six size skill skip
+sky small smile so social some someone something son song soon sort sound source south space speak special
+speed spend sport staff stage stand star start state status stay step still stock stop store story street
+string strong student study style submit success such summer sun sunday support sure switch system
+table take talk task tea teach team tech tell ten term text than thank thanks that the their
+them then there these they thing things think this those though three through thursday time title to today
+together token too tool tools top total touch toward town track trade train tree trip true trust try tuesday
+turn two type types
+under understand unit until up update upload upon url us use user users usual
+value very video view visit voice
+wait walk wall want war warm wash watch water way we web website wednesday week well were west what when
+where which while white who whole why wife will win window windows winter wish with within without woman word
+words work world would write wrong
+xml yard yeah year yes yet you young your
+zero zone zoom
+";
+
+fn bundled() -> &'static HashSet<&'static str> {
+    static SET: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    SET.get_or_init(|| WORDS.split_whitespace().collect())
+}
+
+/// Bundled list plus words loaded at runtime
+#[derive(Debug, Clone, Default)]
+pub struct EnglishWords {
+    loaded: HashSet<String>,
+}
+
+impl EnglishWords {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check if a word is known (case-insensitive)
+    pub fn contains(&self, word: &str) -> bool {
+        let word = word.to_lowercase();
+        bundled().contains(word.as_str()) || self.loaded.contains(&word)
+    }
+
+    /// Load words from text, returns the number of new words
+    pub fn load(&mut self, text: &str) -> usize {
+        let before = self.loaded.len();
+        for line in text.lines() {
+            if line.trim_start().starts_with('#') {
+                continue;
+            }
+            for word in line.split_whitespace() {
+                self.loaded.insert(word.to_lowercase());
+            }
+        }
+        self.loaded.len() - before
+    }
+
+    /// Load words from a file, returns the number of new words
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> io::Result<usize> {
+        Ok(self.load(&std::fs::read_to_string(path)?))
+    }
+
+    /// Drop loaded words (bundled list stays)
+    pub fn clear(&mut self) {
+        self.loaded.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled() {
+        let words = EnglishWords::new();
+        for w in ["docs", "mix", "fix", "box", "Text"] {
+            assert!(words.contains(w), "{}", w);
+        }
+        assert!(!words.contains("nguoi"));
+    }
+
+    #[test]
+    fn test_load() {
+        let mut words = EnglishWords::new();
+        assert_eq!(
+            words.load("# extra words\nRustacean\ncrate  crates\ncrate"),
+            3
+        );
+        assert!(words.contains("rustacean"));
+        assert!(!words.contains("#"));
+        words.clear();
+        assert!(!words.contains("crate"));
+        assert!(words.contains("docs"));
+    }
+
+    #[test]
+    fn test_load_file() {
+        let path = std::env::temp_dir().join("gonhanh_english_words_test.txt");
+        std::fs::write(&path, "serde\ntokio\n").unwrap();
+        let mut words = EnglishWords::new();
+        assert_eq!(words.load_file(&path).unwrap(), 2);
+        assert!(words.contains("tokio"));
+        std::fs::remove_file(&path).unwrap();
+        assert!(words.load_file(&path).is_err());
+    }
+}
@@ -0,0 +1,729 @@
+//! Vietnamese Syllable Frequencies
+//!
+//! Approximate occurrences per million syllables of common Vietnamese
+//! syllables and two-syllable words, used to rank accent restoration
+//! candidates ("khong co" → "không có").
+//!
+//! Syllables may be written with either tone placement (hoà / hòa);
+//! lookups compare letters, modifiers and mark, not mark position.
+
+/// Common syllables with approximate frequency
+pub const SYLLABLES: &[(&str, u32)] = &[
+    // Function words
+    ("của", 30000),
+    ("và", 28000),
+    ("có", 27000),
+    ("các", 22000),
+    ("là", 21000),
+    ("được", 20000),
+    ("trong", 19000),
+    ("không", 18000),
+    ("cho", 17000),
+    ("người", 16000),
+    ("với", 15000),
+    ("một", 15000),
+    ("những", 13000),
+    ("này", 12000),
+    ("đã", 12000),
+    ("để", 11000),
+    ("năm", 11000),
+    ("đến", 10000),
+    ("khi", 9500),
+    ("về", 9000),
+    ("ra", 9000),
+    ("từ", 8500),
+    ("như", 8000),
+    ("nhiều", 7500),
+    ("sẽ", 7500),
+    ("theo", 7000),
+    ("cũng", 7000),
+    ("tại", 6500),
+    ("nhà", 6500),
+    ("đó", 6500),
+    ("thì", 6000),
+    ("lại", 6000),
+    ("làm", 6000),
+    ("công", 6000),
+    ("việc", 5800),
+    ("nhưng", 5800),
+    ("phải", 5500),
+    ("trên", 5500),
+    ("nước", 5500),
+    ("đi", 5400),
+    ("vào", 5300),
+    ("học", 5200),
+    ("sự", 5200),
+    ("hiện", 5000),
+    ("ở", 5000),
+    ("hơn", 4800),
+    ("chỉ", 4800),
+    ("mà", 4800),
+    ("đầu", 4500),
+    ("đây", 4500),
+    ("thể", 4500),
+    ("ông", 4500),
+    ("nhất", 4500),
+    ("thời", 4400),
+    ("hàng", 4300),
+    ("nhân", 4300),
+    ("tôi", 4200),
+    ("anh", 4200),
+    ("biết", 4000),
+    ("cùng", 4000),
+    ("sau", 4000),
+    ("bị", 4000),
+    ("thành", 4000),
+    ("mới", 4000),
+    ("gì", 4000),
+    ("ta", 4000),
+    ("ngày", 4000),
+    ("động", 3900),
+    ("chính", 3900),
+    ("thế", 3800),
+    ("cả", 3800),
+    ("chúng", 3800),
+    ("bạn", 3700),
+    ("quan", 3700),
+    ("việt", 3600),
+    ("nam", 3600),
+    ("tình", 3500),
+    ("hội", 3500),
+    ("trường", 3500),
+    ("trình", 3500),
+    ("tế", 3500),
+    ("điều", 3400),
+    ("số", 3400),
+    ("phát", 3300),
+    ("nhiên", 3000),
+    ("triển", 3000),
+    ("hoạt", 3000),
+    ("tư", 3000),
+    ("sinh", 3000),
+    ("chức", 3000),
+    ("còn", 3000),
+    ("vì", 3000),
+    ("nói", 3000),
+    ("năng", 3000),
+    ("xã", 2800),
+    ("hình", 2800),
+    ("dụng", 2800),
+    ("quốc", 2800),
+    ("gia", 2800),
+    ("thông", 2800),
+    ("tin", 2800),
+    ("sản", 2700),
+    ("xuất", 2700),
+    ("kinh", 2700),
+    ("trung", 2700),
+    ("tiếp", 2600),
+    ("bộ", 2600),
+    ("tiền", 2600),
+    ("đồng", 2600),
+    ("cao", 2600),
+    ("thực", 2600),
+    ("đang", 2600),
+    ("trước", 2500),
+    ("dân", 2500),
+    ("tổ", 2500),
+    ("phần", 2500),
+    ("mình", 2500),
+    ("tiên", 2400),
+    ("thị", 2400),
+    ("hai", 2400),
+    ("đất", 2300),
+    ("điểm", 2300),
+    ("chủ", 2300),
+    ("quá", 2300),
+    ("giá", 2300),
+    ("hiệu", 2200),
+    ("lên", 2200),
+    ("lý", 2200),
+    ("vẫn", 2200),
+    ("xe", 2200),
+    ("thấy", 2200),
+    ("bằng", 2200),
+    ("lớn", 2200),
+    ("nếu", 2200),
+    ("hay", 2200),
+    ("ba", 2000),
+    ("giữa", 2000),
+    ("luật", 2000),
+    ("tháng", 2000),
+    ("sống", 2000),
+    ("muốn", 2000),
+    ("bà", 2000),
+    ("em", 2000),
+    ("trẻ", 2000),
+    ("chuyện", 2000),
+    ("cơ", 2000),
+    ("đội", 2000),
+    ("mặt", 1900),
+    ("khác", 1900),
+    ("tiếng", 1900),
+    ("nên", 1900),
+    ("rất", 1900),
+    ("dự", 1900),
+    ("án", 1800),
+    ("viên", 1800),
+    ("cô", 1800),
+    ("nội", 1800),
+    ("đúng", 1800),
+    ("chưa", 1800),
+    ("lúc", 1800),
+    ("ai", 1800),
+    ("mỗi", 1800),
+    ("đường", 1800),
+    ("con", 1800),
+    ("quả", 1700),
+    ("kết", 1700),
+    ("vấn", 1700),
+    ("đề", 1700),
+    ("yêu", 1700),
+    ("cầu", 1700),
+    ("phố", 1700),
+    ("tỉnh", 1700),
+    ("minh", 1600),
+    ("giải", 1600),
+    ("pháp", 1600),
+    ("quyết", 1600),
+    ("định", 1600),
+    ("cần", 1600),
+    ("mẹ", 1600),
+    ("đại", 1500),
+    ("huyện", 1500),
+    ("cha", 1500),
+    ("đời", 1500),
+    ("giờ", 1500),
+    ("tự", 1500),
+    ("do", 1500),
+    ("nào", 1500),
+    ("đưa", 1500),
+    ("xem", 1500),
+    ("ấy", 1500),
+    ("họ", 1500),
+    ("nó", 1500),
+    ("rồi", 1500),
+    ("nghĩa", 1400),
+    ("chí", 1400),
+    ("tâm", 1400),
+    ("hệ", 1400),
+    ("thống", 1400),
+    ("trò", 1300),
+    ("khách", 1300),
+    ("hợp", 1300),
+    ("tác", 1300),
+    ("tốt", 1300),
+    ("mạnh", 1300),
+    ("nhỏ", 1300),
+    ("ngoài", 1300),
+    ("hết", 1300),
+    ("vừa", 1300),
+    ("hành", 1300),
+    ("phòng", 1300),
+    ("tay", 1300),
+    ("trời", 1200),
+    ("thương", 1200),
+    ("mua", 1200),
+    ("bán", 1200),
+    ("ăn", 1200),
+    ("nghe", 1200),
+    ("nhìn", 1200),
+    ("thích", 1200),
+    ("chị", 1200),
+    ("bên", 1200),
+    ("cái", 1200),
+    ("qua", 1200),
+    ("gái", 1200),
+    ("trai", 1200),
+    ("đẹp", 1100),
+    ("sao", 1100),
+    ("vậy", 1100),
+    ("dưới", 1100),
+    ("giúp", 1100),
+    ("kiến", 1100),
+    ("chiến", 1100),
+    ("lượng", 1100),
+    ("tăng", 1100),
+    ("cuộc", 1100),
+    ("cách", 1100),
+    ("đều", 1100),
+    ("mức", 1000),
+    ("ý", 1000),
+    ("lần", 1000),
+    ("đồ", 1000),
+    ("bài", 1000),
+    ("luôn", 1000),
+    ("thật", 1000),
+    ("toàn", 1000),
+    ("bảo", 1000),
+    ("giáo", 1000),
+    ("nghiệp", 1000),
+    ("nhận", 1000),
+    ("cậu", 900),
+    ("giảm", 900),
+    ("hoàn", 900),
+    ("khoa", 900),
+    ("dục", 900),
+    ("bệnh", 900),
+    ("viện", 900),
+    ("tiêu", 900),
+    ("ngay", 900),
+    ("thứ", 900),
+    ("thanh", 900),
+    ("văn", 900),
+    ("hoá", 900),
+    ("doanh", 900),
+    ("dịch", 900),
+    ("vụ", 900),
+    ("điện", 900),
+    ("máy", 900),
+    ("tính", 900),
+    ("báo", 900),
+    ("tới", 900),
+    ("nghĩ", 900),
+    ("mọi", 900),
+    ("thường", 900),
+    ("bao", 900),
+    ("nay", 900),
+    ("chiếc", 900),
+    ("à", 800),
+    ("uống", 800),
+    ("sáng", 800),
+    ("tối", 800),
+    ("hà", 800),
+    ("cứu", 800),
+    ("phương", 800),
+    ("quân", 800),
+    ("bình", 800),
+    ("an", 800),
+    ("hiểu", 800),
+    ("cảm", 800),
+    ("kế", 800),
+    ("lực", 800),
+    ("hoặc", 800),
+    ("đổi", 800),
+    ("trở", 800),
+    ("hôm", 800),
+    ("ơi", 700),
+    ("thuốc", 700),
+    ("chuẩn", 700),
+    ("trợ", 700),
+    ("nhanh", 700),
+    ("nhật", 700),
+    ("nghiên", 700),
+    ("mạng", 700),
+    ("trang", 700),
+    ("sách", 700),
+    ("chơi", 700),
+    ("tranh", 700),
+    ("hoà", 700),
+    ("nhớ", 700),
+    ("xin", 700),
+    ("gặp", 700),
+    ("cây", 700),
+    ("lòng", 700),
+    ("hỏi", 700),
+    ("trả", 700),
+    ("lời", 700),
+    ("câu", 700),
+    ("tất", 700),
+    ("thuật", 700),
+    ("khả", 700),
+    ("phủ", 700),
+    ("quyền", 700),
+    ("chung", 700),
+    ("thay", 700),
+    ("chuyển", 700),
+    ("lấy", 700),
+    ("khó", 700),
+    ("đâu", 700),
+    ("mất", 700),
+    ("viết", 900),
+    ("ạ", 600),
+    ("nhé", 600),
+    ("tuy", 600),
+    ("chứ", 600),
+    ("đêm", 600),
+    ("chiều", 600),
+    ("tuần", 600),
+    ("hồ", 600),
+    ("hải", 600),
+    ("sử", 600),
+    ("địa", 600),
+    ("vật", 600),
+    ("truyền", 600),
+    ("bóng", 600),
+    ("trận", 600),
+    ("vô", 600),
+    ("cảnh", 600),
+    ("bác", 600),
+    ("chồng", 600),
+    ("vợ", 600),
+    ("ơn", 600),
+    ("chào", 600),
+    ("mở", 600),
+    ("cửa", 600),
+    ("biển", 600),
+    ("hoa", 600),
+    ("đọc", 600),
+    ("ít", 600),
+    ("mấy", 600),
+    ("đủ", 600),
+    ("thi", 600),
+    ("kỳ", 600),
+    ("thiết", 600),
+    ("mục", 600),
+    ("lợi", 600),
+    ("nhiệm", 600),
+    ("đặc", 600),
+    ("đơn", 600),
+    ("riêng", 600),
+    ("giữ", 600),
+    ("đặt", 600),
+    ("bắt", 600),
+    ("phép", 600),
+    ("vui", 600),
+    ("chắc", 600),
+    ("thôi", 600),
+    ("đấy", 600),
+    ("cũ", 500),
+    ("phút", 500),
+    ("cơm", 500),
+    ("bánh", 500),
+    ("hương", 500),
+    ("đà", 500),
+    ("ngân", 500),
+    ("khẩu", 500),
+    ("nhập", 500),
+    ("thoại", 500),
+    ("tức", 500),
+    ("phim", 500),
+    ("nhạc", 500),
+    ("ca", 500),
+    ("sĩ", 500),
+    ("đá", 500),
+    ("thắng", 500),
+    ("đấu", 500),
+    ("sát", 500),
+    ("phạm", 500),
+    ("khoẻ", 500),
+    ("chết", 500),
+    ("già", 500),
+    ("bé", 500),
+    ("đứa", 500),
+    ("biệt", 500),
+    ("chạy", 500),
+    ("đứng", 500),
+    ("ngồi", 500),
+    ("ngủ", 500),
+    ("đóng", 500),
+    ("bàn", 500),
+    ("xanh", 500),
+    ("vàng", 500),
+    ("trái", 500),
+    ("mắt", 500),
+    ("chân", 500),
+    ("chữ", 500),
+    ("mai", 500),
+    ("trăm", 500),
+    ("mười", 500),
+    ("bốn", 500),
+    ("thầy", 500),
+    ("lớp", 500),
+    ("kỹ", 500),
+    ("hoạch", 500),
+    ("chương", 500),
+    ("rõ", 500),
+    ("dễ", 500),
+    ("biến", 500),
+    ("cố", 500),
+    ("gửi", 500),
+    ("nhiêu", 500),
+    ("mày", 500),
+    ("cực", 500),
+    ("quê", 400),
+    ("cá", 400),
+    ("tiện", 400),
+    ("toán", 400),
+    ("cổ", 400),
+    ("hát", 400),
+    ("địch", 400),
+    ("ninh", 400),
+    ("tội", 400),
+    ("xử", 400),
+    ("sư", 400),
+    ("đau", 400),
+    ("cháu", 400),
+    ("quên", 400),
+    ("tạm", 400),
+    ("dậy", 400),
+    ("nằm", 400),
+    ("làng", 400),
+    ("sông", 400),
+    ("mưa", 400),
+    ("nóng", 400),
+    ("lá", 400),
+    ("đỏ", 400),
+    ("trắng", 400),
+    ("màu", 400),
+    ("kể", 400),
+    ("ngữ", 400),
+    ("nghìn", 400),
+    ("đôi", 400),
+    ("khăn", 400),
+    ("ích", 400),
+    ("trách", 400),
+    ("sai", 400),
+    ("tuyệt", 400),
+    ("buồn", 400),
+    ("sợ", 400),
+    ("lẽ", 400),
+    ("kia", 400),
+    ("hồi", 400),
+    ("thơ", 400),
+    ("xấu", 400),
+    ("chậm", 400),
+    ("sớm", 400),
+    ("trưa", 400),
+    ("giây", 400),
+    ("bữa", 400),
+    ("lắm", 700),
+    ("ty", 1200),
+    ("giới", 1200),
+    ("lai", 500),
+    ("lịch", 500),
+    ("du", 500),
+    ("hạnh", 300),
+    ("phúc", 400),
+    ("phụ", 600),
+    ("nữ", 600),
+    ("đàn", 300),
+    ("tộc", 400),
+    ("sức", 500),
+    ("thủ", 600),
+    ("đô", 400),
+    ("nông", 400),
+    ("thôn", 200),
+    ("âm", 500),
+    ("nghệ", 600),
+    ("bây", 500),
+    ("bố", 500),
+    ("bè", 300),
+    ("tương", 600),
+    ("trọng", 600),
+    ("tục", 400),
+    ("muộn", 300),
+    ("cà", 300),
+    ("phê", 300),
+    ("trà", 300),
+    ("mì", 300),
+    ("gà", 300),
+    ("thịt", 300),
+    ("rau", 300),
+    ("nẵng", 300),
+    ("huế", 300),
+    ("phiếu", 300),
+    ("thua", 300),
+    ("cụ", 300),
+    ("cưới", 300),
+    ("núi", 300),
+    ("nắng", 300),
+    ("gió", 300),
+    ("lạnh", 300),
+    ("tóc", 300),
+    ("sắc", 300),
+    ("đen", 300),
+    ("ngàn", 300),
+    ("sáu", 300),
+    ("bảy", 300),
+    ("tám", 300),
+    ("chín", 300),
+    ("tiểu", 300),
+    ("phổ", 300),
+    ("vai", 300),
+    ("ràng", 300),
+    ("giản", 300),
+    ("tạp", 300),
+    ("xuyên", 300),
+    ("buộc", 300),
+    ("cấm", 300),
+    ("tặng", 300),
+    ("giả", 300),
+    ("hẳn", 300),
+    ("xưa", 300),
+    ("chút", 400),
+    ("thiếu", 400),
+    ("ừ", 300),
+    ("tao", 300),
+    ("ghế", 300),
+    ("tưởng", 500),
+    ("lỗi", 500),
+    ("phở", 200),
+    ("sài", 200),
+    ("gòn", 200),
+    ("tù", 200),
+    ("toà", 300),
+    ("đẻ", 200),
+    ("hẹn", 200),
+    ("giường", 200),
+    ("tường", 200),
+    ("mát", 200),
+    ("miệng", 200),
+    ("tim", 300),
+    ("phức", 200),
+    ("gắng", 200),
+    ("dở", 200),
+    ("vời", 200),
+    ("giận", 200),
+    ("ghét", 200),
+    ("nọ", 200),
+    ("thao", 300),
+    ("gian", 1000),
+    ("đình", 800),
+    ("rút", 300),
+    ("tô", 200),
+    ("lát", 200),
+    ("bể", 200),
+    ("chả", 200),
+];
+
+/// Common two-syllable words with approximate frequency
+pub const BIGRAMS: &[(&str, &str, u32)] = &[
+    ("không", "có", 3000),
+    ("có", "thể", 3000),
+    ("việt", "nam", 3000),
+    ("người", "ta", 800),
+    ("chúng", "ta", 1200),
+    ("chúng", "tôi", 1200),
+    ("hà", "nội", 800),
+    ("hồ", "chí", 500),
+    ("chí", "minh", 500),
+    ("sài", "gòn", 200),
+    ("đà", "nẵng", 300),
+    ("kinh", "tế", 1500),
+    ("xã", "hội", 1500),
+    ("phát", "triển", 1500),
+    ("chính", "phủ", 600),
+    ("quốc", "gia", 900),
+    ("học", "sinh", 800),
+    ("sinh", "viên", 800),
+    ("giáo", "dục", 800),
+    ("bệnh", "viện", 700),
+    ("công", "ty", 1200),
+    ("doanh", "nghiệp", 800),
+    ("thông", "tin", 1200),
+    ("điện", "thoại", 500),
+    ("máy", "tính", 400),
+    ("hoạt", "động", 1200),
+    ("tổ", "chức", 1200),
+    ("nhân", "dân", 600),
+    ("đất", "nước", 600),
+    ("thời", "gian", 900),
+    ("vấn", "đề", 1200),
+    ("kết", "quả", 1000),
+    ("cảm", "ơn", 500),
+    ("xin", "chào", 300),
+    ("xin", "lỗi", 300),
+    ("tạm", "biệt", 200),
+    ("bây", "giờ", 500),
+    ("hôm", "nay", 500),
+    ("ngày", "mai", 300),
+    ("hôm", "qua", 300),
+    ("tất", "cả", 700),
+    ("bao", "nhiêu", 400),
+    ("bao", "giờ", 300),
+    ("như", "thế", 500),
+    ("thế", "nào", 600),
+    ("vì", "vậy", 500),
+    ("tuy", "nhiên", 500),
+    ("nhưng", "mà", 200),
+    ("có", "lẽ", 300),
+    ("đi", "học", 200),
+    ("đi", "làm", 200),
+    ("ăn", "cơm", 200),
+    ("uống", "nước", 100),
+    ("làm", "việc", 600),
+    ("công", "việc", 800),
+    ("gia", "đình", 800),
+    ("bạn", "bè", 300),
+    ("anh", "em", 300),
+    ("bố", "mẹ", 300),
+    ("cha", "mẹ", 300),
+    ("ông", "bà", 200),
+    ("vợ", "chồng", 300),
+    ("con", "cái", 200),
+    ("tiếng", "việt", 500),
+    ("người", "việt", 300),
+    ("thành", "phố", 1000),
+    ("thủ", "đô", 200),
+    ("nông", "thôn", 200),
+    ("cà", "phê", 300),
+    ("bóng", "đá", 400),
+    ("âm", "nhạc", 300),
+    ("ca", "sĩ", 300),
+    ("bài", "hát", 200),
+    ("khoa", "học", 700),
+    ("công", "nghệ", 600),
+    ("kỹ", "thuật", 400),
+    ("nghiên", "cứu", 600),
+    ("đại", "học", 800),
+    ("trường", "học", 200),
+    ("năm", "nay", 300),
+    ("không", "sao", 100),
+    ("không", "biết", 400),
+    ("không", "phải", 600),
+    ("có", "gì", 300),
+    ("làm", "gì", 300),
+    ("cái", "gì", 200),
+    ("tại", "sao", 300),
+    ("vì", "sao", 200),
+    ("như", "vậy", 400),
+    ("rất", "nhiều", 300),
+    ("một", "số", 900),
+    ("nhiều", "người", 300),
+    ("mọi", "người", 400),
+    ("sử", "dụng", 1000),
+    ("đặc", "biệt", 500),
+    ("quan", "trọng", 500),
+    ("quan", "hệ", 500),
+    ("quan", "tâm", 400),
+    ("hệ", "thống", 700),
+    ("thị", "trường", 800),
+    ("giá", "cả", 100),
+    ("nhà", "nước", 800),
+    ("pháp", "luật", 500),
+    ("luật", "sư", 200),
+    ("bác", "sĩ", 300),
+    ("yêu", "cầu", 600),
+    ("giải", "quyết", 500),
+    ("quyết", "định", 700),
+    ("thực", "hiện", 1000),
+    ("hiện", "nay", 600),
+    ("hiện", "tại", 400),
+    ("tương", "lai", 300),
+    ("lịch", "sử", 400),
+    ("văn", "hoá", 600),
+    ("du", "lịch", 400),
+    ("dân", "số", 200),
+    ("dân", "tộc", 300),
+    ("chiến", "tranh", 400),
+    ("hoà", "bình", 300),
+    ("an", "ninh", 300),
+    ("cảnh", "sát", 300),
+    ("quân", "đội", 300),
+    ("thể", "thao", 300),
+    ("sức", "khoẻ", 400),
+    ("cuộc", "sống", 500),
+    ("tình", "yêu", 300),
+    ("yêu", "thương", 200),
+    ("hạnh", "phúc", 300),
+    ("trẻ", "em", 500),
+    ("phụ", "nữ", 500),
+    ("đàn", "ông", 200),
+    ("con", "người", 500),
+    ("thế", "giới", 1000),
+    ("có", "được", 400),
+    ("đã", "có", 400),
+    ("sẽ", "có", 300),
+    ("được", "sử", 300),
+];
@@ -0,0 +1,269 @@
+//! Platform Keycode Translation
+//!
+//! The engine works on macOS virtual keycodes (`keys`). Frontends on other
+//! systems pass their native code tagged with its source and get the same
+//! translation here, instead of each carrying its own table:
+//! - `Mac`: macOS virtual keycode (as-is)
+//! - `Windows`: Windows virtual-key code (VK_*), US layout
+//! - `X11`: X11/XKB keysym (also what Wayland/Fcitx/IBus report)
+//! - `Evdev`: Linux evdev scancode (KEY_* from input-event-codes.h)
+//!
+//! Keypad digits are not mapped: they stay plain digits on screen
+//! (same as on macOS, where they have their own keycodes).
+
+use super::keys;
+
+/// Shift held
+pub const MOD_SHIFT: u8 = 0x01;
+/// Caps Lock on
+pub const MOD_CAPS_LOCK: u8 = 0x02;
+/// Cmd/Ctrl/Alt/Super held (bypasses the IME)
+pub const MOD_CTRL: u8 = 0x04;
+
+/// Where a keycode comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeySource {
+    Mac,
+    Windows,
+    X11,
+    Evdev,
+}
+
+impl KeySource {
+    /// FFI id: 0=macOS, 1=Windows VK, 2=X11 keysym, 3=Linux evdev
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::Mac),
+            1 => Some(Self::Windows),
+            2 => Some(Self::X11),
+            3 => Some(Self::Evdev),
+            _ => None,
+        }
+    }
+}
+
+/// macOS keycode for a native keycode, `None` for keys the engine ignores
+pub fn translate(source: KeySource, code: u32) -> Option<u16> {
+    match source {
+        KeySource::Mac => u16::try_from(code).ok(),
+        KeySource::Windows => from_windows(code),
+        KeySource::X11 => from_keysym(code),
+        KeySource::Evdev => from_evdev(code),
+    }
+}
+
+/// Engine (caps, shift) flags from modifier bits
+///
+/// For letters Shift inverts Caps Lock (Shift+A with Caps Lock = "a");
+/// other keys get Caps Lock as-is and Shift for their shifted symbol.
+pub fn caps_shift(key: u16, mods: u8) -> (bool, bool) {
+    let shift = mods & MOD_SHIFT != 0;
+    let caps_lock = mods & MOD_CAPS_LOCK != 0;
+    let caps = if keys::is_letter(key) {
+        caps_lock != shift
+    } else {
+        caps_lock
+    };
+    (caps, shift)
+}
+
+/// Windows virtual-key code (US layout)
+fn from_windows(vk: u32) -> Option<u16> {
+    let key = match vk {
+        // VK_A..VK_Z and VK_0..VK_9 are the ASCII codes
+        0x41..=0x5A | 0x30..=0x39 => return keys::from_char(char::from(vk as u8)).map(|k| k.0),
+        0x08 => keys::DELETE, // VK_BACK
+        0x09 => keys::TAB,
+        0x0D => keys::RETURN,
+        0x1B => keys::ESC,
+        0x20 => keys::SPACE,
+        0x25 => keys::LEFT,
+        0x26 => keys::UP,
+        0x27 => keys::RIGHT,
+        0x28 => keys::DOWN,
+        0xBA => keys::SEMICOLON, // VK_OEM_1
+        0xBB => keys::EQUAL,     // VK_OEM_PLUS
+        0xBC => keys::COMMA,     // VK_OEM_COMMA
+        0xBD => keys::MINUS,     // VK_OEM_MINUS
+        0xBE => keys::DOT,       // VK_OEM_PERIOD
+        0xBF => keys::SLASH,     // VK_OEM_2
+        0xC0 => keys::BACKQUOTE, // VK_OEM_3
+        0xDB => keys::LBRACKET,  // VK_OEM_4
+        0xDC => keys::BACKSLASH, // VK_OEM_5
+        0xDD => keys::RBRACKET,  // VK_OEM_6
+        0xDE => keys::QUOTE,     // VK_OEM_7
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// X11/XKB keysym
+fn from_keysym(sym: u32) -> Option<u16> {
+    let key = match sym {
+        // Latin-1 printable keysyms are the characters themselves,
+        // upper and lower case (and shifted symbols) on the same key
+        0x20..=0x7E => return keys::from_char(char::from(sym as u8)).map(|k| k.0),
+        0xFF08 => keys::DELETE,       // BackSpace
+        0xFF09 | 0xFE20 => keys::TAB, // Tab, ISO_Left_Tab (Shift+Tab)
+        0xFF0D => keys::RETURN,
+        0xFF1B => keys::ESC,
+        0xFF51 => keys::LEFT,
+        0xFF52 => keys::UP,
+        0xFF53 => keys::RIGHT,
+        0xFF54 => keys::DOWN,
+        0xFF8D => keys::ENTER, // KP_Enter
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// Linux evdev scancode (US layout)
+fn from_evdev(code: u32) -> Option<u16> {
+    const ROWS: [(u32, &str); 4] = [
+        (2, "1234567890-="),
+        (16, "qwertyuiop[]"),
+        (30, "asdfghjkl;'`"),
+        (44, "zxcvbnm,./"),
+    ];
+    for (start, row) in ROWS {
+        let c = code
+            .checked_sub(start)
+            .and_then(|i| row.chars().nth(i as usize));
+        if let Some(c) = c {
+            return keys::from_char(c).map(|k| k.0);
+        }
+    }
+    let key = match code {
+        1 => keys::ESC,
+        14 => keys::DELETE, // KEY_BACKSPACE
+        15 => keys::TAB,
+        28 => keys::RETURN,
+        43 => keys::BACKSLASH,
+        57 => keys::SPACE,
+        96 => keys::ENTER, // KEY_KPENTER
+        103 => keys::UP,
+        105 => keys::LEFT,
+        106 => keys::RIGHT,
+        108 => keys::DOWN,
+        _ => return None,
+    };
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (char, Windows VK, X11 keysym, evdev) for every key on a US layout
+    const US_KEYS: &[(char, u32, u32, u32)] = &[
+        ('a', 0x41, 0x61, 30),
+        ('b', 0x42, 0x62, 48),
+        ('c', 0x43, 0x63, 46),
+        ('d', 0x44, 0x64, 32),
+        ('e', 0x45, 0x65, 18),
+        ('f', 0x46, 0x66, 33),
+        ('g', 0x47, 0x67, 34),
+        ('h', 0x48, 0x68, 35),
+        ('i', 0x49, 0x69, 23),
+        ('j', 0x4A, 0x6A, 36),
+        ('k', 0x4B, 0x6B, 37),
+        ('l', 0x4C, 0x6C, 38),
+        ('m', 0x4D, 0x6D, 50),
+        ('n', 0x4E, 0x6E, 49),
+        ('o', 0x4F, 0x6F, 24),
+        ('p', 0x50, 0x70, 25),
+        ('q', 0x51, 0x71, 16),
+        ('r', 0x52, 0x72, 19),
+        ('s', 0x53, 0x73, 31),
+        ('t', 0x54, 0x74, 20),
+        ('u', 0x55, 0x75, 22),
+        ('v', 0x56, 0x76, 47),
+        ('w', 0x57, 0x77, 17),
+        ('x', 0x58, 0x78, 45),
+        ('y', 0x59, 0x79, 21),
+        ('z', 0x5A, 0x7A, 44),
+        ('0', 0x30, 0x30, 11),
+        ('1', 0x31, 0x31, 2),
+        ('2', 0x32, 0x32, 3),
+        ('3', 0x33, 0x33, 4),
+        ('4', 0x34, 0x34, 5),
+        ('5', 0x35, 0x35, 6),
+        ('6', 0x36, 0x36, 7),
+        ('7', 0x37, 0x37, 8),
+        ('8', 0x38, 0x38, 9),
+        ('9', 0x39, 0x39, 10),
+        (' ', 0x20, 0x20, 57),
+        ('\t', 0x09, 0xFF09, 15),
+        ('\n', 0x0D, 0xFF0D, 28),
+        (';', 0xBA, 0x3B, 39),
+        ('=', 0xBB, 0x3D, 13),
+        (',', 0xBC, 0x2C, 51),
+        ('-', 0xBD, 0x2D, 12),
+        ('.', 0xBE, 0x2E, 52),
+        ('/', 0xBF, 0x2F, 53),
+        ('`', 0xC0, 0x60, 41),
+        ('[', 0xDB, 0x5B, 26),
+        ('\\', 0xDC, 0x5C, 43),
+        (']', 0xDD, 0x5D, 27),
+        ('\'', 0xDE, 0x27, 40),
+    ];
+
+    #[test]
+    fn test_us_layout() {
+        for &(c, vk, sym, evdev) in US_KEYS {
+            let expected = keys::from_char(c).map(|k| k.0);
+            assert_eq!(translate(KeySource::Windows, vk), expected, "VK {vk:#x}");
+            assert_eq!(translate(KeySource::X11, sym), expected, "keysym {sym:#x}");
+            assert_eq!(
+                translate(KeySource::Evdev, evdev),
+                expected,
+                "evdev {evdev}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_editing_keys() {
+        // (mac, Windows, X11, evdev)
+        let table = [
+            (keys::DELETE, 0x08, 0xFF08, 14),
+            (keys::ESC, 0x1B, 0xFF1B, 1),
+            (keys::LEFT, 0x25, 0xFF51, 105),
+            (keys::UP, 0x26, 0xFF52, 103),
+            (keys::RIGHT, 0x27, 0xFF53, 106),
+            (keys::DOWN, 0x28, 0xFF54, 108),
+        ];
+        for (mac, vk, sym, evdev) in table {
+            assert_eq!(translate(KeySource::Windows, vk), Some(mac));
+            assert_eq!(translate(KeySource::X11, sym), Some(mac));
+            assert_eq!(translate(KeySource::Evdev, evdev), Some(mac));
+            assert_eq!(translate(KeySource::Mac, mac as u32), Some(mac));
+        }
+    }
+
+    #[test]
+    fn test_shifted_and_unknown() {
+        // Shifted keysyms land on the unshifted key
+        assert_eq!(translate(KeySource::X11, 'A' as u32), Some(keys::A));
+        assert_eq!(translate(KeySource::X11, ':' as u32), Some(keys::SEMICOLON));
+        assert_eq!(translate(KeySource::X11, '@' as u32), Some(keys::N2));
+        // Keypad digits, function keys, modifiers
+        assert_eq!(translate(KeySource::Windows, 0x60), None); // VK_NUMPAD0
+        assert_eq!(translate(KeySource::Windows, 0x10), None); // VK_SHIFT
+        assert_eq!(translate(KeySource::X11, 0xFFBE), None); // F1
+        assert_eq!(translate(KeySource::Evdev, 42), None); // KEY_LEFTSHIFT
+        assert_eq!(translate(KeySource::Evdev, 82), None); // KEY_KP0
+        assert_eq!(translate(KeySource::Mac, 0x10000), None);
+    }
+
+    #[test]
+    fn test_caps_shift() {
+        assert_eq!(caps_shift(keys::A, MOD_SHIFT), (true, true));
+        assert_eq!(caps_shift(keys::A, MOD_CAPS_LOCK), (true, false));
+        assert_eq!(
+            caps_shift(keys::A, MOD_SHIFT | MOD_CAPS_LOCK),
+            (false, true)
+        );
+        assert_eq!(caps_shift(keys::N2, MOD_SHIFT), (false, true));
+    }
+}
@@ -151,3 +151,90 @@ pub fn is_consonant(key: u16) -> bool {
 pub fn is_number(key: u16) -> bool {
     matches!(key, N0 | N1 | N2 | N3 | N4 | N5 | N6 | N7 | N8 | N9)
 }
+
+/// Convert an ASCII character to its keycode and Shift state
+/// Examples: 'a' → (A, false), 'A' → (A, true), '?' → (SLASH, true)
+pub fn from_char(c: char) -> Option<(u16, bool)> {
+    let shift = c.is_ascii_uppercase();
+    let key = match c.to_ascii_lowercase() {
+        'a' => A,
+        'b' => B,
+        'c' => C,
+        'd' => D,
+        'e' => E,
+        'f' => F,
+        'g' => G,
+        'h' => H,
+        'i' => I,
+        'j' => J,
+        'k' => K,
+        'l' => L,
+        'm' => M,
+        'n' => N,
+        'o' => O,
+        'p' => P,
+        'q' => Q,
+        'r' => R,
+        's' => S,
+        't' => T,
+        'u' => U,
+        'v' => V,
+        'w' => W,
+        'x' => X,
+        'y' => Y,
+        'z' => Z,
+        '0' => N0,
+        '1' => N1,
+        '2' => N2,
+        '3' => N3,
+        '4' => N4,
+        '5' => N5,
+        '6' => N6,
+        '7' => N7,
+        '8' => N8,
+        '9' => N9,
+        ' ' => SPACE,
+        '\t' => TAB,
+        '\n' => RETURN,
+        '.' => DOT,
+        ',' => COMMA,
+        '/' => SLASH,
+        ';' => SEMICOLON,
+        '\'' => QUOTE,
+        '[' => LBRACKET,
+        ']' => RBRACKET,
+        '\\' => BACKSLASH,
+        '-' => MINUS,
+        '=' => EQUAL,
+        '`' => BACKQUOTE,
+        _ => {
+            // Shifted symbols
+            let key = match c {
+                '!' => N1,
+                '@' => N2,
+                '#' => N3,
+                '$' => N4,
+                '%' => N5,
+                '^' => N6,
+                '&' => N7,
+                '*' => N8,
+                '(' => N9,
+                ')' => N0,
+                '>' => DOT,
+                '<' => COMMA,
+                '?' => SLASH,
+                ':' => SEMICOLON,
+                '"' => QUOTE,
+                '{' => LBRACKET,
+                '}' => RBRACKET,
+                '|' => BACKSLASH,
+                '_' => MINUS,
+                '+' => EQUAL,
+                '~' => BACKQUOTE,
+                _ => return None,
+            };
+            return Some((key, true));
+        }
+    };
+    Some((key, shift))
+}
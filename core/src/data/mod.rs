@@ -2,11 +2,23 @@
 //!
 //! This module contains all linguistic data for Vietnamese input:
 //! - `keys`: Virtual keycode definitions (platform-specific)
+//! - `keycodes`: Windows/X11/evdev keycode translation to `keys`
 //! - `chars`: Unicode character conversion (includes tone/mark constants)
+//! - `dictionary`: Attested Vietnamese syllables (dictionary validation)
+//! - `emoji`: Built-in emoji/symbol shortcodes (`:tim:` → ❤️)
+//! - `encoding`: Output encodings (Unicode NFC/NFD, TCVN3, VNI-Windows, VISCII)
+//! - `english`: Common English words (auto-restore)
+//! - `frequency`: Syllable and bigram frequencies (accent restoration)
 //! - `vowel`: Vietnamese vowel phonology system
 
 pub mod chars;
 pub mod constants;
+pub mod dictionary;
+pub mod emoji;
+pub mod encoding;
+pub mod english;
+pub mod frequency;
+pub mod keycodes;
 pub mod keys;
 pub mod vowel;
 
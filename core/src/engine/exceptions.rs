@@ -0,0 +1,153 @@
+//! Auto-restore Exceptions
+//!
+//! User-managed word lists that override English auto-restore:
+//! - always restore: raw keystrokes that must come back as typed ("docs")
+//! - never restore: Vietnamese words that must stay transformed ("tét")
+//!
+//! Words are matched case-insensitively. A never-restore word matches either
+//! the transformed word or its raw keystrokes; it wins over always-restore.
+
+use std::collections::HashSet;
+
+/// Exception list id
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExceptionList {
+    /// Always restore to raw ASCII
+    AlwaysRestore = 0,
+    /// Never restore (keep Vietnamese)
+    NeverRestore = 1,
+}
+
+impl ExceptionList {
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(ExceptionList::AlwaysRestore),
+            1 => Some(ExceptionList::NeverRestore),
+            _ => None,
+        }
+    }
+}
+
+/// Restore decision forced by the exception lists
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Exception {
+    Restore,
+    Keep,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RestoreExceptions {
+    always: HashSet<String>,
+    never: HashSet<String>,
+}
+
+impl RestoreExceptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn list(&self, list: ExceptionList) -> &HashSet<String> {
+        match list {
+            ExceptionList::AlwaysRestore => &self.always,
+            ExceptionList::NeverRestore => &self.never,
+        }
+    }
+
+    fn list_mut(&mut self, list: ExceptionList) -> &mut HashSet<String> {
+        match list {
+            ExceptionList::AlwaysRestore => &mut self.always,
+            ExceptionList::NeverRestore => &mut self.never,
+        }
+    }
+
+    /// Add a word, returns false if empty or already listed
+    pub fn add(&mut self, list: ExceptionList, word: &str) -> bool {
+        let word = word.trim().to_lowercase();
+        !word.is_empty() && self.list_mut(list).insert(word)
+    }
+
+    /// Remove a word, returns false if not listed
+    pub fn remove(&mut self, list: ExceptionList, word: &str) -> bool {
+        self.list_mut(list).remove(&word.trim().to_lowercase())
+    }
+
+    pub fn clear(&mut self, list: ExceptionList) {
+        self.list_mut(list).clear();
+    }
+
+    /// Add whitespace-separated words (`#` starts a comment line),
+    /// returns the number of new words
+    pub fn load(&mut self, list: ExceptionList, text: &str) -> usize {
+        text.lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .flat_map(str::split_whitespace)
+            .filter(|word| self.add(list, word))
+            .count()
+    }
+
+    pub fn contains(&self, list: ExceptionList, word: &str) -> bool {
+        self.list(list).contains(&word.to_lowercase())
+    }
+
+    pub fn len(&self, list: ExceptionList) -> usize {
+        self.list(list).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.always.is_empty() && self.never.is_empty()
+    }
+
+    /// Decision for a word: `raw` keystrokes and `output` as transformed
+    pub fn check(&self, raw: &str, output: &str) -> Option<Exception> {
+        use ExceptionList::*;
+        if self.contains(NeverRestore, output) || self.contains(NeverRestore, raw) {
+            Some(Exception::Keep)
+        } else if self.contains(AlwaysRestore, raw) {
+            Some(Exception::Restore)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ExceptionList::*;
+
+    #[test]
+    fn test_add_remove() {
+        let mut ex = RestoreExceptions::new();
+        assert!(ex.add(AlwaysRestore, "Docs"));
+        assert!(!ex.add(AlwaysRestore, "docs"));
+        assert!(!ex.add(AlwaysRestore, "  "));
+        assert!(ex.contains(AlwaysRestore, "DOCS"));
+        assert!(!ex.contains(NeverRestore, "docs"));
+        assert!(ex.remove(AlwaysRestore, "docs"));
+        assert!(!ex.remove(AlwaysRestore, "docs"));
+        assert!(ex.is_empty());
+    }
+
+    #[test]
+    fn test_load_and_clear() {
+        let mut ex = RestoreExceptions::new();
+        assert_eq!(ex.load(NeverRestore, "# keep\ntét rét\nhót tét"), 3);
+        assert_eq!(ex.len(NeverRestore), 3);
+        ex.clear(NeverRestore);
+        assert_eq!(ex.len(NeverRestore), 0);
+    }
+
+    #[test]
+    fn test_check() {
+        let mut ex = RestoreExceptions::new();
+        ex.add(AlwaysRestore, "test");
+        assert_eq!(ex.check("test", "tét"), Some(Exception::Restore));
+        assert_eq!(ex.check("rest", "rét"), None);
+        // Never-restore wins, by output or raw keystrokes
+        ex.add(NeverRestore, "tét");
+        assert_eq!(ex.check("Test", "Tét"), Some(Exception::Keep));
+        ex.add(NeverRestore, "rest");
+        assert_eq!(ex.check("rest", "rét"), Some(Exception::Keep));
+    }
+}
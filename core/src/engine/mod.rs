@@ -11,20 +11,36 @@
 //! 4. **Longest-Match-First**: For diacritic placement
 
 pub mod buffer;
+pub mod exceptions;
+pub mod pattern;
+pub mod profile;
 pub mod shortcut;
+pub mod shortcut_analysis;
+pub mod shortcut_io;
 pub mod syllable;
+pub mod template;
 pub mod transform;
+pub mod usage;
 pub mod validation;
 
+use crate::accent;
 use crate::data::{
     chars::{self, mark, tone},
-    constants, keys,
+    constants, dictionary, emoji,
+    encoding::{self, Encoding},
+    english::EnglishWords,
+    keycodes::{self, KeySource},
+    keys,
     vowel::{Phonology, Vowel},
 };
-use crate::input::{self, ToneType};
+use crate::input::{self, ActiveMethod, CustomMethod, Symbol, ToneType};
 use crate::utils;
 use buffer::{Buffer, Char, MAX};
-use shortcut::{InputMethod, ShortcutTable};
+use exceptions::{Exception, RestoreExceptions};
+use profile::{AppState, Profile, Profiles, Settings};
+use shortcut::{InputMethod, ShortcutMatch, ShortcutTable};
+use std::sync::Arc;
+use usage::{Suggestion, Usage};
 use validation::{is_foreign_word_pattern, is_valid, is_valid_for_transform, is_valid_with_tones};
 
 /// Engine action result
@@ -46,12 +62,28 @@ pub struct Result {
     /// Flags byte:
     /// - bit 0 (0x01): key_consumed - if set, the trigger key should NOT be passed through
     ///   Used for shortcuts where the trigger key is part of the replacement
+    /// - bit 1 (0x02): more_output - `chars` holds only the first chunk of the text;
+    ///   fetch the rest with `ime_next_output` (each chunk has backspace 0)
+    /// - bit 2 (0x04): preedit - preedit mode only, the composition string changed
+    ///   (fetch it with `ime_get_preedit`)
     pub flags: u8,
+    /// Characters to move the cursor left once all output is inserted
+    /// (shortcut `{cursor}` marker). Set on the last chunk only.
+    pub cursor_left: u16,
+    /// Characters after the cursor to delete before inserting (editing
+    /// inside a word, see `Engine::set_surrounding_text`)
+    pub delete_after: u8,
 }
 
 /// Flag: key was consumed by shortcut, don't pass through
 pub const FLAG_KEY_CONSUMED: u8 = 0x01;
 
+/// Flag: output continues in further chunks (see `Engine::next_output`)
+pub const FLAG_MORE_OUTPUT: u8 = 0x02;
+
+/// Flag: the preedit string changed (preedit mode, see `Engine::preedit`)
+pub const FLAG_PREEDIT: u8 = 0x04;
+
 impl Result {
     pub fn none() -> Self {
         Self {
@@ -60,6 +92,8 @@ impl Result {
             backspace: 0,
             count: 0,
             flags: 0,
+            cursor_left: 0,
+            delete_after: 0,
         }
     }
 
@@ -70,6 +104,8 @@ impl Result {
             backspace,
             count: chars.len().min(MAX) as u8,
             flags: 0,
+            cursor_left: 0,
+            delete_after: 0,
         };
         for (i, &c) in chars.iter().take(MAX).enumerate() {
             result.chars[i] = c as u32;
@@ -88,6 +124,11 @@ impl Result {
     pub fn key_consumed(&self) -> bool {
         self.flags & FLAG_KEY_CONSUMED != 0
     }
+
+    /// Check if more output chunks follow
+    pub fn has_more(&self) -> bool {
+        self.flags & FLAG_MORE_OUTPUT != 0
+    }
 }
 
 /// Transform type for revert tracking
@@ -108,6 +149,23 @@ enum Transform {
 /// Word history ring buffer capacity (stores last N committed words)
 const HISTORY_CAPACITY: usize = 10;
 
+/// Maximum suggestions offered for the current word (one candidate popup page)
+const MAX_CANDIDATES: usize = 9;
+
+/// Last shortcut expansion, reverted by the undo key right after it
+struct ShortcutUndo {
+    /// Trigger that fired (to uncount it)
+    trigger: String,
+    /// Screen units the expansion took (in the output encoding)
+    erase: u8,
+    /// What was typed instead (trigger text plus the key)
+    typed: Vec<char>,
+    /// Word committed by the expansion's space (restored to history)
+    word: Option<Buffer>,
+    /// Token once undone (as if no shortcut had fired)
+    token: Vec<char>,
+}
+
 /// Ring buffer for word history (stack-allocated, O(1) push/pop)
 ///
 /// Used for backspace-after-space feature: when user presses backspace
@@ -164,6 +222,41 @@ fn is_sentence_ending(key: u16, shift: bool) -> bool {
         || (shift && key == keys::SLASH) // ?
 }
 
+/// Apply a key's Result to the text it edits (`typed` is the key's char)
+///
+/// Returns how many chars the backspace reached before the start of `text`.
+fn apply_to_text(
+    text: &mut Vec<char>,
+    key: u16,
+    shift: bool,
+    typed: Option<char>,
+    result: &Result,
+    pending: &[char],
+) -> usize {
+    if result.action == Action::Send as u8 {
+        let backspace = result.backspace as usize;
+        let erased_before = backspace.saturating_sub(text.len());
+        text.truncate(text.len() - (backspace - erased_before));
+        text.extend(
+            result.chars[..result.count as usize]
+                .iter()
+                .filter_map(|&c| char::from_u32(c)),
+        );
+        text.extend(pending);
+        if key != keys::SPACE && keys::is_break_ext(key, shift) && !result.key_consumed() {
+            text.extend(typed);
+        }
+        erased_before
+    } else {
+        if key == keys::DELETE {
+            text.pop();
+        } else {
+            text.extend(typed);
+        }
+        0
+    }
+}
+
 /// Check if a break key should reset pending_capitalize
 /// Neutral keys like quotes, parentheses, arrows should NOT reset (preserve pending)
 /// Word-breaking keys like comma should reset
@@ -244,6 +337,8 @@ fn break_key_to_char(key: u16, shift: bool) -> Option<char> {
 pub struct Engine {
     buf: Buffer,
     method: u8,
+    /// User-defined keymap (overrides built-in key mappings, `method` is its base)
+    custom_method: Option<Arc<CustomMethod>>,
     enabled: bool,
     last_transform: Option<Transform>,
     shortcuts: ShortcutTable,
@@ -269,6 +364,14 @@ pub struct Engine {
     /// When true, automatically restores English words that were transformed
     /// e.g., "tẽt" → "text", "ễpct" → "expect"
     english_auto_restore: bool,
+    /// Validate syllables against the bundled dictionary (data::dictionary)
+    /// When true, legal but non-existent syllables ("dóc" from "docs")
+    /// count as invalid, so auto-restore can treat them as English
+    dictionary_validation: bool,
+    /// Known English words for auto-restore (bundled + loaded lists)
+    english_words: EnglishWords,
+    /// User lists that force or prevent auto-restore for specific words
+    restore_exceptions: RestoreExceptions,
     /// Word history for backspace-after-space feature
     word_history: WordHistory,
     /// Number of spaces typed after committing a word (for backspace tracking)
@@ -319,6 +422,47 @@ pub struct Engine {
     /// Tracks if auto-capitalize was just used on the current word
     /// Used to restore pending_capitalize when user deletes the capitalized letter
     auto_capitalize_used: bool,
+    /// Output encoding for results (Unicode NFC by default)
+    output_encoding: Encoding,
+    /// VIQR: previous key was "\" - the next modifier symbol is typed literally
+    /// Example: "to\." → "to." (backslash removed, dot not applied as nặng)
+    symbol_escape: bool,
+    /// Suggestion mode: rank alternative spellings of the buffer after each key
+    suggestions: bool,
+    /// Current suggestions for the buffer, best first (empty when disabled)
+    candidates: Vec<String>,
+    /// Expand built-in `:name:` emoji/symbol shortcodes
+    emoji: bool,
+    /// Output that did not fit in the last Result (see FLAG_MORE_OUTPUT)
+    pending_output: Vec<char>,
+    /// Cursor move for the last chunk of the pending output
+    pending_cursor: usize,
+    /// Buffer chars after the cursor when editing inside a word
+    word_tail: usize,
+    /// Named profiles, app assignments and per-app state
+    profiles: Profiles,
+    /// Shortcut counts and typed phrases (for suggested shortcuts)
+    usage: Usage,
+    /// Current word as shown on screen, digits and symbols included
+    /// (what pattern shortcuts match); reset by space, Enter, ESC, arrows
+    token: Vec<char>,
+    /// Keys typed for `token`, before any transform (what emoji names match)
+    token_keys: String,
+    /// Trigger of the shortcut expanded on the current key
+    shortcut_fired: Option<String>,
+    /// Last expansion, while the undo key can still revert it
+    shortcut_undo: Option<ShortcutUndo>,
+    /// Key that reverts an expansion typed right before it (None = off)
+    shortcut_undo_key: Option<u16>,
+    /// Word whose expansion was reverted: not expanded again on space
+    suppressed_shortcut: Option<String>,
+    /// Preedit mode: the word is reported as a composition string and
+    /// committed on word boundaries, instead of backspace + insert
+    preedit_mode: bool,
+    /// Uncommitted text (preedit mode)
+    preedit: Vec<char>,
+    /// That shortcut was a pattern or emoji (its backspace counts token chars)
+    pattern_fired: bool,
 }
 
 impl Default for Engine {
@@ -332,6 +476,7 @@ impl Engine {
         Self {
             buf: Buffer::new(),
             method: 0,
+            custom_method: None,
             enabled: true,
             last_transform: None,
             shortcuts: ShortcutTable::with_defaults(),
@@ -342,6 +487,9 @@ impl Engine {
             free_tone_enabled: false,
             modern_tone: true,           // Default: modern style (hoà, thuý)
             english_auto_restore: false, // Default: OFF (experimental feature)
+            dictionary_validation: false,
+            english_words: EnglishWords::new(),
+            restore_exceptions: RestoreExceptions::new(),
             word_history: WordHistory::new(),
             spaces_after_commit: 0,
             pending_breve_pos: None,
@@ -356,11 +504,62 @@ impl Engine {
             auto_capitalize: false, // Default: OFF
             pending_capitalize: false,
             auto_capitalize_used: false,
+            output_encoding: Encoding::Unicode,
+            symbol_escape: false,
+            suggestions: false, // Default: OFF
+            candidates: Vec::new(),
+            emoji: false, // Default: OFF
+            pending_output: Vec::new(),
+            pending_cursor: 0,
+            word_tail: 0,
+            profiles: Profiles::default(),
+            usage: Usage::new(),
+            token: Vec::new(),
+            token_keys: String::new(),
+            shortcut_fired: None,
+            shortcut_undo: None,
+            preedit_mode: false,
+            preedit: Vec::new(),
+            shortcut_undo_key: None, // Default: OFF (platforms opt in)
+            suppressed_shortcut: None,
+            pattern_fired: false,
         }
     }
 
     pub fn set_method(&mut self, method: u8) {
         self.method = method;
+        self.custom_method = None;
+    }
+
+    /// Use a user-defined keymap
+    /// Engine behaviour (delayed stroke, w→ư, ...) follows the keymap's base method
+    pub fn set_custom_method(&mut self, method: CustomMethod) {
+        self.method = method.base();
+        self.custom_method = Some(Arc::new(method));
+        self.clear();
+    }
+
+    /// Key mapping in use: custom keymap if loaded, otherwise built-in method
+    fn keymap(&self) -> ActiveMethod {
+        match &self.custom_method {
+            Some(m) => ActiveMethod::Custom(Arc::clone(m)),
+            None => ActiveMethod::Builtin(input::get(self.method)),
+        }
+    }
+
+    /// Letter and digit keys that add a mark in the active keymap
+    fn mark_keys(&self) -> Vec<u16> {
+        let m = self.keymap();
+        ('a'..='z')
+            .chain('0'..='9')
+            .filter_map(keys::from_char)
+            .map(|(k, _)| k)
+            .filter(|&k| m.mark(k).is_some())
+            .collect()
+    }
+
+    fn is_mark_key(&self, key: u16) -> bool {
+        self.keymap().mark(key).is_some()
     }
 
     pub fn set_enabled(&mut self, enabled: bool) {
@@ -397,6 +596,11 @@ impl Engine {
         self.english_auto_restore = enabled;
     }
 
+    /// Set whether to validate syllables against the bundled dictionary
+    pub fn set_dictionary_validation(&mut self, enabled: bool) {
+        self.dictionary_validation = enabled;
+    }
+
     /// Set whether to enable auto-capitalize after sentence-ending punctuation
     pub fn set_auto_capitalize(&mut self, enabled: bool) {
         self.auto_capitalize = enabled;
@@ -405,6 +609,67 @@ impl Engine {
         }
     }
 
+    /// Set output encoding (Unicode NFC or NFD)
+    /// Applies to every result, including shortcut replacements and restores
+    pub fn set_output_encoding(&mut self, encoding: Encoding) {
+        self.output_encoding = encoding;
+    }
+
+    /// Enable suggestion mode (candidate list for the current word)
+    pub fn set_suggestions(&mut self, enabled: bool) {
+        self.suggestions = enabled;
+        self.update_candidates();
+    }
+
+    /// Enable built-in emoji/symbol shortcodes (`:tim:` → ❤️, `:mui-ten:` → →)
+    pub fn set_emoji(&mut self, enabled: bool) {
+        self.emoji = enabled;
+    }
+
+    /// Preedit mode: report the word as a composition string (`preedit`)
+    /// and commit it on word boundaries, instead of backspace + insert
+    ///
+    /// Switching drops the current word; commit it first with `commit_preedit`.
+    pub fn set_preedit_mode(&mut self, enabled: bool) {
+        self.preedit_mode = enabled;
+        self.clear_all();
+    }
+
+    pub fn preedit_mode(&self) -> bool {
+        self.preedit_mode
+    }
+
+    /// Uncommitted text in preedit mode (in the output encoding)
+    pub fn preedit(&self) -> String {
+        self.encode_text(&self.preedit).into_iter().collect()
+    }
+
+    /// Cursor position in the preedit (chars from its start)
+    pub fn preedit_cursor(&self) -> usize {
+        self.encode_text(&self.preedit).len()
+    }
+
+    /// Commit the preedit as it is (focus change, mouse click...)
+    ///
+    /// Returns a Result with the text to commit, or `Result::none()` if
+    /// nothing is composed.
+    pub fn commit_preedit(&mut self) -> Result {
+        let text = std::mem::take(&mut self.preedit);
+        self.clear_all();
+        if text.is_empty() {
+            return Result::none();
+        }
+        let text = self.encode_text(&text);
+        self.send_text(0, &text, FLAG_PREEDIT)
+    }
+
+    /// Key that reverts a shortcut expansion when pressed right after it
+    /// (`None` disables undo)
+    pub fn set_shortcut_undo_key(&mut self, key: Option<u16>) {
+        self.shortcut_undo_key = key;
+        self.shortcut_undo = None;
+    }
+
     pub fn shortcuts(&self) -> &ShortcutTable {
         &self.shortcuts
     }
@@ -413,11 +678,173 @@ impl Engine {
         &mut self.shortcuts
     }
 
+    pub fn usage(&self) -> &Usage {
+        &self.usage
+    }
+
+    pub fn usage_mut(&mut self) -> &mut Usage {
+        &mut self.usage
+    }
+
+    /// Phrases typed often that the active shortcut table does not cover
+    pub fn shortcut_suggestions(&self, limit: usize) -> Vec<Suggestion> {
+        self.usage.suggestions(&self.shortcuts, limit)
+    }
+
+    pub fn english_words(&self) -> &EnglishWords {
+        &self.english_words
+    }
+
+    pub fn english_words_mut(&mut self) -> &mut EnglishWords {
+        &mut self.english_words
+    }
+
+    pub fn restore_exceptions(&self) -> &RestoreExceptions {
+        &self.restore_exceptions
+    }
+
+    pub fn restore_exceptions_mut(&mut self) -> &mut RestoreExceptions {
+        &mut self.restore_exceptions
+    }
+
+    // ============================================================
+    // Profiles
+    // ============================================================
+
+    /// Settings of the active profile
+    pub fn settings(&self) -> Settings {
+        Settings {
+            skip_w_shortcut: self.skip_w_shortcut,
+            esc_restore: self.esc_restore_enabled,
+            free_tone: self.free_tone_enabled,
+            modern_tone: self.modern_tone,
+            english_auto_restore: self.english_auto_restore,
+            dictionary_validation: self.dictionary_validation,
+            auto_capitalize: self.auto_capitalize,
+            suggestions: self.suggestions,
+            emoji: self.emoji,
+            shortcut_undo_key: self.shortcut_undo_key,
+            output_encoding: self.output_encoding,
+        }
+    }
+
+    pub fn apply_settings(&mut self, settings: Settings) {
+        self.set_skip_w_shortcut(settings.skip_w_shortcut);
+        self.set_esc_restore(settings.esc_restore);
+        self.set_free_tone(settings.free_tone);
+        self.set_modern_tone(settings.modern_tone);
+        self.set_english_auto_restore(settings.english_auto_restore);
+        self.set_dictionary_validation(settings.dictionary_validation);
+        self.set_auto_capitalize(settings.auto_capitalize);
+        self.set_output_encoding(settings.output_encoding);
+        self.set_suggestions(settings.suggestions);
+        self.set_emoji(settings.emoji);
+        self.set_shortcut_undo_key(settings.shortcut_undo_key);
+    }
+
+    /// Name of the active profile
+    pub fn profile(&self) -> &str {
+        &self.profiles.active
+    }
+
+    /// Create an empty profile (default settings, no shortcuts),
+    /// returns false if the name is taken
+    pub fn add_profile(&mut self, name: &str) -> bool {
+        if name.is_empty() || self.profiles.exists(name) {
+            return false;
+        }
+        self.profiles
+            .stored
+            .insert(name.to_string(), Profile::default());
+        true
+    }
+
+    /// Delete a profile and its app assignments
+    /// The default profile and the active profile cannot be removed
+    pub fn remove_profile(&mut self, name: &str) -> bool {
+        if name == profile::DEFAULT_PROFILE || self.profiles.stored.remove(name).is_none() {
+            return false;
+        }
+        self.profiles.assignments.retain(|_, p| p != name);
+        true
+    }
+
+    /// Make a profile active, so setters and `shortcuts_mut` edit it
+    pub fn use_profile(&mut self, name: &str) -> bool {
+        if name == self.profiles.active {
+            return true;
+        }
+        let Some(mut next) = self.profiles.stored.remove(name) else {
+            return false;
+        };
+
+        // Clock, clipboard and counters stay with the engine
+        std::mem::swap(
+            self.shortcuts.templates_mut(),
+            next.shortcuts.templates_mut(),
+        );
+        let current = Profile {
+            settings: self.settings(),
+            shortcuts: std::mem::replace(&mut self.shortcuts, next.shortcuts),
+        };
+        let previous = std::mem::replace(&mut self.profiles.active, name.to_string());
+        self.profiles.stored.insert(previous, current);
+        self.apply_settings(next.settings);
+        self.clear_all();
+        true
+    }
+
+    /// Assign an app to a profile (empty name: back to default)
+    /// Takes effect immediately if the app is the current context
+    pub fn assign_profile(&mut self, app_id: &str, name: &str) -> bool {
+        if name.is_empty() {
+            self.profiles.assignments.remove(app_id);
+        } else if self.profiles.exists(name) {
+            self.profiles
+                .assignments
+                .insert(app_id.to_string(), name.to_string());
+        } else {
+            return false;
+        }
+        if app_id == self.profiles.context {
+            let name = self.profiles.profile_for(app_id).to_string();
+            self.use_profile(&name);
+        }
+        true
+    }
+
+    /// Switch to another app: remember the current app's method and on/off
+    /// state, activate the new app's profile and restore its state
+    /// (apps seen for the first time keep the current method and state)
+    pub fn set_context(&mut self, app_id: &str) {
+        if app_id == self.profiles.context {
+            return;
+        }
+
+        let state = AppState {
+            method: self.method,
+            custom_method: self.custom_method.clone(),
+            enabled: self.enabled,
+        };
+        let previous = std::mem::replace(&mut self.profiles.context, app_id.to_string());
+        self.profiles.apps.insert(previous, state);
+
+        let name = self.profiles.profile_for(app_id).to_string();
+        self.use_profile(&name);
+        if let Some(state) = self.profiles.apps.get(app_id).cloned() {
+            self.method = state.method;
+            self.custom_method = state.custom_method;
+            self.set_enabled(state.enabled);
+        }
+        self.clear_all();
+    }
+
     /// Get current input method as InputMethod enum
     fn current_input_method(&self) -> InputMethod {
         match self.method {
             0 => InputMethod::Telex,
             1 => InputMethod::Vni,
+            2 => InputMethod::Viqr,
             _ => InputMethod::All,
         }
     }
@@ -432,6 +859,32 @@ impl Engine {
         self.on_key_ext(key, caps, ctrl, false)
     }
 
+    /// Handle a key event given as a native keycode
+    ///
+    /// `code` is translated from `source` (see `data::keycodes`); `mods`
+    /// are `keycodes::MOD_*` bits. Keys the engine doesn't use pass through
+    /// without touching its state.
+    pub fn on_key_code(&mut self, source: KeySource, code: u32, mods: u8) -> Result {
+        let Some(key) = keycodes::translate(source, code) else {
+            return Result::none();
+        };
+        let (caps, shift) = keycodes::caps_shift(key, mods);
+        self.on_key_ext(key, caps, mods & keycodes::MOD_CTRL != 0, shift)
+    }
+
+    /// Handle a key event given as the character it types
+    ///
+    /// Case and Shift come from the character; of `mods` only
+    /// `keycodes::MOD_CTRL` is used. A character with no key on a US
+    /// layout (e.g. "đ" from a native Vietnamese layout) ends the word.
+    pub fn on_char(&mut self, c: char, mods: u8) -> Result {
+        let Some((key, shift)) = keys::from_char(c) else {
+            self.clear_all();
+            return Result::none();
+        };
+        self.on_key_ext(key, c.is_uppercase(), mods & keycodes::MOD_CTRL != 0, shift)
+    }
+
     /// Check if key+shift combo is a raw mode prefix character
     /// Raw prefixes: @ # : /
     #[allow(dead_code)] // TEMP DISABLED
@@ -460,6 +913,625 @@ impl Engine {
     /// * `ctrl` - true if Cmd/Ctrl/Alt is pressed (bypasses IME)
     /// * `shift` - true if Shift key is pressed (for symbols like @, #, $)
     pub fn on_key_ext(&mut self, key: u16, caps: bool, ctrl: bool, shift: bool) -> Result {
+        // Unfetched chunks of the previous output are dropped
+        self.pending_output.clear();
+        self.pending_cursor = 0;
+        self.shortcut_fired = None;
+        if let Some(undo) = self.shortcut_undo.take() {
+            if !ctrl && self.shortcut_undo_key == Some(key) {
+                let result = self.undo_shortcut(undo);
+                self.update_candidates();
+                return result;
+            }
+        }
+
+        if self.preedit_mode {
+            let result = self.handle_key(key, caps, ctrl, shift);
+            let result = self.track_token(key, caps, ctrl, shift, result);
+            let result = self.preedit_result(key, caps, ctrl, shift, result);
+            self.update_candidates();
+            return result;
+        }
+
+        // Inside a word only letters edit it; anything else goes in at the cursor
+        if self.word_tail > 0 && (ctrl || !keys::is_letter(key)) {
+            self.clear_all();
+        }
+        let tail = self.word_tail;
+        let tail_width: usize = self.screen_widths().iter().rev().take(tail).sum();
+
+        let token = self.token.clone();
+        let screen = self.token.clone();
+        let word = (key == keys::SPACE).then(|| self.buf.clone());
+        let result = if self.output_encoding == Encoding::Unicode {
+            let result = self.handle_key(key, caps, ctrl, shift);
+            let result = self.track_token(key, caps, ctrl, shift, result);
+            self.save_shortcut_undo(key, caps, shift, token, word, &result);
+            result
+        } else {
+            // Screen width of each buffer char before this key, to convert backspace counts
+            let widths = self.screen_widths();
+            let token_widths: Vec<usize> = self
+                .token
+                .iter()
+                .map(|&ch| encoding::encoded_len(ch, self.output_encoding))
+                .collect();
+            let result = self.handle_key(key, caps, ctrl, shift);
+            let result = self.track_token(key, caps, ctrl, shift, result);
+            self.save_shortcut_undo(key, caps, shift, token, word, &result);
+            // Pattern output replaces token chars, not buffer chars
+            let widths = if self.pattern_fired {
+                token_widths
+            } else {
+                widths
+            };
+            self.encode_result(result, &widths)
+        };
+        let result = if tail > 0 {
+            self.edit_inside_word(&screen, tail, tail_width, result)
+        } else {
+            result
+        };
+        self.update_candidates();
+        result
+    }
+
+    /// Rewrite a Result for a cursor `tail` buffer chars before the word end
+    ///
+    /// The key was handled as if typed at the end of the word. The rewrite
+    /// is widened to cover the text after the cursor (`tail_width` units of
+    /// `screen`), which is deleted with `delete_after` and sent again, and
+    /// `cursor_left` moves the cursor back in front of it. A key that didn't
+    /// rewrite the word went in at the cursor, so the word ends there.
+    fn edit_inside_word(
+        &mut self,
+        screen: &[char],
+        tail: usize,
+        tail_width: usize,
+        result: Result,
+    ) -> Result {
+        if result.action != Action::Send as u8 || result.has_more() || self.buf.is_empty() {
+            self.clear_all();
+            return result;
+        }
+        let mut backspace = result.backspace as usize;
+        let mut output: Vec<char> = vec![];
+        if backspace < tail_width {
+            let end = screen.len().saturating_sub(backspace);
+            output.extend(&screen[screen.len().saturating_sub(tail_width)..end]);
+            backspace = tail_width;
+        }
+        output.extend(
+            result.chars[..result.count as usize]
+                .iter()
+                .filter_map(|&c| char::from_u32(c)),
+        );
+
+        self.word_tail = tail.min(self.buf.len());
+        let width: usize = self.screen_widths().iter().rev().take(self.word_tail).sum();
+        let mut edited = Result::send((backspace - tail_width) as u8, &output);
+        edited.flags = result.flags;
+        edited.delete_after = tail_width.min(u8::MAX as usize) as u8;
+        edited.cursor_left = width.min(output.len()) as u16;
+        edited
+    }
+
+    /// Turn a key's Result into preedit updates and commits
+    ///
+    /// The Result is applied to the preedit as if it were on screen. Word
+    /// boundaries (space, punctuation) commit the preedit with their char;
+    /// keys that type nothing (Enter, arrows, Ctrl+...) commit it and pass
+    /// through. The returned Result carries the committed text in `chars`,
+    /// with `backspace` counting committed chars to delete first (only when
+    /// a shortcut reaches back past the preedit, e.g. "-" then ">").
+    fn preedit_result(
+        &mut self,
+        key: u16,
+        caps: bool,
+        ctrl: bool,
+        shift: bool,
+        result: Result,
+    ) -> Result {
+        let typed = if key == keys::SPACE {
+            Some(' ')
+        } else {
+            utils::key_to_char_ext(key, caps, shift)
+        };
+        let passes =
+            ctrl || matches!(
+                key,
+                keys::TAB
+                    | keys::RETURN
+                    | keys::ENTER
+                    | keys::ESC
+                    | keys::LEFT
+                    | keys::RIGHT
+                    | keys::UP
+                    | keys::DOWN
+            ) || (typed.is_none() && key != keys::DELETE);
+        // Backspace with nothing composed deletes committed text as usual
+        if key == keys::DELETE && self.preedit.is_empty() && result.action != Action::Send as u8 {
+            return Result::none();
+        }
+
+        let before = self.preedit.clone();
+        let pending = std::mem::take(&mut self.pending_output);
+        let cursor = std::mem::take(&mut self.pending_cursor).max(result.cursor_left as usize);
+        let typed = if passes { None } else { typed };
+        let erase = apply_to_text(&mut self.preedit, key, shift, typed, &result, &pending);
+
+        // Modifier symbols (VIQR "^", Telex "[" → "ư") leave the word open
+        let commit = passes || (keys::is_break_ext(key, shift) && self.buf.is_empty());
+        let text = if commit {
+            // Committed text can't be reopened by backspace
+            self.word_history.clear();
+            self.spaces_after_commit = 0;
+            let text = std::mem::take(&mut self.preedit);
+            // Cursor move in output units
+            self.pending_cursor = self
+                .encode_text(&text[text.len().saturating_sub(cursor)..])
+                .len();
+            text
+        } else {
+            vec![]
+        };
+        let changed = self.preedit != before;
+        if passes && erase == 0 && text.is_empty() {
+            return Result::none();
+        }
+
+        let mut flags = if changed { FLAG_PREEDIT } else { 0 };
+        if !passes {
+            flags |= FLAG_KEY_CONSUMED;
+        }
+        let text = self.encode_text(&text);
+        self.send_text(erase.min(u8::MAX as usize) as u8, &text, flags)
+    }
+
+    /// Text in the output encoding
+    fn encode_text(&self, text: &[char]) -> Vec<char> {
+        if self.output_encoding == Encoding::Unicode {
+            return text.to_vec();
+        }
+        let mut output = Vec::with_capacity(text.len() * 2);
+        for &ch in text {
+            encoding::encode(ch, self.output_encoding, &mut output);
+        }
+        output
+    }
+
+    /// Remember a shortcut expansion on this key for `undo_shortcut`
+    ///
+    /// `token` and `word` are the token and buffer before the key. Skipped
+    /// when the expansion moved the cursor (backspace would not reach it).
+    fn save_shortcut_undo(
+        &mut self,
+        key: u16,
+        caps: bool,
+        shift: bool,
+        token: Vec<char>,
+        word: Option<Buffer>,
+        result: &Result,
+    ) {
+        let Some(trigger) = self.shortcut_fired.clone() else {
+            return;
+        };
+        let erased = result.backspace as usize;
+        if self.shortcut_undo_key.is_none()
+            || result.cursor_left > 0
+            || self.pending_cursor > 0
+            || erased > token.len()
+        {
+            return;
+        }
+        let key_char = if key == keys::SPACE {
+            Some(' ')
+        } else {
+            utils::key_to_char_ext(key, caps, shift)
+        };
+        let mut typed = token[token.len() - erased..].to_vec();
+        typed.extend(key_char);
+        let mut token = token;
+        token.extend(key_char);
+        let mut output: Vec<char> = result.chars[..result.count as usize]
+            .iter()
+            .filter_map(|&c| char::from_u32(c))
+            .chain(self.pending_output.iter().copied())
+            .collect();
+        if key != keys::SPACE && keys::is_break_ext(key, shift) && !result.key_consumed() {
+            output.extend(key_char);
+        }
+        let erase = if self.output_encoding == Encoding::Unicode {
+            output.len()
+        } else {
+            output
+                .iter()
+                .map(|&ch| encoding::encoded_len(ch, self.output_encoding))
+                .sum()
+        };
+        // Longer expansions can't be erased with one Result
+        let Ok(erase) = u8::try_from(erase) else {
+            return;
+        };
+        self.shortcut_undo = Some(ShortcutUndo {
+            trigger,
+            erase,
+            typed,
+            word: word.filter(|w| !w.is_empty()),
+            token,
+        });
+    }
+
+    /// Put back what was typed before the last shortcut expansion
+    ///
+    /// A word committed by the expansion's space goes back to the word
+    /// history (as if it had been typed without a shortcut) and is not
+    /// expanded again when it is committed once more.
+    fn undo_shortcut(&mut self, undo: ShortcutUndo) -> Result {
+        self.usage.retract_shortcut(&undo.trigger);
+        self.clear();
+        self.word_history.clear();
+        self.spaces_after_commit = 0;
+        self.token_keys.clear();
+        if let Some(word) = undo.word {
+            self.suppressed_shortcut = Some(word.to_full_string());
+            self.word_history.push(word);
+            self.spaces_after_commit = 1;
+            self.token.clear();
+        } else {
+            self.token = undo.token;
+        }
+
+        if self.output_encoding == Encoding::Unicode {
+            return self.send_text(undo.erase, &undo.typed, FLAG_KEY_CONSUMED);
+        }
+        let mut output = Vec::with_capacity(undo.typed.len() * 2);
+        for &ch in &undo.typed {
+            encoding::encode(ch, self.output_encoding, &mut output);
+        }
+        self.send_text(undo.erase, &output, FLAG_KEY_CONSUMED)
+    }
+
+    /// Mirror the key's effect on screen into `token`, then try pattern
+    /// shortcuts on it (after literal shortcuts, which ran in `handle_key`)
+    fn track_token(
+        &mut self,
+        key: u16,
+        caps: bool,
+        ctrl: bool,
+        shift: bool,
+        result: Result,
+    ) -> Result {
+        self.pattern_fired = false;
+        let ends_token = matches!(
+            key,
+            keys::TAB
+                | keys::RETURN
+                | keys::ENTER
+                | keys::ESC
+                | keys::LEFT
+                | keys::RIGHT
+                | keys::UP
+                | keys::DOWN
+        );
+        if ctrl || ends_token {
+            self.token.clear();
+            self.token_keys.clear();
+            return result;
+        }
+
+        let before = self.token.len();
+        let typed = if key == keys::SPACE {
+            Some(' ')
+        } else {
+            utils::key_to_char_ext(key, caps, shift)
+        };
+        apply_to_text(
+            &mut self.token,
+            key,
+            shift,
+            typed,
+            &result,
+            &self.pending_output,
+        );
+        if key == keys::DELETE {
+            self.token_keys.pop();
+        } else {
+            self.token_keys.extend(typed);
+        }
+
+        let result = if self.shortcut_fired.is_some() || key == keys::DELETE || result.has_more() {
+            result
+        } else {
+            let result = self.try_pattern_shortcut(key, before, result);
+            if self.shortcut_fired.is_some() {
+                result
+            } else {
+                self.try_emoji(key, shift, before, result)
+            }
+        };
+
+        if key == keys::SPACE {
+            self.token.clear();
+            self.token_keys.clear();
+        } else if self.token.len() > MAX {
+            self.token.drain(..self.token.len() - MAX);
+        }
+        if self.token_keys.len() > MAX {
+            let cut = self.token_keys.len() - MAX;
+            self.token_keys.drain(..cut);
+        }
+        result
+    }
+
+    /// Expand a pattern shortcut matching the end of `token`
+    ///
+    /// `before` is the token length before the key; `result` is what the key
+    /// already did on screen. The expansion is merged into one Result.
+    fn try_pattern_shortcut(&mut self, key: u16, before: usize, result: Result) -> Result {
+        if !self.enabled || !self.shortcuts.has_patterns() {
+            return result;
+        }
+        let at_space = key == keys::SPACE;
+        let mut text: String = self.token.iter().collect();
+        if at_space && text.pop() != Some(' ') {
+            return result;
+        }
+        let input_method = self.current_input_method();
+        let key_char = at_space.then_some(' ');
+        let Some(m) = self
+            .shortcuts
+            .try_match_pattern(&text, key_char, at_space, input_method)
+        else {
+            return result;
+        };
+
+        // Chars of the token to replace (the match, plus the space)
+        let replaced = m.backspace_count + usize::from(at_space);
+        self.replace_token_end(before, result, replaced, m)
+    }
+
+    /// Expand a `:name:` emoji shortcode on its closing colon
+    ///
+    /// The name is read from the raw keys (`:heart:` must not be spelled
+    /// through Telex); the screen text from the opening colon is replaced.
+    fn try_emoji(&mut self, key: u16, shift: bool, before: usize, result: Result) -> Result {
+        if !self.enabled || !self.emoji || key != keys::SEMICOLON || !shift {
+            return result;
+        }
+        let Some(typed) = self.token_keys.strip_suffix(':') else {
+            return result;
+        };
+        let Some(open) = typed.rfind(':') else {
+            return result;
+        };
+        let name = &typed[open + 1..];
+        let at_word_start = typed[..open]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_alphanumeric());
+        if name.is_empty() || !name.chars().all(emoji::is_alias_char) || !at_word_start {
+            return result;
+        }
+        let Some(symbol) = emoji::lookup(name) else {
+            return result;
+        };
+        let Some(open_on_screen) = self.token[..self.token.len().saturating_sub(1)]
+            .iter()
+            .rposition(|&c| c == ':')
+        else {
+            return result;
+        };
+
+        let replaced = self.token.len() - open_on_screen;
+        let m = ShortcutMatch {
+            trigger: format!(":{}:", name.to_ascii_lowercase()),
+            backspace_count: replaced,
+            output: symbol.to_string(),
+            include_trigger_key: false,
+            cursor_left: 0,
+        };
+        self.replace_token_end(before, result, replaced, m)
+    }
+
+    /// Replace the last `replaced` chars of `token` with `m.output`
+    ///
+    /// Merged with what the key already did on screen (`result`, applied to
+    /// a token of `before` chars) into one Result.
+    fn replace_token_end(
+        &mut self,
+        before: usize,
+        result: Result,
+        replaced: usize,
+        m: ShortcutMatch,
+    ) -> Result {
+        let backspace = if result.action == Action::Send as u8 {
+            result.backspace as usize
+        } else {
+            0
+        };
+        let added = self.token.len() + backspace.min(before) - before;
+        let mut output: Vec<char> = vec![];
+        let backspace = if replaced <= added {
+            let end = self.token.len() - replaced;
+            output.extend(&self.token[self.token.len() - added..end]);
+            backspace
+        } else {
+            backspace + replaced - added
+        };
+        output.extend(m.output.chars());
+
+        let keep = self.token.len() - replaced;
+        self.token.truncate(keep);
+        self.token_keys.clear();
+        self.clear();
+        self.word_history.clear();
+        self.spaces_after_commit = 0;
+        self.pattern_fired = true;
+        self.send_shortcut(
+            backspace.min(u8::MAX as usize) as u8,
+            &ShortcutMatch {
+                output: output.iter().collect(),
+                ..m
+            },
+            FLAG_KEY_CONSUMED,
+        )
+    }
+
+    /// Suggestions for the current word, best first
+    ///
+    /// Alternative spellings of the buffer ignoring its diacritics
+    /// (e.g. "duoc" → "được", "dược", "dước"). Empty unless suggestion
+    /// mode is on and the buffer has a legal Vietnamese form.
+    pub fn candidates(&self) -> &[String] {
+        &self.candidates
+    }
+
+    /// Replace the current word with suggestion `index`
+    ///
+    /// Returns the backspace/chars replacement (in the output encoding),
+    /// or `Result::none()` if there is no such candidate.
+    pub fn pick_candidate(&mut self, index: usize) -> Result {
+        let Some(word) = self.candidates.get(index).cloned() else {
+            return Result::none();
+        };
+
+        let widths = self.screen_widths();
+        let chars: Vec<char> = word.chars().collect();
+        self.pending_output.clear();
+        self.pending_cursor = 0;
+        let result = Result::send(self.buf.len() as u8, &chars);
+        let kept = self.token.len().saturating_sub(self.buf.len());
+        self.token.truncate(kept);
+        self.token.extend(&chars);
+        let preedit_result = self.preedit_mode.then(|| {
+            let kept = self.preedit.len().saturating_sub(self.buf.len());
+            self.preedit.truncate(kept);
+            self.preedit.extend(&chars);
+            Result::send_consumed(0, &[])
+        });
+
+        self.buf.clear();
+        self.raw_input.clear();
+        self.last_transform = None;
+        self.load_word(&word);
+
+        if let Some(mut result) = preedit_result {
+            result.flags = FLAG_PREEDIT;
+            result
+        } else if self.output_encoding == Encoding::Unicode {
+            result
+        } else {
+            self.encode_result(result, &widths)
+        }
+    }
+
+    /// Recompute suggestions for the buffer
+    fn update_candidates(&mut self) {
+        self.candidates.clear();
+        if !self.suggestions || self.buf.is_empty() {
+            return;
+        }
+        self.candidates = accent::word_candidates(&self.buf.to_full_string());
+        self.candidates.truncate(MAX_CANDIDATES);
+    }
+
+    /// Width (in encoded units) of each buffer char as displayed on screen
+    fn screen_widths(&self) -> Vec<usize> {
+        self.buf
+            .iter()
+            .map(|c| {
+                let ch = if c.key == keys::D && c.stroke {
+                    Some(chars::get_d(c.caps))
+                } else {
+                    chars::to_char(c.key, c.caps, c.tone, c.mark)
+                };
+                ch.map_or(1, |ch| encoding::encoded_len(ch, self.output_encoding))
+            })
+            .collect()
+    }
+
+    /// Convert a Unicode NFC result to the output encoding
+    ///
+    /// Backspace counts refer to chars at the end of the word being typed:
+    /// their widths come from `widths`. Anything beyond the word (spaces, ASCII
+    /// shortcut prefixes) is one unit wide in every encoding.
+    fn encode_result(&mut self, result: Result, widths: &[usize]) -> Result {
+        if result.action != Action::Send as u8 {
+            return result;
+        }
+
+        let backspace: usize = (0..result.backspace as usize)
+            .map(|i| widths.len().checked_sub(i + 1).map_or(1, |pos| widths[pos]))
+            .sum();
+
+        // Encode the whole text (first chunk + pending), then re-chunk
+        let pending = std::mem::take(&mut self.pending_output);
+        let text: Vec<char> = result.chars[..result.count as usize]
+            .iter()
+            .filter_map(|&c| char::from_u32(c))
+            .chain(pending)
+            .collect();
+        let mut output = Vec::with_capacity(text.len() * 2);
+        for &ch in &text {
+            encoding::encode(ch, self.output_encoding, &mut output);
+        }
+
+        // Cursor move in encoded units
+        let cursor = self.pending_cursor.max(result.cursor_left as usize);
+        self.pending_cursor = text[text.len().saturating_sub(cursor)..]
+            .iter()
+            .map(|&ch| encoding::encoded_len(ch, self.output_encoding))
+            .sum();
+
+        let backspace = backspace.min(u8::MAX as usize) as u8;
+        self.send_text(backspace, &output, result.flags & !FLAG_MORE_OUTPUT)
+    }
+
+    /// Send text of any length
+    ///
+    /// The first `MAX` chars go in the Result; the rest is kept for
+    /// `next_output` and signalled with FLAG_MORE_OUTPUT.
+    fn send_text(&mut self, backspace: u8, chars: &[char], flags: u8) -> Result {
+        let split = chars.len().min(MAX);
+        let mut result = Result::send(backspace, &chars[..split]);
+        result.flags = flags;
+        self.pending_output = chars[split..].to_vec();
+        if self.pending_output.is_empty() {
+            result.cursor_left =
+                std::mem::take(&mut self.pending_cursor).min(u16::MAX as usize) as u16;
+        } else {
+            result.flags |= FLAG_MORE_OUTPUT;
+        }
+        result
+    }
+
+    /// Send a shortcut expansion (with its cursor move)
+    fn send_shortcut(&mut self, backspace: u8, m: &ShortcutMatch, flags: u8) -> Result {
+        self.shortcut_fired = Some(m.trigger.clone());
+        self.usage.record_shortcut(&m.trigger);
+        let output: Vec<char> = m.output.chars().collect();
+        self.pending_cursor = m.cursor_left;
+        self.send_text(backspace, &output, flags)
+    }
+
+    /// Next chunk of a long output (after a Result with FLAG_MORE_OUTPUT)
+    ///
+    /// Chunks have no backspace and are appended as-is. Returns
+    /// `Result::none()` when nothing is pending.
+    pub fn next_output(&mut self) -> Result {
+        if self.pending_output.is_empty() {
+            return Result::none();
+        }
+        let rest = std::mem::take(&mut self.pending_output);
+        self.send_text(0, &rest, 0)
+    }
+
+    /// Handle key event (Unicode NFC output)
+    fn handle_key(&mut self, key: u16, caps: bool, ctrl: bool, shift: bool) -> Result {
+        // VIQR escape only applies to the key right after "\"
+        let symbol_escaped = std::mem::take(&mut self.symbol_escape);
+
         // Issue #129: Process shortcuts even when IME is disabled
         // Only bypass completely for Ctrl/Cmd modifier keys
         if ctrl {
@@ -493,10 +1565,9 @@ impl Engine {
                         input_method,
                     ) {
                         // Found a match! Send the replacement
-                        let output: Vec<char> = m.output.chars().collect();
                         let backspace_count = (m.backspace_count as u8).saturating_sub(1);
                         self.shortcut_prefix.clear();
-                        return Result::send_consumed(backspace_count, &output);
+                        return self.send_shortcut(backspace_count, &m, FLAG_KEY_CONSUMED);
                     }
                     // No match yet, keep accumulating
                     return Result::none();
@@ -508,6 +1579,14 @@ impl Engine {
             return Result::none();
         }
 
+        // VIQR escape: "\" + modifier symbol → delete the backslash, type the symbol as-is
+        if symbol_escaped && self.keymap().symbol(key, shift).is_some() {
+            if let Some(ch) = break_key_to_char(key, shift) {
+                self.shortcut_prefix.clear();
+                return Result::send_consumed(1, &[ch]);
+            }
+        }
+
         // Check for word boundary shortcuts ONLY on SPACE
         // Also auto-restore invalid Vietnamese to raw English
         if key == keys::SPACE {
@@ -551,6 +1630,7 @@ impl Engine {
 
             // Push buffer to history before clearing (for backspace-after-space feature)
             if !self.buf.is_empty() {
+                self.usage.commit_word(&self.buf.to_full_string());
                 self.word_history.push(self.buf.clone());
                 self.spaces_after_commit = 1; // First space after word
             } else if self.spaces_after_commit > 0 {
@@ -573,6 +1653,13 @@ impl Engine {
             self.clear();
             self.word_history.clear();
             self.spaces_after_commit = 0;
+            self.usage.end_phrase();
+            return result;
+        }
+
+        // VIQR: punctuation modifiers (' ` ? ~ . ^ + () apply to the current word
+        // Falls through to normal break handling when the symbol can't apply
+        if let Some(result) = self.try_symbol(key, caps, shift) {
             return result;
         }
 
@@ -580,6 +1667,9 @@ impl Engine {
         // Also trigger auto-restore for invalid Vietnamese before clearing
         // Use is_break_ext to handle shifted symbols like @, !, #, etc.
         if keys::is_break_ext(key, shift) {
+            // VIQR: "\" escapes the next modifier symbol
+            let escape_next = self.method == 2 && key == keys::BACKSLASH && !shift;
+
             // Issue #107 + Bug #11: When buffer is empty AND we're at true start of input
             // (no word history), accumulate break chars for shortcuts.
             // This allows shortcuts like "#fne", "->", "=>" to work.
@@ -613,16 +1703,16 @@ impl Engine {
                         // Found a match! Send the replacement with key_consumed flag
                         // Note: backspace_count - 1 because current key hasn't been typed yet
                         // Example: "->" trigger has backspace_count=2, but only '-' is on screen
-                        let output: Vec<char> = m.output.chars().collect();
                         let backspace_count = (m.backspace_count as u8).saturating_sub(1);
                         self.shortcut_prefix.clear();
-                        return Result::send_consumed(backspace_count, &output);
+                        return self.send_shortcut(backspace_count, &m, FLAG_KEY_CONSUMED);
                     }
 
                     // Auto-capitalize: set pending if sentence-ending (! or ?)
                     if self.auto_capitalize && is_sentence_ending(key, shift) {
                         self.pending_capitalize = true;
                     }
+                    self.symbol_escape = escape_next;
                     return Result::none(); // Let the char pass through, keep accumulating
                 }
             }
@@ -638,6 +1728,11 @@ impl Engine {
             self.auto_capitalize_used = false; // Reset on word boundary
 
             let restore_result = self.try_auto_restore_on_break();
+            // Punctuation commits the word and ends the phrase
+            if restore_result.action == 0 && !self.buf.is_empty() {
+                self.usage.commit_word(&self.buf.to_full_string());
+            }
+            self.usage.end_phrase();
             self.clear();
             self.word_history.clear();
             self.spaces_after_commit = 0;
@@ -649,6 +1744,7 @@ impl Engine {
                 self.shortcut_prefix.push(ch);
             }
 
+            self.symbol_escape = escape_next;
             return restore_result;
         }
 
@@ -660,6 +1756,7 @@ impl Engine {
                 if self.spaces_after_commit == 0 {
                     // All spaces deleted - restore the word buffer
                     if let Some(restored_buf) = self.word_history.pop() {
+                        self.usage.retract_word();
                         // Restore raw_input from buffer (for ESC restore to work)
                         self.restore_raw_input_from_buffer(&restored_buf);
                         self.buf = restored_buf;
@@ -709,7 +1806,7 @@ impl Engine {
         // This allows "cha" + restore + "f" → "chà" (f is mark key)
         // But "cha" + restore + "m" → "m..." (m is consonant, start fresh)
         if self.restored_pending_clear && keys::is_letter(key) {
-            let m = input::get(self.method);
+            let m = self.keymap();
             let is_mark_or_tone = m.mark(key).is_some() || m.tone(key).is_some();
             if keys::is_consonant(key) && !is_mark_or_tone {
                 // Regular consonant (not mark/tone key) = user starting new word
@@ -734,6 +1831,11 @@ impl Engine {
             caps
         };
 
+        // A new word ends the suppression of an undone shortcut
+        if self.buf.is_empty() && keys::is_letter(key) {
+            self.suppressed_shortcut = None;
+        }
+
         // Record raw keystroke for ESC restore (letters and numbers only)
         if keys::is_letter(key) || keys::is_number(key) {
             self.raw_input.push((key, effective_caps, shift));
@@ -754,7 +1856,7 @@ impl Engine {
 
     /// Main processing pipeline - pattern-based
     fn process(&mut self, key: u16, caps: bool, shift: bool) -> Result {
-        let m = input::get(self.method);
+        let m = self.keymap();
 
         // Handle pending mark revert pop: if previous key was a mark revert (like "ss"),
         // and THIS key is a consonant, pop the consumed modifier from raw_input.
@@ -879,7 +1981,8 @@ impl Engine {
 
         // 5. In Telex: "w" as vowel "ư" when valid Vietnamese context
         // Examples: "w" → "ư", "nhw" → "như", but "kw" → "kw" (invalid)
-        if self.method == 0 && key == keys::W {
+        // Only while the keymap binds w as horn (custom keymaps may unmap it)
+        if self.method == 0 && key == keys::W && m.tone(key) == Some(ToneType::Horn) {
             if let Some(result) = self.try_w_as_vowel(caps) {
                 return result;
             }
@@ -910,6 +2013,14 @@ impl Engine {
             format!("{}{}", self.shortcut_prefix, self.buf.to_full_string())
         };
 
+        // An undone expansion stays undone for this word
+        if let Some(word) = &self.suppressed_shortcut {
+            if *word == full_trigger {
+                return Result::none();
+            }
+            self.suppressed_shortcut = None;
+        }
+
         let input_method = self.current_input_method();
 
         // Check for word boundary shortcut match
@@ -917,9 +2028,8 @@ impl Engine {
             self.shortcuts
                 .try_match_for_method(&full_trigger, Some(' '), true, input_method)
         {
-            let output: Vec<char> = m.output.chars().collect();
             // backspace_count = trigger.len() which already includes prefix (e.g., "#fne" = 4)
-            return Result::send(m.backspace_count as u8, &output);
+            return self.send_shortcut(m.backspace_count as u8, &m, 0);
         }
 
         Result::none()
@@ -994,7 +2104,7 @@ impl Engine {
         // Use is_valid_with_tones to check modifier requirements (e.g., E+U needs circumflex)
         let buffer_keys: Vec<u16> = self.buf.iter().map(|c| c.key).collect();
         let buffer_tones: Vec<u8> = self.buf.iter().map(|c| c.tone).collect();
-        if is_valid_with_tones(&buffer_keys, &buffer_tones) {
+        if is_valid_with_tones(&buffer_keys, &buffer_tones) && self.is_attested(false) {
             self.last_transform = Some(Transform::WAsVowel);
             self.had_any_transform = true;
 
@@ -1095,8 +2205,8 @@ impl Engine {
 
         // Find position of un-stroked 'd' to apply stroke
         // Also track if this is a short pattern stroke (revertible)
-        let (pos, is_short_pattern_stroke) = if self.method == 0 {
-            // Telex: First try adjacent 'd' (last char is un-stroked d)
+        let (pos, is_short_pattern_stroke) = if self.method != 1 {
+            // Telex/VIQR: First try adjacent 'd' (last char is un-stroked d)
             let last_pos = self.buf.len().checked_sub(1)?;
             let last_char = self.buf.get(last_pos)?;
 
@@ -1104,6 +2214,11 @@ impl Engine {
                 // Adjacent stroke: "dd" → "đ" (not a short pattern)
                 (last_pos, false)
             } else {
+                // VIQR only strokes adjacent "dd" - delayed stroke is a Telex convenience
+                if self.method != 0 {
+                    return None;
+                }
+
                 // Delayed stroke: check if initial 'd' can be stroked
                 // Only allow if: first char is 'd', has vowel, and forms valid Vietnamese
                 let first_char = self.buf.get(0)?;
@@ -2382,6 +3497,123 @@ impl Engine {
         None
     }
 
+    /// Try to apply a punctuation modifier (VIQR: ' ` ? ~ . ^ + (, or custom keymap symbols)
+    ///
+    /// Returns None if the key isn't a modifier for the current method or can't apply
+    /// to the buffer - the key then falls through to normal break handling.
+    /// Applied modifiers set FLAG_KEY_CONSUMED so the platform doesn't type the symbol.
+    fn try_symbol(&mut self, key: u16, caps: bool, shift: bool) -> Option<Result> {
+        let m = self.keymap();
+        let symbol = m.symbol(key, shift)?;
+        if self.buf.is_empty() {
+            return self.try_symbol_vowel(key, caps, shift);
+        }
+
+        // Same symbol twice: undo the modifier and type the symbol literally
+        // Example: "to.." → "to.", "a''" → "a'"
+        let is_repeat = match (symbol, self.last_transform) {
+            (Symbol::Mark(val), Some(Transform::Mark(last_key, last_val))) => {
+                last_key == key && last_val == val
+            }
+            (Symbol::Tone(_), Some(Transform::Tone(last_key, _))) => last_key == key,
+            (Symbol::Stroke, Some(Transform::Stroke(last_key))) => last_key == key,
+            _ => false,
+        };
+        if is_repeat {
+            return Some(self.revert_symbol(symbol, m.symbol_targets(key, shift)));
+        }
+
+        // ` and ~ share a key: a different symbol replaces the mark instead of reverting it
+        self.last_transform = None;
+
+        self.raw_input.push((key, caps, shift));
+        let result = match symbol {
+            Symbol::Mark(mark_val) => self.try_mark(key, caps, mark_val),
+            Symbol::Tone(tone_type) => {
+                let targets = m.symbol_targets(key, shift);
+                self.try_tone(key, caps, tone_type, targets)
+            }
+            Symbol::Stroke => self.try_stroke(key),
+            Symbol::Remove => self.try_remove(),
+        };
+
+        match result {
+            Some(mut result) => {
+                result.flags |= FLAG_KEY_CONSUMED;
+                Some(result)
+            }
+            None => {
+                self.raw_input.pop();
+                self.try_symbol_vowel(key, caps, shift)
+            }
+        }
+    }
+
+    /// Type the vowel of a standalone symbol key (custom keymaps:
+    /// `horn ] u`, "t]" → "tư") when no vowel of the word took the modifier
+    ///
+    /// Only if the word stays valid Vietnamese; otherwise the symbol is typed.
+    fn try_symbol_vowel(&mut self, key: u16, caps: bool, shift: bool) -> Option<Result> {
+        let m = self.keymap();
+        let vowel = m.symbol_vowel(key, shift)?;
+        let Some(Symbol::Tone(tone_type)) = m.symbol(key, shift) else {
+            return None;
+        };
+
+        let mut c = Char::new(vowel, caps);
+        c.tone = tone_type.value();
+        self.buf.push(c);
+        let buffer_keys: Vec<u16> = self.buf.iter().map(|c| c.key).collect();
+        let buffer_tones: Vec<u8> = self.buf.iter().map(|c| c.tone).collect();
+        if !is_valid_with_tones(&buffer_keys, &buffer_tones) || !self.is_attested(false) {
+            self.buf.pop();
+            return None;
+        }
+
+        self.raw_input.push((key, caps, shift));
+        self.last_transform = None;
+        self.had_any_transform = true;
+        let ch = chars::to_char(vowel, caps, tone_type.value(), 0)?;
+        Some(Result::send_consumed(0, &[ch]))
+    }
+
+    /// Undo a punctuation modifier and end the word
+    /// The symbol itself is passed through by the platform (key not consumed)
+    fn revert_symbol(&mut self, symbol: Symbol, targets: &[u16]) -> Result {
+        let mut pos = None;
+        for i in self.buf.find_vowels().into_iter().rev() {
+            if let Some(c) = self.buf.get_mut(i) {
+                match symbol {
+                    Symbol::Mark(_) if c.mark > mark::NONE => {
+                        c.mark = mark::NONE;
+                        pos = Some(i);
+                        break;
+                    }
+                    // Horn may cover two vowels (ươ) - clear all of them
+                    Symbol::Tone(tone_type)
+                        if c.tone == tone_type.value() && targets.contains(&c.key) =>
+                    {
+                        c.tone = tone::NONE;
+                        pos = Some(i);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if symbol == Symbol::Stroke {
+            pos = self.buf.iter().position(|c| c.key == keys::D && c.stroke);
+            if let Some(c) = pos.and_then(|i| self.buf.get_mut(i)) {
+                c.stroke = false;
+            }
+        }
+
+        let result = pos.map_or(Result::none(), |pos| self.rebuild_from(pos));
+        self.clear();
+        self.word_history.clear();
+        self.spaces_after_commit = 0;
+        result
+    }
+
     /// Handle normal letter input
     fn handle_normal_letter(&mut self, key: u16, caps: bool) -> Result {
         // Special case: "o" after "w→ư" should form "ươ" compound
@@ -2630,7 +3862,7 @@ impl Engine {
             // not true consonants. User typing "đườ" + 's' wants to add sắc mark, not restore.
             //
             // Only run if english_auto_restore is enabled (experimental feature)
-            let im = input::get(self.method);
+            let im = self.keymap();
             let is_mark_key = im.mark(key).is_some();
             if self.english_auto_restore
                 && keys::is_consonant(key)
@@ -2813,6 +4045,7 @@ impl Engine {
         }
         self.buf.clear();
         self.raw_input.clear();
+        self.word_tail = 0;
         self.last_transform = None;
         self.has_non_letter_prefix = false;
         self.pending_breve_pos = None;
@@ -2824,6 +4057,7 @@ impl Engine {
         self.had_vowel_triggered_circumflex = false;
         self.restored_pending_clear = false;
         self.shortcut_prefix.clear();
+        self.candidates.clear();
     }
 
     /// Clear everything including word history
@@ -2833,13 +4067,28 @@ impl Engine {
         self.clear();
         self.word_history.clear();
         self.spaces_after_commit = 0;
+        self.token.clear();
+        self.token_keys.clear();
+        self.shortcut_undo = None;
+        self.suppressed_shortcut = None;
+        self.preedit.clear();
+        self.usage.end_phrase();
     }
 
     /// Get the full composed buffer as a Vietnamese string with diacritics.
     ///
     /// Used for "Select All + Replace" injection method.
+    /// Returned in the output encoding.
     pub fn get_buffer_string(&self) -> String {
-        self.buf.to_full_string()
+        let full = self.buf.to_full_string();
+        if self.output_encoding == Encoding::Unicode {
+            return full;
+        }
+        let mut out = Vec::with_capacity(full.len());
+        for c in full.chars() {
+            encoding::encode(c, self.output_encoding, &mut out);
+        }
+        out.into_iter().collect()
     }
 
     /// Debug: Check if vowel-triggered circumflex flag is set
@@ -2863,7 +4112,67 @@ impl Engine {
     /// Parses Vietnamese characters back to buffer components.
     pub fn restore_word(&mut self, word: &str) {
         self.clear();
-        for c in word.chars() {
+        self.load_word(word);
+        self.update_candidates();
+    }
+
+    /// Pick up the word under the cursor from the text around it
+    ///
+    /// `text` is the app's text near the cursor (in the output encoding) and
+    /// `cursor` the cursor position in it, in chars. When the cursor is in or
+    /// right after a Vietnamese word, that word becomes the buffer again so
+    /// the next keys add or change its marks in place (click after "chao",
+    /// type "f" → "chào"; click in "Viet", type "j" → "Việt"). Inside a word
+    /// the Results also delete and resend the text after the cursor
+    /// (`delete_after`, `cursor_left`). After a non-letter or past the end of
+    /// `text` a new word starts. Ignored while a preedit is composed, since
+    /// apps don't include it in the surrounding text.
+    pub fn set_surrounding_text(&mut self, text: &str, cursor: usize) {
+        if !self.preedit.is_empty() {
+            return;
+        }
+        let chars: Vec<char> = text.chars().collect();
+        if cursor > chars.len() {
+            self.clear_all();
+            return;
+        }
+        let before: String = chars[..cursor].iter().collect();
+        let after: String = chars[cursor..].iter().collect();
+        let before = encoding::decode(&before, self.output_encoding);
+        let after = encoding::decode(&after, self.output_encoding);
+
+        let in_word = |c: char| c.is_alphabetic() || ('\u{300}'..='\u{36F}').contains(&c);
+        let mut head: Vec<char> = before.chars().rev().take_while(|&c| in_word(c)).collect();
+        head.reverse();
+        let head = encoding::compose(&head.into_iter().collect::<String>());
+        let tail: String = after.chars().take_while(|&c| in_word(c)).collect();
+        let tail = encoding::compose(&tail);
+        let word = format!("{}{}", head, tail);
+        // Preedit frontends can't delete text after the cursor
+        if head.is_empty()
+            || (!tail.is_empty() && self.preedit_mode)
+            || !word.chars().all(|c| chars::parse_char(c).is_some())
+        {
+            self.clear_all();
+            return;
+        }
+
+        // Still typing this word: keep its keystrokes
+        let tail = tail.chars().count();
+        if self.buf.to_full_string() == word && self.word_tail == tail {
+            return;
+        }
+        self.clear_all();
+        self.load_word(&word);
+        self.word_tail = tail;
+        self.token = self.encode_text(&word.chars().collect::<Vec<_>>());
+        self.update_candidates();
+    }
+
+    /// Append the components of a Vietnamese word to the buffer
+    fn load_word(&mut self, word: &str) {
+        // Screen text may be decomposed (NFD output encoding)
+        for c in encoding::compose(word).chars() {
             if let Some(parsed) = chars::parse_char(c) {
                 let mut ch = Char::new(parsed.key, parsed.caps);
                 ch.tone = parsed.tone;
@@ -2890,6 +4199,19 @@ impl Engine {
             return None;
         }
 
+        // User exception lists override every heuristic below
+        if !self.restore_exceptions.is_empty() {
+            let raw: String = self.raw_input_chars().into_iter().collect();
+            let output = self.buf.to_full_string();
+            match self.restore_exceptions.check(&raw, &output) {
+                Some(Exception::Keep) => return None,
+                Some(Exception::Restore) if raw != output => {
+                    return Some(self.raw_input_chars());
+                }
+                _ => {}
+            }
+        }
+
         // If no Vietnamese transforms were ever applied this word, nothing to restore
         // This prevents false restore for words with numbers/symbols like "nhatkha1407@gmail.com"
         // where the buffer is invalid Vietnamese but no transforms were ever attempted
@@ -2906,6 +4228,16 @@ impl Engine {
         let has_marks_or_tones = self.buf.iter().any(|c| c.tone > 0 || c.mark > 0);
         let has_stroke = self.buf.iter().any(|c| c.stroke);
 
+        // Known English word: restore even when the result is valid Vietnamese
+        // ("docs" → "dóc") or was reverted ("pass" → "pas"), unless it is a
+        // common Vietnamese syllable ("cos" → "có", "rust" → "rút")
+        if is_word_complete
+            && self.is_raw_input_english_word()
+            && !dictionary::is_common(&self.buf.to_full_string())
+        {
+            return Some(self.raw_input_chars());
+        }
+
         // If no transforms remain in buffer AND user reverted at END of word,
         // keep the result (user intentionally reverted)
         // Examples: "ass" → "as", "maxx" → "max" (double modifier at end)
@@ -3036,32 +4368,16 @@ impl Engine {
             return false;
         }
 
-        // Check if it's a vowel tone key (Telex: a, e, o for circumflex; w for horn/breve)
-        // These are always intentional reverts - no English words use double vowels like this
-        if self.method == 0 {
-            if matches!(last_key, keys::A | keys::E | keys::O | keys::W) {
-                return true;
-            }
-        } else {
-            // VNI: 6, 7, 8 for vowel tones
-            if matches!(last_key, keys::N6 | keys::N7 | keys::N8) {
-                return true;
-            }
+        // Check if it's a vowel tone key (Telex: a, e, o for circumflex; w for horn/breve;
+        // VNI: 6, 7, 8). These are always intentional reverts - no English words use
+        // double vowels like this
+        let m = self.keymap();
+        if m.tone(last_key).is_some() {
+            return true;
         }
 
-        // Check if it's a mark key
-        let is_mark_key = if self.method == 0 {
-            // Telex tone modifiers: s, f, r, x, j
-            matches!(last_key, keys::S | keys::F | keys::R | keys::X | keys::J)
-        } else {
-            // VNI tone modifiers: 1, 2, 3, 4, 5
-            matches!(
-                last_key,
-                keys::N1 | keys::N2 | keys::N3 | keys::N4 | keys::N5
-            )
-        };
-
-        if !is_mark_key {
+        // Check if it's a mark key (Telex: s, f, r, x, j; VNI: 1-5)
+        if m.mark(last_key).is_none() {
             return false;
         }
 
@@ -3079,15 +4395,9 @@ impl Engine {
         }
 
         // For longer words (5+ chars), check modifier type:
-        // - 'x', 'j' (Telex) or VNI numbers: not common doubles in English → keep
-        // - 's', 'f', 'r' (Telex): very common doubles in English (bass, staff, error) → restore
-        if self.method == 0 {
-            // Telex: only keep for uncommon double letters (x, j)
-            matches!(last_key, keys::X | keys::J)
-        } else {
-            // VNI: number modifiers are always intentional → keep
-            true
-        }
+        // - digits (VNI) and letters that rarely double in English (x, j): keep
+        // - 's', 'f', 'r': very common doubles in English (bass, staff, error) → restore
+        !keys::is_letter(last_key) || !matches!(last_key, keys::S | keys::F | keys::R)
     }
 
     /// Check if buffer is NOT valid Vietnamese (for unified auto-restore logic)
@@ -3104,7 +4414,14 @@ impl Engine {
         let buffer_tones: Vec<u8> = self.buf.iter().map(|c| c.tone).collect();
 
         // Use full validation with tone info for accurate Vietnamese checking
-        !validation::is_valid_with_tones(&buffer_keys, &buffer_tones)
+        !validation::is_valid_with_tones(&buffer_keys, &buffer_tones) || !self.is_attested(true)
+    }
+
+    /// Check buffer against the dictionary (always true when disabled)
+    ///
+    /// `complete`: whole word typed (exact match) vs mid-word (prefix match)
+    fn is_attested(&self, complete: bool) -> bool {
+        !self.dictionary_validation || validation::is_attested(&self.buf.to_full_string(), complete)
     }
 
     /// Check if raw_input is valid English (for unified auto-restore logic)
@@ -3118,10 +4435,13 @@ impl Engine {
         }
 
         // All keys must be ASCII letters (A-Z)
-        let all_ascii_letters = self.raw_input.iter().all(|(k, _, _)| {
+        // VIQR: apostrophe is part of English words ("it's", "don't")
+        let all_ascii_letters = self.raw_input.iter().all(|&(k, _, shift)| {
             // Keys are in range A-Z (from keys.rs)
             // Consonants and vowels are valid English letters
-            keys::is_consonant(*k) || keys::is_vowel(*k)
+            keys::is_consonant(k)
+                || keys::is_vowel(k)
+                || (self.method == 2 && k == keys::QUOTE && !shift)
         });
 
         if !all_ascii_letters {
@@ -3140,6 +4460,20 @@ impl Engine {
         has_vowel
     }
 
+    /// Check if raw_input spells a word from the English word list
+    fn is_raw_input_english_word(&self) -> bool {
+        let word: String = self.raw_input_chars().into_iter().collect();
+        word.len() == self.raw_input.len() && self.english_words.contains(&word)
+    }
+
+    /// raw_input as typed (keys that have no character are skipped)
+    fn raw_input_chars(&self) -> Vec<char> {
+        self.raw_input
+            .iter()
+            .filter_map(|&(key, caps, shift)| utils::key_to_char_ext(key, caps, shift))
+            .collect()
+    }
+
     /// Build raw chars from raw_input for restore
     ///
     /// When a mark was reverted (e.g., "ss" → "s"), decide between buffer and raw_input:
@@ -3510,8 +4844,7 @@ impl Engine {
             let (second_last_key, _, _) = self.raw_input[len - 2];
 
             // Check for double modifier at end (ss, rr, ff, xx, jj)
-            let tone_modifiers = [keys::S, keys::F, keys::R, keys::X, keys::J];
-            if tone_modifiers.contains(&last_key) && last_key == second_last_key {
+            if self.is_mark_key(last_key) && last_key == second_last_key {
                 // Count occurrences of this modifier key in raw_input
                 let occurrence_count = self
                     .raw_input
@@ -3563,7 +4896,7 @@ impl Engine {
                 // This means mark modifiers (s, f, r, x, j) immediately after W are tone marks
                 // for the ư vowel, not consonants.
                 // Examples: "wf" → "ừ", "ws" → "ứ", "wmf" → "ừm"
-                let tone_modifiers = [keys::S, keys::F, keys::R, keys::X, keys::J];
+                let tone_modifiers = self.mark_keys();
 
                 // Check for "W + only mark modifiers" pattern → valid Vietnamese (ừ, ứ, ử, ữ, ự)
                 // This handles standalone W with tone marks like "wf " → "ừ "
@@ -3720,7 +5053,7 @@ impl Engine {
                             }
 
                             // Check if there's ANY tone modifier (j/s/f/r/x) in the rest of the word
-                            let tone_modifiers = [keys::S, keys::F, keys::R, keys::X, keys::J];
+                            let tone_modifiers = self.mark_keys();
                             let has_tone_modifier = self.raw_input[2..]
                                 .iter()
                                 .any(|(k, _, _)| tone_modifiers.contains(k));
@@ -3736,7 +5069,7 @@ impl Engine {
         }
 
         // Telex modifiers that add tone marks
-        let tone_modifiers = [keys::S, keys::F, keys::R, keys::X, keys::J];
+        let tone_modifiers = self.mark_keys();
 
         // Pattern: Consecutive tone modifiers followed by VOWEL (English pattern)
         // Example: "cursor" = c-u-r-s-o-r → "rs" followed by vowel 'o' → English
@@ -4065,7 +5398,7 @@ impl Engine {
         // Counter-example: "leex" = l + ee + x → L is common Vietnamese initial → keep "lễ"
         // Counter-example: "meex" = m + ee + x → M is common Vietnamese initial → keep "mễ"
         // Counter-example: "soos" = s + oo + s → "số" (Vietnamese for "number") - O vowel is common
-        let tone_modifiers = [keys::S, keys::F, keys::R, keys::X, keys::J];
+        let tone_modifiers = self.mark_keys();
         if self.raw_input.len() >= 4 {
             let (first, _, _) = self.raw_input[0];
             let (last, _, _) = self.raw_input[self.raw_input.len() - 1];
@@ -4113,7 +5446,7 @@ impl Engine {
 
             let is_consonant_0 = keys::is_consonant(c0);
             let is_vowel_1 = keys::is_vowel(c1);
-            let is_tone_2 = matches!(c2, keys::S | keys::F | keys::R | keys::X | keys::J);
+            let is_tone_2 = self.is_mark_key(c2);
             let is_circumflex_vowel_34 = matches!(c3, keys::A | keys::E | keys::O) && c3 == c4;
 
             if is_consonant_0 && is_vowel_1 && is_tone_2 && is_circumflex_vowel_34 {
@@ -4131,7 +5464,7 @@ impl Engine {
             let (last, _, _) = self.raw_input[self.raw_input.len() - 1];
             if last == keys::K {
                 let (second_last, _, _) = self.raw_input[self.raw_input.len() - 2];
-                let tone_modifiers = [keys::S, keys::F, keys::R, keys::X, keys::J];
+                let tone_modifiers = self.mark_keys();
                 // Check if second_last is a tone modifier (s, f, r, x, j)
                 if tone_modifiers.contains(&second_last) {
                     // Key insight: if modifier was consumed (applied to vowel),
@@ -4230,13 +5563,13 @@ impl Engine {
     /// valid Vietnamese, restore to original English + space.
     /// Example: "tẽt" (from typing "text") → "text " (restored + space)
     /// Example: "ễpct" (from typing "expect") → "expect " (restored + space)
-    fn try_auto_restore_on_space(&self) -> Result {
+    fn try_auto_restore_on_space(&mut self) -> Result {
         if let Some(mut raw_chars) = self.should_auto_restore(true) {
             // Add space at the end
             raw_chars.push(' ');
             // Backspace count = current buffer length (displayed chars)
             let backspace = self.buf.len() as u8;
-            Result::send(backspace, &raw_chars)
+            self.send_text(backspace, &raw_chars, 0)
         } else {
             Result::none()
         }
@@ -4248,11 +5581,11 @@ impl Engine {
     /// but result is not valid Vietnamese, restore to original English.
     /// Does NOT include the break key (it's passed through by the app).
     /// Example: "ễpct" + comma → "expect" (comma added by app)
-    fn try_auto_restore_on_break(&self) -> Result {
+    fn try_auto_restore_on_break(&mut self) -> Result {
         if let Some(raw_chars) = self.should_auto_restore(true) {
             // Backspace count = current buffer length (displayed chars)
             let backspace = self.buf.len() as u8;
-            Result::send(backspace, &raw_chars)
+            self.send_text(backspace, &raw_chars, 0)
         } else {
             Result::none()
         }
@@ -4262,7 +5595,7 @@ impl Engine {
     ///
     /// Called when ESC is pressed. Replaces transformed output with original keystrokes.
     /// Example: "tẽt" (from typing "text" in Telex) → "text"
-    fn restore_to_raw(&self) -> Result {
+    fn restore_to_raw(&mut self) -> Result {
         if self.raw_input.is_empty() || self.buf.is_empty() {
             return Result::none();
         }
@@ -4290,7 +5623,7 @@ impl Engine {
         // Backspace count = current buffer length (displayed chars)
         let backspace = self.buf.len() as u8;
 
-        Result::send(backspace, &raw_chars)
+        self.send_text(backspace, &raw_chars, 0)
     }
 
     /// Restore raw_input from buffer (for ESC restore to work after backspace-restore)
@@ -0,0 +1,537 @@
+//! Pattern Triggers - Small regex subset for shortcut triggers
+//!
+//! Lets one shortcut cover a family of inputs ("100k", "25k" → "nghìn"):
+//!
+//! | Syntax          | Matches                                       |
+//! |-----------------|-----------------------------------------------|
+//! | `a`             | The char itself (case-insensitive)            |
+//! | `.`             | Any char                                      |
+//! | `\d` `\w`       | Digit; letter or digit                        |
+//! | `\x`            | `x` literally (`\.`, `\(`, `\\`...)           |
+//! | `[a-z0-9_]`     | Any listed char or range, `[^...]` negates    |
+//! | `(...)`         | Capture group, referenced as `{1}`-`{9}`      |
+//! | `a\|b`          | Either side                                   |
+//! | `*` `+` `?`     | Repeat the previous item (greedy)             |
+//!
+//! Patterns match the end of the typed text, starting at a word start
+//! (text start or after a non-alphanumeric char), so `\d+k` matches
+//! "giá 100k" but not "a100k".
+//!
+//! Matching is a backtracking walk over a compiled program, with failed
+//! (instruction, position) pairs remembered so time stays linear in
+//! pattern × text length.
+
+use std::fmt;
+
+/// Capture groups a pattern may define (`{1}`-`{9}`)
+pub const MAX_GROUPS: usize = 9;
+
+/// Invalid pattern
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternError {
+    /// Char index in the pattern
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.message, self.position)
+    }
+}
+
+/// Successful match
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternMatch {
+    /// Char index where the match starts (it always runs to the end)
+    pub start: usize,
+    /// Matched text as typed
+    pub text: String,
+    /// Group texts as typed, `groups[0]` is group 1 (empty if unused)
+    pub groups: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+enum Class {
+    Digit,
+    Word,
+    Set {
+        ranges: Vec<(char, char)>,
+        negated: bool,
+    },
+}
+
+impl Class {
+    fn contains(&self, c: char) -> bool {
+        match self {
+            Class::Digit => c.is_ascii_digit(),
+            Class::Word => c.is_alphanumeric(),
+            Class::Set { ranges, negated } => {
+                ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi) != *negated
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Empty,
+    Char(char),
+    Any,
+    Class(Class),
+    Concat(Vec<Node>),
+    Alt(Vec<Node>),
+    Group(usize, Box<Node>),
+    Star(Box<Node>),
+    Plus(Box<Node>),
+    Quest(Box<Node>),
+}
+
+impl Node {
+    /// Can match without consuming input
+    fn nullable(&self) -> bool {
+        match self {
+            Node::Empty | Node::Star(_) | Node::Quest(_) => true,
+            Node::Char(_) | Node::Any | Node::Class(_) => false,
+            Node::Concat(nodes) => nodes.iter().all(Node::nullable),
+            Node::Alt(nodes) => nodes.iter().any(Node::nullable),
+            Node::Group(_, node) | Node::Plus(node) => node.nullable(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Inst {
+    Char(char),
+    Any,
+    Class(Class),
+    /// Try the first target, then the second
+    Split(usize, usize),
+    Jmp(usize),
+    /// Record the position in capture slot n
+    Save(usize),
+    Match,
+}
+
+/// Compiled pattern
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    program: Vec<Inst>,
+    groups: usize,
+}
+
+impl Pattern {
+    /// Compile a pattern (matching ignores case)
+    pub fn parse(pattern: &str) -> Result<Self, PatternError> {
+        let mut parser = Parser {
+            chars: pattern.chars().collect(),
+            pos: 0,
+            groups: 0,
+        };
+        let node = parser.alternation()?;
+        if parser.pos < parser.chars.len() {
+            return Err(parser.error("unmatched ')'"));
+        }
+        if node.nullable() {
+            return Err(PatternError {
+                position: 0,
+                message: "pattern matches empty text".to_string(),
+            });
+        }
+
+        let mut program = vec![];
+        compile(&node, &mut program);
+        program.push(Inst::Match);
+        Ok(Self {
+            program,
+            groups: parser.groups,
+        })
+    }
+
+    /// Number of capture groups
+    pub fn groups(&self) -> usize {
+        self.groups
+    }
+
+    /// Match the end of `text`, from the earliest word start that matches
+    pub fn match_end(&self, text: &str) -> Option<PatternMatch> {
+        let typed: Vec<char> = text.chars().collect();
+        let lower: Vec<char> = typed.iter().map(|&c| fold_case(c)).collect();
+        let slice = |from: usize, to: usize| typed[from..to].iter().collect::<String>();
+
+        for start in 0..lower.len() {
+            if start > 0 && lower[start - 1].is_alphanumeric() {
+                continue;
+            }
+            let mut matcher = Matcher {
+                program: &self.program,
+                text: &lower,
+                failed: vec![false; self.program.len() * (lower.len() + 1)],
+                slots: vec![None; 2 * self.groups],
+            };
+            if matcher.run(0, start) {
+                let groups = matcher
+                    .slots
+                    .chunks(2)
+                    .map(|slot| match (slot[0], slot[1]) {
+                        (Some(from), Some(to)) if from <= to => slice(from, to),
+                        _ => String::new(),
+                    })
+                    .collect();
+                return Some(PatternMatch {
+                    start,
+                    text: slice(start, typed.len()),
+                    groups,
+                });
+            }
+        }
+        None
+    }
+}
+
+/// Single-char lowercase (chars whose lowercase is longer are kept)
+fn fold_case(c: char) -> char {
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(l), None) => l,
+        _ => c,
+    }
+}
+
+struct Matcher<'a> {
+    program: &'a [Inst],
+    text: &'a [char],
+    /// (pc, pos) pairs known to fail
+    failed: Vec<bool>,
+    /// Capture positions, two per group
+    slots: Vec<Option<usize>>,
+}
+
+impl Matcher<'_> {
+    fn run(&mut self, mut pc: usize, mut pos: usize) -> bool {
+        loop {
+            let key = pc * (self.text.len() + 1) + pos;
+            if self.failed[key] {
+                return false;
+            }
+            let next = match &self.program[pc] {
+                Inst::Match => return pos == self.text.len(),
+                Inst::Char(c) => self.text.get(pos) == Some(c),
+                Inst::Any => pos < self.text.len(),
+                Inst::Class(class) => self.text.get(pos).is_some_and(|&c| class.contains(c)),
+                Inst::Jmp(target) => {
+                    pc = *target;
+                    continue;
+                }
+                Inst::Split(first, second) => {
+                    let (first, second) = (*first, *second);
+                    if self.run(first, pos) || self.run(second, pos) {
+                        return true;
+                    }
+                    self.failed[key] = true;
+                    return false;
+                }
+                Inst::Save(slot) => {
+                    let slot = *slot;
+                    let old = self.slots[slot].replace(pos);
+                    if self.run(pc + 1, pos) {
+                        return true;
+                    }
+                    self.slots[slot] = old;
+                    self.failed[key] = true;
+                    return false;
+                }
+            };
+            if !next {
+                self.failed[key] = true;
+                return false;
+            }
+            pc += 1;
+            pos += 1;
+        }
+    }
+}
+
+fn compile(node: &Node, program: &mut Vec<Inst>) {
+    match node {
+        Node::Empty => {}
+        Node::Char(c) => program.push(Inst::Char(*c)),
+        Node::Any => program.push(Inst::Any),
+        Node::Class(class) => program.push(Inst::Class(class.clone())),
+        Node::Concat(nodes) => nodes.iter().for_each(|n| compile(n, program)),
+        Node::Alt(nodes) => {
+            // split(first, next split) ... each branch jumps to the end
+            let mut jumps = vec![];
+            for (i, n) in nodes.iter().enumerate() {
+                if i + 1 < nodes.len() {
+                    let split = program.len();
+                    program.push(Inst::Split(split + 1, 0));
+                    compile(n, program);
+                    jumps.push(program.len());
+                    program.push(Inst::Jmp(0));
+                    program[split] = Inst::Split(split + 1, program.len());
+                } else {
+                    compile(n, program);
+                }
+            }
+            let end = program.len();
+            for jump in jumps {
+                program[jump] = Inst::Jmp(end);
+            }
+        }
+        Node::Group(index, n) => {
+            program.push(Inst::Save(2 * (index - 1)));
+            compile(n, program);
+            program.push(Inst::Save(2 * (index - 1) + 1));
+        }
+        Node::Star(n) => {
+            let split = program.len();
+            program.push(Inst::Split(split + 1, 0));
+            compile(n, program);
+            program.push(Inst::Jmp(split));
+            program[split] = Inst::Split(split + 1, program.len());
+        }
+        Node::Plus(n) => {
+            let start = program.len();
+            compile(n, program);
+            program.push(Inst::Split(start, program.len() + 1));
+        }
+        Node::Quest(n) => {
+            let split = program.len();
+            program.push(Inst::Split(split + 1, 0));
+            compile(n, program);
+            program[split] = Inst::Split(split + 1, program.len());
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    groups: usize,
+}
+
+impl Parser {
+    fn error(&self, message: &str) -> PatternError {
+        PatternError {
+            position: self.pos,
+            message: message.to_string(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn alternation(&mut self) -> Result<Node, PatternError> {
+        let mut branches = vec![self.concat()?];
+        while self.peek() == Some('|') {
+            self.pos += 1;
+            branches.push(self.concat()?);
+        }
+        Ok(if branches.len() == 1 {
+            branches.pop().unwrap_or(Node::Empty)
+        } else {
+            Node::Alt(branches)
+        })
+    }
+
+    fn concat(&mut self) -> Result<Node, PatternError> {
+        let mut nodes = vec![];
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            nodes.push(self.quantified(atom)?);
+        }
+        Ok(match nodes.len() {
+            0 => Node::Empty,
+            1 => nodes.pop().unwrap_or(Node::Empty),
+            _ => Node::Concat(nodes),
+        })
+    }
+
+    fn quantified(&mut self, atom: Node) -> Result<Node, PatternError> {
+        let Some(q @ ('*' | '+' | '?')) = self.peek() else {
+            return Ok(atom);
+        };
+        if atom.nullable() {
+            return Err(self.error("nothing to repeat"));
+        }
+        self.pos += 1;
+        if matches!(self.peek(), Some('*' | '+' | '?')) {
+            return Err(self.error("nested quantifier"));
+        }
+        let atom = Box::new(atom);
+        Ok(match q {
+            '*' => Node::Star(atom),
+            '+' => Node::Plus(atom),
+            _ => Node::Quest(atom),
+        })
+    }
+
+    fn atom(&mut self) -> Result<Node, PatternError> {
+        let c = self.peek().ok_or_else(|| self.error("unexpected end"))?;
+        self.pos += 1;
+        match c {
+            '.' => Ok(Node::Any),
+            '*' | '+' | '?' => {
+                self.pos -= 1;
+                Err(self.error("nothing to repeat"))
+            }
+            '\\' => Ok(match self.escape()? {
+                Escape::Char(c) => Node::Char(fold_case(c)),
+                Escape::Class(class) => Node::Class(class),
+            }),
+            '[' => self.class(),
+            '(' => {
+                if self.groups == MAX_GROUPS {
+                    return Err(self.error("too many groups"));
+                }
+                self.groups += 1;
+                let index = self.groups;
+                let inner = self.alternation()?;
+                if self.peek() != Some(')') {
+                    return Err(self.error("missing ')'"));
+                }
+                self.pos += 1;
+                Ok(Node::Group(index, Box::new(inner)))
+            }
+            c => Ok(Node::Char(fold_case(c))),
+        }
+    }
+
+    /// After a backslash
+    fn escape(&mut self) -> Result<Escape, PatternError> {
+        let c = self
+            .peek()
+            .ok_or_else(|| self.error("trailing backslash"))?;
+        self.pos += 1;
+        Ok(match c {
+            'd' => Escape::Class(Class::Digit),
+            'w' => Escape::Class(Class::Word),
+            c => Escape::Char(c),
+        })
+    }
+
+    /// After `[`
+    fn class(&mut self) -> Result<Node, PatternError> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.pos += 1;
+        }
+        let mut ranges = vec![];
+        let mut first = true;
+        loop {
+            let c = self.peek().ok_or_else(|| self.error("missing ']'"))?;
+            self.pos += 1;
+            let lo = match c {
+                ']' if !first => break,
+                '\\' => match self.escape()? {
+                    Escape::Char(c) => fold_case(c),
+                    Escape::Class(Class::Digit) => {
+                        ranges.push(('0', '9'));
+                        first = false;
+                        continue;
+                    }
+                    Escape::Class(_) => return Err(self.error("\\w inside []")),
+                },
+                c => fold_case(c),
+            };
+            first = false;
+            // Range "a-z" (a trailing '-' is literal)
+            if self.peek() == Some('-') && self.chars.get(self.pos + 1).is_some_and(|&c| c != ']') {
+                self.pos += 1;
+                let hi = match self.peek() {
+                    Some('\\') => {
+                        self.pos += 1;
+                        match self.escape()? {
+                            Escape::Char(c) => fold_case(c),
+                            Escape::Class(_) => return Err(self.error("class in range")),
+                        }
+                    }
+                    Some(c) => {
+                        self.pos += 1;
+                        fold_case(c)
+                    }
+                    None => return Err(self.error("missing ']'")),
+                };
+                if hi < lo {
+                    return Err(self.error("range out of order"));
+                }
+                ranges.push((lo, hi));
+            } else {
+                ranges.push((lo, lo));
+            }
+        }
+        Ok(Node::Class(Class::Set { ranges, negated }))
+    }
+}
+
+enum Escape {
+    Char(char),
+    Class(Class),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matched(pattern: &str, text: &str) -> Option<(String, Vec<String>)> {
+        Pattern::parse(pattern)
+            .unwrap()
+            .match_end(text)
+            .map(|m| (m.text, m.groups))
+    }
+
+    #[test]
+    fn test_literals_and_classes() {
+        assert!(matched("abc", "abc").is_some());
+        assert!(matched("abc", "ABC").is_some());
+        assert!(matched("abc", "abcd").is_none());
+        assert!(matched("a.c", "a-c").is_some());
+        assert!(matched("[a-c]+", "cab").is_some());
+        assert!(matched("[^0-9]+", "ab1").is_none());
+        assert!(matched("\\d+k", "100k").is_some());
+        assert!(matched("\\d+k", "k").is_none());
+        assert!(matched("\\w+", "được").is_some());
+        assert!(matched("a\\.b", "a.b").is_some());
+        assert!(matched("a\\.b", "axb").is_none());
+        assert!(matched("[-+]\\d", "-1").is_some());
+    }
+
+    #[test]
+    fn test_word_start() {
+        assert_eq!(matched("\\d+k", "giá 100k").unwrap().0, "100k");
+        assert_eq!(matched("\\d+k", "(25k").unwrap().0, "25k");
+        assert!(matched("\\d+k", "a100k").is_none());
+        // Earliest word start wins
+        assert_eq!(matched(":\\w+:", "::smile:").unwrap().0, ":smile:");
+    }
+
+    #[test]
+    fn test_groups_and_alternation() {
+        let (text, groups) = matched("(\\d+)(k|tr)", "150TR").unwrap();
+        assert_eq!(text, "150TR");
+        assert_eq!(groups, ["150", "TR"]);
+
+        let (_, groups) = matched("(a)?b", "b").unwrap();
+        assert_eq!(groups, [""]);
+
+        // Backtracking into a greedy repeat
+        let (_, groups) = matched("(\\d+)(\\d)", "1234").unwrap();
+        assert_eq!(groups, ["123", "4"]);
+    }
+
+    #[test]
+    fn test_errors() {
+        for bad in [
+            "", "a|", "(a", "a)", "*a", "a**", "[a", "[z-a]", "(a*)*", "\\",
+        ] {
+            assert!(Pattern::parse(bad).is_err(), "{bad}");
+        }
+        let err = Pattern::parse("ab)").unwrap_err();
+        assert_eq!(err.to_string(), "unmatched ')' at 2");
+    }
+}
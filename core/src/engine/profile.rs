@@ -0,0 +1,108 @@
+//! Per-application Profiles
+//!
+//! A profile is a named set of settings plus its own shortcut table
+//! ("code" for terminals/IDEs, "chat" for messengers). Apps are assigned to
+//! profiles by id (bundle id, executable name...); `Engine::set_context`
+//! switches to the app's profile and restores the input method and on/off
+//! state the app had when it was last left.
+//!
+//! The active profile lives in the engine itself, so the existing setters
+//! (`set_free_tone`, `shortcuts_mut`, ...) edit whichever profile is active.
+
+use super::shortcut::ShortcutTable;
+use crate::data::encoding::Encoding;
+use crate::input::CustomMethod;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Profile used by apps without an assignment
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Settings that follow the profile
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Settings {
+    pub skip_w_shortcut: bool,
+    pub esc_restore: bool,
+    pub free_tone: bool,
+    pub modern_tone: bool,
+    pub english_auto_restore: bool,
+    pub dictionary_validation: bool,
+    pub auto_capitalize: bool,
+    pub suggestions: bool,
+    pub emoji: bool,
+    pub shortcut_undo_key: Option<u16>,
+    pub output_encoding: Encoding,
+}
+
+impl Default for Settings {
+    /// Same defaults as `Engine::new`
+    fn default() -> Self {
+        Self {
+            skip_w_shortcut: false,
+            esc_restore: false,
+            free_tone: false,
+            modern_tone: true,
+            english_auto_restore: false,
+            dictionary_validation: false,
+            auto_capitalize: false,
+            suggestions: false,
+            emoji: false,
+            shortcut_undo_key: None,
+            output_encoding: Encoding::Unicode,
+        }
+    }
+}
+
+/// Inactive profile
+#[derive(Debug, Default)]
+pub struct Profile {
+    pub settings: Settings,
+    pub shortcuts: ShortcutTable,
+}
+
+/// Input method and on/off state remembered per app
+#[derive(Debug, Clone)]
+pub(crate) struct AppState {
+    pub method: u8,
+    pub custom_method: Option<Arc<CustomMethod>>,
+    pub enabled: bool,
+}
+
+#[derive(Debug)]
+pub(crate) struct Profiles {
+    /// Inactive profiles by name (the active one is the engine's own state)
+    pub stored: HashMap<String, Profile>,
+    /// Name of the active profile
+    pub active: String,
+    /// App id → profile name
+    pub assignments: HashMap<String, String>,
+    /// App id → state when the app was last left
+    pub apps: HashMap<String, AppState>,
+    /// Current app id (empty until the platform sets one)
+    pub context: String,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self {
+            stored: HashMap::new(),
+            active: DEFAULT_PROFILE.to_string(),
+            assignments: HashMap::new(),
+            apps: HashMap::new(),
+            context: String::new(),
+        }
+    }
+}
+
+impl Profiles {
+    pub fn exists(&self, name: &str) -> bool {
+        name == self.active || self.stored.contains_key(name)
+    }
+
+    /// Profile for an app (default if unassigned)
+    pub fn profile_for(&self, app_id: &str) -> &str {
+        self.assignments
+            .get(app_id)
+            .map_or(DEFAULT_PROFILE, String::as_str)
+    }
+}
@@ -1,16 +1,12 @@
 //! Shortcut Table - Abbreviation expansion
 //!
 //! Allows users to define shortcuts like "vn" → "Việt Nam"
-//! Shortcuts can be specific to input methods (Telex/VNI) or apply to all.
+//! Shortcuts can be specific to input methods (Telex/VNI/VIQR) or apply to all.
 
-use super::buffer::MAX;
+use super::pattern::Pattern;
+use super::template::{Templates, CURSOR_MARKER};
 use std::collections::HashMap;
 
-/// Maximum replacement length in UTF-32 codepoints (matches Result.chars array size)
-/// This limit ensures replacement fits in the FFI result buffer.
-/// Note: Vietnamese characters with diacritics (ồ, ế, ẫ) count as 1 codepoint each.
-pub const MAX_REPLACEMENT_LEN: usize = MAX - 1; // -1 to leave room for trailing space
-
 /// Input method that shortcut applies to
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum InputMethod {
@@ -21,6 +17,8 @@ pub enum InputMethod {
     Telex,
     /// Apply only to VNI
     Vni,
+    /// Apply only to VIQR
+    Viqr,
 }
 
 /// Trigger condition for shortcut
@@ -32,6 +30,17 @@ pub enum TriggerCondition {
     OnWordBoundary,
 }
 
+/// How the trigger is matched
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TriggerKind {
+    /// Trigger equals the typed word
+    #[default]
+    Literal,
+    /// Trigger is a pattern (see `pattern`), checked after literal triggers;
+    /// `{1}`-`{9}` in the replacement insert its capture groups
+    Pattern,
+}
+
 /// Case handling mode
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CaseMode {
@@ -56,74 +65,73 @@ pub struct Shortcut {
     pub enabled: bool,
     /// Which input method this shortcut applies to
     pub input_method: InputMethod,
+    /// Literal or pattern trigger
+    pub kind: TriggerKind,
 }
 
 impl Shortcut {
-    /// Validate and truncate replacement if it exceeds MAX_REPLACEMENT_LEN.
-    /// Counts UTF-32 codepoints (Vietnamese diacritics = 1 codepoint each).
-    fn validate_replacement(replacement: &str) -> String {
-        let char_count = replacement.chars().count();
-        if char_count <= MAX_REPLACEMENT_LEN {
-            replacement.to_string()
-        } else {
-            // Truncate to MAX_REPLACEMENT_LEN codepoints
-            replacement.chars().take(MAX_REPLACEMENT_LEN).collect()
-        }
-    }
-
     /// Create a new shortcut with word boundary trigger (applies to all input methods)
     /// Issue #86: Case-insensitive matching, smart case output (ko→không, KO→KHÔNG, Ko→Không)
-    /// Replacement is truncated to MAX_REPLACEMENT_LEN (63) codepoints if too long.
     pub fn new(trigger: &str, replacement: &str) -> Self {
         Self {
             trigger: trigger.to_lowercase(), // Store lowercase for case-insensitive matching
-            replacement: Self::validate_replacement(replacement),
+            replacement: replacement.to_string(),
             condition: TriggerCondition::OnWordBoundary,
             case_mode: CaseMode::MatchCase, // Smart case transformation
             enabled: true,
             input_method: InputMethod::All,
+            kind: TriggerKind::Literal,
         }
     }
 
     /// Create an immediate trigger shortcut (applies to all input methods).
     /// Issue #86: Case-insensitive matching, smart case output
-    /// Replacement is truncated to MAX_REPLACEMENT_LEN (63) codepoints if too long.
     pub fn immediate(trigger: &str, replacement: &str) -> Self {
         Self {
             trigger: trigger.to_lowercase(), // Store lowercase for case-insensitive matching
-            replacement: Self::validate_replacement(replacement),
+            replacement: replacement.to_string(),
             condition: TriggerCondition::Immediate,
             case_mode: CaseMode::MatchCase, // Smart case transformation
             enabled: true,
             input_method: InputMethod::All,
+            kind: TriggerKind::Literal,
         }
     }
 
     /// Create a Telex-specific shortcut with immediate trigger.
     /// Issue #86: Case-insensitive matching, smart case output
-    /// Replacement is truncated to MAX_REPLACEMENT_LEN (63) codepoints if too long.
     pub fn telex(trigger: &str, replacement: &str) -> Self {
         Self {
             trigger: trigger.to_lowercase(), // Store lowercase for case-insensitive matching
-            replacement: Self::validate_replacement(replacement),
+            replacement: replacement.to_string(),
             condition: TriggerCondition::Immediate,
             case_mode: CaseMode::MatchCase, // Smart case transformation
             enabled: true,
             input_method: InputMethod::Telex,
+            kind: TriggerKind::Literal,
         }
     }
 
     /// Create a VNI-specific shortcut with immediate trigger.
     /// Issue #86: Case-insensitive matching, smart case output
-    /// Replacement is truncated to MAX_REPLACEMENT_LEN (63) codepoints if too long.
     pub fn vni(trigger: &str, replacement: &str) -> Self {
         Self {
             trigger: trigger.to_lowercase(), // Store lowercase for case-insensitive matching
-            replacement: Self::validate_replacement(replacement),
+            replacement: replacement.to_string(),
             condition: TriggerCondition::Immediate,
             case_mode: CaseMode::MatchCase, // Smart case transformation
             enabled: true,
             input_method: InputMethod::Vni,
+            kind: TriggerKind::Literal,
+        }
+    }
+
+    /// Create a pattern shortcut with word boundary trigger
+    /// e.g. `Shortcut::pattern("(\\d+)k", "{1} nghìn")`: "100k" → "100 nghìn"
+    pub fn pattern(pattern: &str, replacement: &str) -> Self {
+        Self {
+            kind: TriggerKind::Pattern,
+            ..Self::new(pattern, replacement)
         }
     }
 
@@ -149,6 +157,9 @@ impl Shortcut {
             InputMethod::Vni => {
                 query_method == InputMethod::Vni || query_method == InputMethod::All
             }
+            InputMethod::Viqr => {
+                query_method == InputMethod::Viqr || query_method == InputMethod::All
+            }
         }
     }
 }
@@ -156,12 +167,17 @@ impl Shortcut {
 /// Shortcut match result
 #[derive(Debug)]
 pub struct ShortcutMatch {
+    /// Trigger that matched (lowercase table key)
+    pub trigger: String,
     /// Number of characters to backspace
     pub backspace_count: usize,
     /// Replacement text to output
     pub output: String,
     /// Whether to include the trigger key in output
     pub include_trigger_key: bool,
+    /// Characters to move the cursor left after inserting `output`
+    /// (set by a `{cursor}` marker in the replacement)
+    pub cursor_left: usize,
 }
 
 /// Shortcut table manager
@@ -171,6 +187,13 @@ pub struct ShortcutTable {
     shortcuts: HashMap<String, Shortcut>,
     /// Sorted triggers by length (longest first) for matching
     sorted_triggers: Vec<String>,
+    /// Compiled pattern triggers, longest first (invalid patterns are left out)
+    patterns: Vec<(String, Pattern)>,
+    /// Clock, clipboard and counters for dynamic replacements
+    templates: Templates,
+    /// Shortcuts lost to a later entry of the same import with the same
+    /// trigger after lowercasing ("VN" and "vn"), see `analyze`
+    replaced: Vec<Shortcut>,
 }
 
 impl ShortcutTable {
@@ -178,6 +201,9 @@ impl ShortcutTable {
         Self {
             shortcuts: HashMap::new(),
             sorted_triggers: vec![],
+            patterns: vec![],
+            templates: Templates::default(),
+            replaced: vec![],
         }
     }
 
@@ -227,17 +253,30 @@ impl ShortcutTable {
         table
     }
 
-    /// Add a shortcut
-    pub fn add(&mut self, shortcut: Shortcut) {
+    /// Add a shortcut, returns the one it replaces (same trigger)
+    pub fn add(&mut self, shortcut: Shortcut) -> Option<Shortcut> {
         let trigger = shortcut.trigger.clone();
-        self.shortcuts.insert(trigger.clone(), shortcut);
+        self.replaced.retain(|s| s.trigger != trigger);
+        let previous = self.shortcuts.insert(trigger, shortcut);
         self.rebuild_sorted_triggers();
+        previous
+    }
+
+    /// Remember a shortcut replaced by a conflicting entry (see `analyze`)
+    pub(super) fn record_replaced(&mut self, shortcut: Shortcut) {
+        self.replaced.push(shortcut);
+    }
+
+    /// Shortcuts replaced by a conflicting entry of the same import
+    pub fn replaced(&self) -> &[Shortcut] {
+        &self.replaced
     }
 
     /// Remove a shortcut (exact match, case-sensitive)
     pub fn remove(&mut self, trigger: &str) -> Option<Shortcut> {
         let result = self.shortcuts.remove(trigger);
         if result.is_some() {
+            self.replaced.retain(|s| s.trigger != trigger);
             self.rebuild_sorted_triggers();
         }
         result
@@ -293,6 +332,9 @@ impl ShortcutTable {
 
     /// Try to match buffer with trigger key for specific input method
     ///
+    /// Replacement placeholders (`{date}`, `{counter}`...) are expanded here,
+    /// before case matching (see `template`).
+    ///
     /// # Arguments
     /// * `buffer` - Current buffer content (as string)
     /// * `key_char` - The key that was just pressed
@@ -312,27 +354,34 @@ impl ShortcutTable {
 
         match shortcut.condition {
             TriggerCondition::Immediate => {
-                let output = self.apply_case(buffer, &shortcut.replacement, shortcut.case_mode);
+                let (output, cursor_left) = self.render(buffer, &shortcut.replacement, shortcut);
                 Some(ShortcutMatch {
+                    trigger: trigger.to_string(),
                     // Use char count, not byte length (UTF-8 chars like đ are multi-byte)
                     backspace_count: trigger.chars().count(),
                     output,
                     include_trigger_key: false,
+                    cursor_left,
                 })
             }
             TriggerCondition::OnWordBoundary => {
                 if is_word_boundary {
-                    let mut output =
-                        self.apply_case(buffer, &shortcut.replacement, shortcut.case_mode);
+                    let (mut output, mut cursor_left) =
+                        self.render(buffer, &shortcut.replacement, shortcut);
                     // Append the trigger key (space, etc.)
                     if let Some(ch) = key_char {
                         output.push(ch);
+                        if cursor_left > 0 {
+                            cursor_left += 1;
+                        }
                     }
                     Some(ShortcutMatch {
+                        trigger: trigger.to_string(),
                         // Use char count, not byte length (UTF-8 chars like đ are multi-byte)
                         backspace_count: trigger.chars().count(),
                         output,
                         include_trigger_key: true,
+                        cursor_left,
                     })
                 } else {
                     None
@@ -341,6 +390,72 @@ impl ShortcutTable {
         }
     }
 
+    /// Try pattern triggers against the end of the typed text
+    ///
+    /// `text` is the word as shown on screen (digits and symbols included).
+    /// Immediate patterns match at any key; word-boundary patterns only when
+    /// `is_word_boundary` (the boundary key is then appended to the output).
+    pub fn try_match_pattern(
+        &self,
+        text: &str,
+        key_char: Option<char>,
+        is_word_boundary: bool,
+        method: InputMethod,
+    ) -> Option<ShortcutMatch> {
+        for (trigger, pattern) in &self.patterns {
+            let Some(shortcut) = self.shortcuts.get(trigger) else {
+                continue;
+            };
+            let immediate = shortcut.condition == TriggerCondition::Immediate;
+            if !shortcut.enabled || !shortcut.applies_to(method) || !(immediate || is_word_boundary)
+            {
+                continue;
+            }
+            let Some(m) = pattern.match_end(text) else {
+                continue;
+            };
+
+            let replacement = substitute_groups(&shortcut.replacement, &m.groups);
+            let (mut output, mut cursor_left) = self.render(&m.text, &replacement, shortcut);
+            if !immediate {
+                if let Some(ch) = key_char {
+                    output.push(ch);
+                    if cursor_left > 0 {
+                        cursor_left += 1;
+                    }
+                }
+            }
+            return Some(ShortcutMatch {
+                trigger: trigger.clone(),
+                backspace_count: m.text.chars().count(),
+                output,
+                include_trigger_key: !immediate,
+                cursor_left,
+            });
+        }
+        None
+    }
+
+    /// Expand placeholders, take out the cursor marker and apply case
+    ///
+    /// Returns the text and the number of chars after the marker
+    /// (0 without a marker). Only the first marker counts.
+    fn render(&self, buffer: &str, replacement: &str, shortcut: &Shortcut) -> (String, usize) {
+        let text = self.templates.expand(replacement, buffer);
+        let (text, cursor_left) = match text.split_once(CURSOR_MARKER) {
+            Some((before, after)) => {
+                let after = after.replace(CURSOR_MARKER, "");
+                let cursor_left = after.chars().count();
+                (format!("{}{}", before, after), cursor_left)
+            }
+            None => (text, 0),
+        };
+        (
+            self.apply_case(buffer, &text, shortcut.case_mode),
+            cursor_left,
+        )
+    }
+
     /// Apply case transformation based on mode
     fn apply_case(&self, trigger: &str, replacement: &str, mode: CaseMode) -> String {
         match mode {
@@ -369,11 +484,37 @@ impl ShortcutTable {
         }
     }
 
-    /// Rebuild sorted triggers list (longest first)
+    /// Rebuild sorted triggers list and compiled patterns (longest first)
     fn rebuild_sorted_triggers(&mut self) {
-        self.sorted_triggers = self.shortcuts.keys().cloned().collect();
+        self.sorted_triggers = self
+            .shortcuts
+            .values()
+            .filter(|s| s.kind == TriggerKind::Literal)
+            .map(|s| s.trigger.clone())
+            .collect();
         self.sorted_triggers
             .sort_by_key(|s| std::cmp::Reverse(s.len()));
+
+        self.patterns = self
+            .shortcuts
+            .values()
+            .filter(|s| s.kind == TriggerKind::Pattern)
+            .filter_map(|s| Some((s.trigger.clone(), Pattern::parse(&s.trigger).ok()?)))
+            .collect();
+        self.patterns
+            .sort_by(|a, b| b.0.len().cmp(&a.0.len()).then(a.0.cmp(&b.0)));
+    }
+
+    /// Iterate over all shortcuts (including disabled ones) in trigger order
+    pub fn iter(&self) -> impl Iterator<Item = &Shortcut> {
+        let mut shortcuts: Vec<&Shortcut> = self.shortcuts.values().collect();
+        shortcuts.sort_by(|a, b| a.trigger.cmp(&b.trigger));
+        shortcuts.into_iter()
+    }
+
+    /// Table has pattern triggers (valid ones)
+    pub fn has_patterns(&self) -> bool {
+        !self.patterns.is_empty()
     }
 
     /// Check if shortcut table is empty
@@ -386,13 +527,52 @@ impl ShortcutTable {
         self.shortcuts.len()
     }
 
-    /// Clear all shortcuts
+    /// Clear all shortcuts (and their counters)
     pub fn clear(&mut self) {
         self.shortcuts.clear();
         self.sorted_triggers.clear();
+        self.patterns.clear();
+        self.replaced.clear();
+        self.templates.reset_counters();
+    }
+
+    /// Clock, clipboard and counters used by dynamic replacements
+    pub fn templates(&self) -> &Templates {
+        &self.templates
+    }
+
+    pub fn templates_mut(&mut self) -> &mut Templates {
+        &mut self.templates
     }
 }
 
+/// Replace `{1}`-`{9}` with capture groups (references past the last group
+/// are kept for the template pass)
+fn substitute_groups(replacement: &str, groups: &[String]) -> String {
+    let mut out = String::new();
+    let mut rest = replacement;
+    while let Some(start) = rest.find('{') {
+        let group = rest[start..]
+            .get(1..3)
+            .and_then(|s| s.strip_suffix('}'))
+            .and_then(|n| n.parse::<usize>().ok())
+            .and_then(|n| groups.get(n.checked_sub(1)?));
+        match group {
+            Some(text) => {
+                out.push_str(&rest[..start]);
+                out.push_str(text);
+                rest = &rest[start + 3..];
+            }
+            None => {
+                out.push_str(&rest[..=start]);
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -592,6 +772,27 @@ mod tests {
         assert_no_match(&table, "7", None, false, InputMethod::Telex);
     }
 
+    #[test]
+    fn test_viqr_specific_shortcut() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new("vn", "Việt Nam").for_method(InputMethod::Viqr));
+
+        // Should match for VIQR
+        assert_shortcut_match(
+            &table,
+            "vn",
+            Some(' '),
+            true,
+            "Việt Nam ",
+            2,
+            InputMethod::Viqr,
+        );
+
+        // Should NOT match for Telex or VNI
+        assert_no_match(&table, "vn", Some(' '), true, InputMethod::Telex);
+        assert_no_match(&table, "vn", Some(' '), true, InputMethod::Vni);
+    }
+
     #[test]
     fn test_all_input_method_shortcut() {
         let table = table_with_shortcut("vn", "Việt Nam");
@@ -682,21 +883,11 @@ mod tests {
     }
 
     #[test]
-    fn test_replacement_validation_truncation() {
-        // Create a very long replacement (100 characters with Vietnamese)
+    fn test_replacement_not_truncated() {
+        // Long replacements are kept whole (sent in chunks by the engine)
         let long_text = "Đây là một đoạn văn bản rất dài để kiểm tra việc cắt ngắn. Nó có nhiều ký tự tiếng Việt có dấu như ồ, ế, ẫ, ơ, ư.";
-        let char_count = long_text.chars().count();
-        assert!(
-            char_count > MAX_REPLACEMENT_LEN,
-            "Test text should exceed limit"
-        );
-
         let shortcut = Shortcut::new("long", long_text);
-        let result_count = shortcut.replacement.chars().count();
-        assert_eq!(
-            result_count, MAX_REPLACEMENT_LEN,
-            "Should truncate to MAX_REPLACEMENT_LEN"
-        );
+        assert_eq!(shortcut.replacement, long_text);
     }
 
     #[test]
@@ -837,4 +1028,89 @@ mod tests {
             InputMethod::All,
         );
     }
+
+    #[test]
+    fn test_template_replacement() {
+        use crate::engine::template::DateTime;
+
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new("hn", "{weekday}, {date}"));
+        table.add(Shortcut::immediate("#n", "#{counter}"));
+        table
+            .templates_mut()
+            .set_clock(Box::new(DateTime::new(2026, 10, 12, 9, 5)));
+
+        let m = table.try_match("hn", Some(' '), true).unwrap();
+        assert_eq!(m.output, "thứ Hai, 12/10/2026 ");
+        // Case is matched after expansion
+        let m = table.try_match("Hn", Some(' '), true).unwrap();
+        assert_eq!(m.output, "Thứ Hai, 12/10/2026 ");
+
+        assert_eq!(table.try_match("#n", None, false).unwrap().output, "#1");
+        assert_eq!(table.try_match("#n", None, false).unwrap().output, "#2");
+        table.clear();
+        table.add(Shortcut::immediate("#n", "#{counter}"));
+        assert_eq!(table.try_match("#n", None, false).unwrap().output, "#1");
+    }
+
+    #[test]
+    fn test_cursor_marker() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new(
+            "kg",
+            "Kính gửi anh/chị {cursor},\nTrân trọng.",
+        ));
+        table.add(Shortcut::immediate("()", "({cursor})"));
+
+        let m = table.try_match("kg", Some(' '), true).unwrap();
+        assert_eq!(m.output, "Kính gửi anh/chị ,\nTrân trọng. ");
+        // ",\nTrân trọng." plus the trailing space
+        assert_eq!(m.cursor_left, 14);
+
+        let m = table.try_match("KG", Some(' '), true).unwrap();
+        assert_eq!(m.output, "KÍNH GỬI ANH/CHỊ ,\nTRÂN TRỌNG. ");
+        assert_eq!(m.cursor_left, 14);
+
+        let m = table.try_match("()", None, false).unwrap();
+        assert_eq!(m.output, "()");
+        assert_eq!(m.cursor_left, 1);
+
+        // No marker: cursor stays at the end
+        table.add(Shortcut::new("vn", "Việt Nam"));
+        assert_eq!(
+            table.try_match("vn", Some(' '), true).unwrap().cursor_left,
+            0
+        );
+    }
+
+    #[test]
+    fn test_pattern_trigger() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::pattern("(\\d+)(k|tr)", "{1} {2}"));
+        let mut emoji = Shortcut::pattern(":(\\w+):", "<{1}>");
+        emoji.condition = TriggerCondition::Immediate;
+        table.add(emoji);
+        let m = |text: &str, boundary: bool| {
+            table.try_match_pattern(text, boundary.then_some(' '), boundary, InputMethod::Telex)
+        };
+
+        let hit = m("giá 100k", true).unwrap();
+        assert_eq!(hit.output, "100 k ");
+        assert_eq!(hit.backspace_count, 4);
+        assert_eq!(hit.trigger, "(\\d+)(k|tr)");
+        // Word boundary patterns wait for the boundary
+        assert!(m("100k", false).is_none());
+        assert!(m("x100k", true).is_none());
+
+        let hit = m("xem :Cat:", false).unwrap();
+        assert_eq!(hit.output, "<Cat>");
+        assert_eq!(hit.backspace_count, 5);
+        assert!(!hit.include_trigger_key);
+
+        // Patterns are not literal triggers
+        assert!(table.lookup("(\\d+)(k|tr)").is_none());
+        assert!(table.has_patterns());
+        table.clear();
+        assert!(!table.has_patterns());
+    }
 }
@@ -0,0 +1,262 @@
+//! Shortcut Analysis - Conflicts and shadowing
+//!
+//! Large shortcut tables collect triggers that misbehave in ways the table
+//! itself cannot see:
+//! - a trigger that is a real Vietnamese syllable ("ban") expands whenever
+//!   the word is typed
+//! - a trigger the input method transforms ("dd" → "đ" in Telex) is never
+//!   in the buffer, so it never matches
+//! - triggers equal after lowercasing ("VN", "vn") in one import: only the
+//!   last entry is kept
+//! - an immediate trigger fires as soon as it is typed, so a longer trigger
+//!   starting with it can never be completed
+//! - a pattern trigger that does not compile never matches
+
+use super::pattern::Pattern;
+use super::shortcut::{InputMethod, Shortcut, ShortcutTable, TriggerCondition, TriggerKind};
+use super::validation;
+use super::Engine;
+use crate::data::keys;
+
+/// What is wrong with a trigger
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConflictKind {
+    /// Trigger is a valid Vietnamese syllable
+    VietnameseSyllable,
+    /// Typing the trigger with this input method transforms it
+    ModifierKeys(InputMethod),
+    /// An entry with the same trigger after lowercasing was replaced by
+    /// this one (the lost replacement)
+    Duplicate(String),
+    /// This immediate trigger is a prefix of the trigger and fires first
+    ShadowedBy(String),
+    /// Pattern trigger does not compile (error message)
+    InvalidPattern(String),
+}
+
+/// Problem found by `ShortcutTable::analyze`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub trigger: String,
+    pub kind: ConflictKind,
+}
+
+impl ShortcutTable {
+    /// Report conflicts between shortcuts, the input methods and Vietnamese
+    /// spelling, in trigger order (invalid patterns first). Disabled
+    /// shortcuts are skipped.
+    pub fn analyze(&self) -> Vec<Conflict> {
+        let (patterns, shortcuts): (Vec<&Shortcut>, Vec<&Shortcut>) = self
+            .iter()
+            .filter(|s| s.enabled)
+            .partition(|s| s.kind == TriggerKind::Pattern);
+        let mut conflicts = vec![];
+
+        for shortcut in patterns {
+            if let Err(err) = Pattern::parse(&shortcut.trigger) {
+                conflicts.push(Conflict {
+                    trigger: shortcut.trigger.clone(),
+                    kind: ConflictKind::InvalidPattern(err.to_string()),
+                });
+            }
+        }
+
+        for shortcut in &shortcuts {
+            let trigger = &shortcut.trigger;
+            let conflict = |kind| Conflict {
+                trigger: trigger.clone(),
+                kind,
+            };
+
+            if is_vietnamese_syllable(trigger) {
+                conflicts.push(conflict(ConflictKind::VietnameseSyllable));
+            }
+
+            for method in methods(shortcut.input_method) {
+                if typed_buffer(trigger, method)
+                    .is_some_and(|typed| typed != trigger.to_lowercase())
+                {
+                    conflicts.push(conflict(ConflictKind::ModifierKeys(method)));
+                }
+            }
+
+            for lost in self.replaced().iter().filter(|s| s.trigger == *trigger) {
+                conflicts.push(conflict(ConflictKind::Duplicate(lost.replacement.clone())));
+            }
+
+            for other in &shortcuts {
+                let shadows = other.condition == TriggerCondition::Immediate
+                    && other.trigger.len() < trigger.len()
+                    && trigger.starts_with(other.trigger.as_str())
+                    && methods_overlap(shortcut.input_method, other.input_method);
+                if shadows {
+                    conflicts.push(conflict(ConflictKind::ShadowedBy(other.trigger.clone())));
+                }
+            }
+        }
+
+        conflicts
+    }
+}
+
+/// Letters-only trigger that passes the Vietnamese spelling rules
+pub(super) fn is_vietnamese_syllable(trigger: &str) -> bool {
+    let keys: Option<Vec<u16>> = trigger
+        .chars()
+        .map(|c| {
+            keys::from_char(c)
+                .map(|(key, _)| key)
+                .filter(|&key| keys::is_letter(key))
+        })
+        .collect();
+    keys.is_some_and(|keys| !keys.is_empty() && validation::is_valid(&keys))
+}
+
+/// Input methods a shortcut is typed with (`All`: Telex, VNI and VIQR)
+fn methods(method: InputMethod) -> Vec<InputMethod> {
+    match method {
+        InputMethod::All => vec![InputMethod::Telex, InputMethod::Vni, InputMethod::Viqr],
+        method => vec![method],
+    }
+}
+
+fn methods_overlap(a: InputMethod, b: InputMethod) -> bool {
+    a == InputMethod::All || b == InputMethod::All || a == b
+}
+
+/// Buffer left by typing `trigger` (lowercase) with `method`
+///
+/// None for triggers with keys other than letters and digits: symbol
+/// triggers are matched from the shortcut prefix, not the buffer.
+fn typed_buffer(trigger: &str, method: InputMethod) -> Option<String> {
+    let id = match method {
+        InputMethod::Telex => 0,
+        InputMethod::Vni => 1,
+        InputMethod::Viqr => 2,
+        InputMethod::All => return None,
+    };
+    let keys: Option<Vec<u16>> = trigger
+        .chars()
+        .map(|c| {
+            keys::from_char(c)
+                .filter(|_| c.is_ascii_alphanumeric())
+                .map(|(key, _)| key)
+        })
+        .collect();
+
+    let mut engine = Engine::new();
+    engine.set_method(id);
+    for key in keys? {
+        engine.on_key(key, false, false);
+    }
+    Some(engine.get_buffer_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(table: &ShortcutTable, trigger: &str) -> Vec<ConflictKind> {
+        table
+            .analyze()
+            .into_iter()
+            .filter(|c| c.trigger == trigger)
+            .map(|c| c.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_vietnamese_syllable() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new("ban", "bạn bè"));
+        table.add(Shortcut::new("vn", "Việt Nam"));
+        assert_eq!(kinds(&table, "ban"), [ConflictKind::VietnameseSyllable]);
+        assert!(kinds(&table, "vn").is_empty());
+    }
+
+    #[test]
+    fn test_modifier_keys() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new("dd", "đặc điểm"));
+        table.add(Shortcut::new("ko", "không"));
+        table.add(Shortcut::new("vn7", "x").for_method(InputMethod::Vni));
+        table.add(Shortcut::new("hn", "Hà Nội"));
+
+        // "dd" → "đ" in Telex and VIQR; VNI uses "d9"
+        assert_eq!(
+            kinds(&table, "dd"),
+            [
+                ConflictKind::ModifierKeys(InputMethod::Telex),
+                ConflictKind::ModifierKeys(InputMethod::Viqr)
+            ]
+        );
+        assert!(kinds(&table, "ko").is_empty());
+        assert!(kinds(&table, "hn").is_empty());
+        assert!(kinds(&table, "vn7").is_empty());
+
+        table.add(Shortcut::new("ao7", "áo").for_method(InputMethod::Vni));
+        assert!(kinds(&table, "ao7").contains(&ConflictKind::ModifierKeys(InputMethod::Vni)));
+    }
+
+    #[test]
+    fn test_duplicate() {
+        use crate::engine::shortcut_io::ShortcutFormat;
+
+        let mut table = ShortcutTable::new();
+        let (count, errors) = table.import("VN:VIỆT NAM\nvn:Việt Nam", ShortcutFormat::UniKey);
+        assert_eq!((count, errors), (2, vec![]));
+        assert_eq!(table.len(), 1);
+        assert_eq!(
+            kinds(&table, "vn"),
+            [ConflictKind::Duplicate("VIỆT NAM".to_string())]
+        );
+
+        // Replacing a trigger from an earlier import or by hand is no conflict
+        table.import("hn:Hà Nội", ShortcutFormat::UniKey);
+        table.import("HN:Hà Nội", ShortcutFormat::UniKey);
+        assert!(kinds(&table, "hn").is_empty());
+        assert_eq!(
+            table
+                .add(Shortcut::new("vn", "Vietnam"))
+                .map(|s| s.replacement),
+            Some("Việt Nam".to_string())
+        );
+        assert!(kinds(&table, "vn").is_empty());
+    }
+
+    #[test]
+    fn test_patterns() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::pattern("(\\d+)k", "{1} nghìn"));
+        table.add(Shortcut::pattern("ban|(", "x"));
+        assert!(kinds(&table, "(\\d+)k").is_empty());
+        assert_eq!(
+            kinds(&table, "ban|("),
+            [ConflictKind::InvalidPattern("missing ')' at 5".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_shadowed_by_immediate() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::immediate("->", "→"));
+        table.add(Shortcut::immediate("->>", "↠"));
+        table.add(Shortcut::new("-", "–"));
+        assert_eq!(
+            kinds(&table, "->>"),
+            [ConflictKind::ShadowedBy("->".to_string())]
+        );
+        assert!(kinds(&table, "->").is_empty());
+
+        // Different input methods never meet
+        table.add(Shortcut::telex("#", "x"));
+        table.add(Shortcut::vni("#1", "y"));
+        assert!(kinds(&table, "#1").is_empty());
+
+        // Disabled shortcuts are ignored
+        let mut off = Shortcut::immediate("->", "→");
+        off.enabled = false;
+        table.add(off);
+        assert!(kinds(&table, "->>").is_empty());
+    }
+}
@@ -0,0 +1,884 @@
+//! Shortcut Import/Export
+//!
+//! Reads and writes shortcut tables as text so macro packs can be shared:
+//! - UniKey macro file: `trigger:replacement` per line, `;` starts a comment
+//! - JSON: array of objects carrying every `Shortcut` field
+//! - CSV: `trigger,replacement,condition,case_mode,enabled,input_method,kind`
+//!
+//! UniKey files have no syntax for the extra fields, so non-default values go
+//! into a `;@` comment line before the entry (UniKey skips it as a comment):
+//!
+//! ```text
+//! ;DO NOT DELETE THIS LINE*** version=1 ***
+//! vn:Việt Nam
+//! ;@ condition=immediate case_mode=exact
+//! ->:→
+//! ```
+//!
+//! Import keeps every valid entry and reports the rejected ones by line.
+//! Export likewise skips entries the format cannot hold (a UniKey trigger with
+//! `:`, a multi-line UniKey replacement) and reports them by trigger.
+
+use super::pattern::Pattern;
+use super::shortcut::{
+    CaseMode, InputMethod, Shortcut, ShortcutTable, TriggerCondition, TriggerKind,
+};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Header line UniKey writes at the top of macro files
+const UNIKEY_HEADER: &str = ";DO NOT DELETE THIS LINE*** version=1 ***";
+
+/// Comment prefix carrying fields UniKey has no syntax for
+const UNIKEY_FIELDS: &str = ";@";
+
+/// Optional fields, in CSV column order after trigger and replacement
+const FIELDS: [&str; 5] = ["condition", "case_mode", "enabled", "input_method", "kind"];
+
+/// Text format for shortcut import/export
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShortcutFormat {
+    /// UniKey macro file
+    UniKey = 0,
+    Json = 1,
+    Csv = 2,
+}
+
+impl ShortcutFormat {
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(ShortcutFormat::UniKey),
+            1 => Some(ShortcutFormat::Json),
+            2 => Some(ShortcutFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// Rejected entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportError {
+    /// 1-based line number (where the entry starts)
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Entry left out of an export
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportError {
+    pub trigger: String,
+    pub message: String,
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}': {}", self.trigger, self.message)
+    }
+}
+
+/// Parse shortcuts from text, returns the valid entries and the rejected ones
+pub fn parse(text: &str, format: ShortcutFormat) -> (Vec<Shortcut>, Vec<ImportError>) {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    match format {
+        ShortcutFormat::UniKey => parse_unikey(text),
+        ShortcutFormat::Json => parse_json(text),
+        ShortcutFormat::Csv => parse_csv(text),
+    }
+}
+
+/// Serialize shortcuts to text, returns the text and the entries left out
+pub fn write<'a>(
+    shortcuts: impl IntoIterator<Item = &'a Shortcut>,
+    format: ShortcutFormat,
+) -> (String, Vec<ExportError>) {
+    match format {
+        ShortcutFormat::UniKey => write_unikey(shortcuts),
+        ShortcutFormat::Json => (write_json(shortcuts), vec![]),
+        ShortcutFormat::Csv => (write_csv(shortcuts), vec![]),
+    }
+}
+
+impl ShortcutTable {
+    /// Add shortcuts from text (existing triggers are replaced),
+    /// returns the number added and the rejected entries
+    ///
+    /// Entries whose triggers are equal after lowercasing ("VN", "vn") leave
+    /// the last one; the others are kept in `replaced` and reported by
+    /// `analyze`.
+    pub fn import(&mut self, text: &str, format: ShortcutFormat) -> (usize, Vec<ImportError>) {
+        let (shortcuts, errors) = parse(text, format);
+        let count = shortcuts.len();
+        let mut seen = HashSet::new();
+        for shortcut in shortcuts {
+            let trigger = shortcut.trigger.clone();
+            if let Some(previous) = self.add(shortcut) {
+                if seen.contains(&trigger) {
+                    self.record_replaced(previous);
+                }
+            }
+            seen.insert(trigger);
+        }
+        (count, errors)
+    }
+
+    /// Serialize all shortcuts (including disabled ones) in trigger order,
+    /// returns the text and the entries the format cannot hold
+    pub fn export(&self, format: ShortcutFormat) -> (String, Vec<ExportError>) {
+        write(self.iter(), format)
+    }
+}
+
+// ============================================================
+// Fields
+// ============================================================
+
+/// Build a shortcut with `Shortcut::new` defaults, then apply `fields`
+fn build(trigger: &str, replacement: &str, fields: &[(&str, &str)]) -> Result<Shortcut, String> {
+    let trigger = trigger.trim();
+    if trigger.is_empty() {
+        return Err("empty trigger".to_string());
+    }
+    if trigger.chars().any(char::is_whitespace) {
+        return Err(format!("trigger '{}' contains whitespace", trigger));
+    }
+    if replacement.is_empty() {
+        return Err(format!("empty replacement for '{}'", trigger));
+    }
+    let mut shortcut = Shortcut::new(trigger, replacement);
+    for &(name, value) in fields {
+        set_field(&mut shortcut, name, value.trim())?;
+    }
+    if shortcut.kind == TriggerKind::Pattern {
+        if let Err(err) = Pattern::parse(&shortcut.trigger) {
+            return Err(format!("invalid pattern '{}': {}", trigger, err));
+        }
+    }
+    Ok(shortcut)
+}
+
+fn set_field(shortcut: &mut Shortcut, name: &str, value: &str) -> Result<(), String> {
+    let invalid = || format!("invalid {} '{}'", name, value);
+    match name {
+        "condition" => {
+            shortcut.condition = match value {
+                "immediate" => TriggerCondition::Immediate,
+                "word_boundary" => TriggerCondition::OnWordBoundary,
+                _ => return Err(invalid()),
+            }
+        }
+        "case_mode" => {
+            shortcut.case_mode = match value {
+                "exact" => CaseMode::Exact,
+                "match_case" => CaseMode::MatchCase,
+                _ => return Err(invalid()),
+            }
+        }
+        "enabled" => {
+            shortcut.enabled = match value {
+                "true" => true,
+                "false" => false,
+                _ => return Err(invalid()),
+            }
+        }
+        "input_method" => {
+            shortcut.input_method = match value {
+                "all" => InputMethod::All,
+                "telex" => InputMethod::Telex,
+                "vni" => InputMethod::Vni,
+                "viqr" => InputMethod::Viqr,
+                _ => return Err(invalid()),
+            }
+        }
+        "kind" => {
+            shortcut.kind = match value {
+                "literal" => TriggerKind::Literal,
+                "pattern" => TriggerKind::Pattern,
+                _ => return Err(invalid()),
+            }
+        }
+        _ => return Err(format!("unknown field '{}'", name)),
+    }
+    Ok(())
+}
+
+/// Optional field values, in `FIELDS` order
+fn field_values(shortcut: &Shortcut) -> [&'static str; 5] {
+    [
+        match shortcut.condition {
+            TriggerCondition::Immediate => "immediate",
+            TriggerCondition::OnWordBoundary => "word_boundary",
+        },
+        match shortcut.case_mode {
+            CaseMode::Exact => "exact",
+            CaseMode::MatchCase => "match_case",
+        },
+        if shortcut.enabled { "true" } else { "false" },
+        match shortcut.input_method {
+            InputMethod::All => "all",
+            InputMethod::Telex => "telex",
+            InputMethod::Vni => "vni",
+            InputMethod::Viqr => "viqr",
+        },
+        match shortcut.kind {
+            TriggerKind::Literal => "literal",
+            TriggerKind::Pattern => "pattern",
+        },
+    ]
+}
+
+// ============================================================
+// UniKey
+// ============================================================
+
+fn parse_unikey(text: &str) -> (Vec<Shortcut>, Vec<ImportError>) {
+    let mut shortcuts = vec![];
+    let mut errors = vec![];
+    // Fields from a `;@` line, applied to the next entry
+    let mut pending: Vec<(&str, &str)> = vec![];
+
+    for (i, line) in text.lines().enumerate() {
+        let err = |message: String| ImportError {
+            line: i + 1,
+            message,
+        };
+
+        if let Some(rest) = line.strip_prefix(UNIKEY_FIELDS) {
+            pending.clear();
+            for pair in rest.split_whitespace() {
+                match pair.split_once('=') {
+                    Some(field) => pending.push(field),
+                    None => errors.push(err(format!("expected name=value, got '{}'", pair))),
+                }
+            }
+            continue;
+        }
+        if line.trim().is_empty() || line.starts_with(';') {
+            continue;
+        }
+
+        let result = match line.split_once(':') {
+            Some((trigger, replacement)) => build(trigger, replacement, &pending),
+            None => Err("expected trigger:replacement".to_string()),
+        };
+        match result {
+            Ok(shortcut) => shortcuts.push(shortcut),
+            Err(message) => errors.push(err(message)),
+        }
+        pending.clear();
+    }
+
+    (shortcuts, errors)
+}
+
+fn write_unikey<'a>(
+    shortcuts: impl IntoIterator<Item = &'a Shortcut>,
+) -> (String, Vec<ExportError>) {
+    let defaults = field_values(&Shortcut::new("", ""));
+    let mut out = format!("{}\n", UNIKEY_HEADER);
+    let mut errors = vec![];
+    for shortcut in shortcuts {
+        // Entries are single `trigger:replacement` lines, split at the first ':'
+        let problem = if shortcut.trigger.contains(':') {
+            Some("trigger contains ':'")
+        } else if shortcut.trigger.starts_with(';') {
+            Some("trigger starts with ';'")
+        } else if shortcut.replacement.contains(['\r', '\n']) {
+            Some("replacement spans several lines")
+        } else {
+            None
+        };
+        if let Some(message) = problem {
+            errors.push(ExportError {
+                trigger: shortcut.trigger.clone(),
+                message: message.to_string(),
+            });
+            continue;
+        }
+
+        let values = field_values(shortcut);
+        let extra: Vec<String> = FIELDS
+            .iter()
+            .zip(values.iter().zip(defaults.iter()))
+            .filter(|(_, (value, default))| value != default)
+            .map(|(name, (value, _))| format!("{}={}", name, value))
+            .collect();
+        if !extra.is_empty() {
+            out.push_str(&format!("{} {}\n", UNIKEY_FIELDS, extra.join(" ")));
+        }
+        out.push_str(&format!("{}:{}\n", shortcut.trigger, shortcut.replacement));
+    }
+    (out, errors)
+}
+
+// ============================================================
+// CSV
+// ============================================================
+
+fn parse_csv(text: &str) -> (Vec<Shortcut>, Vec<ImportError>) {
+    let (records, mut errors) = csv_records(text);
+    let mut shortcuts = vec![];
+
+    for (index, (line, record)) in records.iter().enumerate() {
+        // Blank line
+        if record.len() == 1 && record[0].trim().is_empty() {
+            continue;
+        }
+        // Header row
+        if index == 0 && record[0].trim().eq_ignore_ascii_case("trigger") {
+            continue;
+        }
+
+        let result = if record.len() < 2 {
+            Err("expected trigger,replacement".to_string())
+        } else if record.len() > 2 + FIELDS.len() {
+            Err(format!("too many fields ({})", record.len()))
+        } else {
+            // Empty columns keep their defaults
+            let fields: Vec<(&str, &str)> = FIELDS
+                .iter()
+                .zip(&record[2..])
+                .filter(|(_, value)| !value.trim().is_empty())
+                .map(|(name, value)| (*name, value.as_str()))
+                .collect();
+            build(&record[0], &record[1], &fields)
+        };
+        match result {
+            Ok(shortcut) => shortcuts.push(shortcut),
+            Err(message) => errors.push(ImportError {
+                line: *line,
+                message,
+            }),
+        }
+    }
+
+    (shortcuts, errors)
+}
+
+/// Split CSV text into records (RFC 4180 quoting), each with its start line
+fn csv_records(text: &str) -> (Vec<(usize, Vec<String>)>, Vec<ImportError>) {
+    let mut records = vec![];
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut start = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => quoted = true,
+            ',' => fields.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                records.push((start, std::mem::take(&mut fields)));
+                line += 1;
+                start = line;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if quoted {
+        let error = ImportError {
+            line: start,
+            message: "unterminated quoted field".to_string(),
+        };
+        return (records, vec![error]);
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        records.push((start, fields));
+    }
+    (records, vec![])
+}
+
+fn csv_field(value: &str) -> String {
+    let needs_quotes = value.contains([',', '"', '\n', '\r'])
+        || value.starts_with(char::is_whitespace)
+        || value.ends_with(char::is_whitespace);
+    if needs_quotes {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn write_csv<'a>(shortcuts: impl IntoIterator<Item = &'a Shortcut>) -> String {
+    let mut out = format!("trigger,replacement,{}\n", FIELDS.join(","));
+    for shortcut in shortcuts {
+        let mut row = vec![
+            csv_field(&shortcut.trigger),
+            csv_field(&shortcut.replacement),
+        ];
+        row.extend(field_values(shortcut).iter().map(|v| v.to_string()));
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+// ============================================================
+// JSON
+// ============================================================
+
+/// Parsed JSON value (only what shortcut files need)
+enum Json {
+    Null,
+    Bool(bool),
+    Number,
+    Str(String),
+    /// Items with their start line
+    Array(Vec<(usize, Json)>),
+    Object(Vec<(String, Json)>),
+}
+
+fn parse_json(text: &str) -> (Vec<Shortcut>, Vec<ImportError>) {
+    let mut parser = JsonParser {
+        chars: text.chars().peekable(),
+        line: 1,
+    };
+    let items = match parser.document() {
+        Ok(Json::Array(items)) => items,
+        Ok(_) => return (vec![], vec![parser.error("expected an array of shortcuts")]),
+        Err(error) => return (vec![], vec![error]),
+    };
+
+    let mut shortcuts = vec![];
+    let mut errors = vec![];
+    for (line, item) in items {
+        match json_entry(item) {
+            Ok(shortcut) => shortcuts.push(shortcut),
+            Err(message) => errors.push(ImportError { line, message }),
+        }
+    }
+    (shortcuts, errors)
+}
+
+fn json_entry(item: Json) -> Result<Shortcut, String> {
+    let Json::Object(members) = item else {
+        return Err("expected an object".to_string());
+    };
+
+    let mut trigger = None;
+    let mut replacement = None;
+    let mut values = vec![];
+    for (name, value) in members {
+        let value = match value {
+            Json::Str(s) => s,
+            // `enabled` is a JSON boolean
+            Json::Bool(b) if name == "enabled" => b.to_string(),
+            _ => return Err(format!("invalid {} value", name)),
+        };
+        match name.as_str() {
+            "trigger" => trigger = Some(value),
+            "replacement" => replacement = Some(value),
+            _ => values.push((name, value)),
+        }
+    }
+
+    let trigger = trigger.ok_or("missing trigger")?;
+    let replacement = replacement.ok_or("missing replacement")?;
+    let fields: Vec<(&str, &str)> = values
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+    build(&trigger, &replacement, &fields)
+}
+
+struct JsonParser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+}
+
+impl JsonParser<'_> {
+    fn error(&self, message: &str) -> ImportError {
+        ImportError {
+            line: self.line,
+            message: message.to_string(),
+        }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ImportError> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => Err(self.error(&format!("expected '{}'", expected))),
+        }
+    }
+
+    /// Single value followed by nothing but whitespace
+    fn document(&mut self) -> Result<Json, ImportError> {
+        let value = self.value()?;
+        self.skip_whitespace();
+        match self.chars.peek() {
+            None => Ok(value),
+            Some(_) => Err(self.error("unexpected text after the end")),
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, ImportError> {
+        self.skip_whitespace();
+        match self.chars.peek().copied() {
+            Some('"') => self.string().map(Json::Str),
+            Some('[') => self.array(),
+            Some('{') => self.object(),
+            Some(c) if c.is_ascii_alphabetic() => {
+                let mut word = String::new();
+                while let Some(c) = self.chars.next_if(char::is_ascii_alphabetic) {
+                    word.push(c);
+                }
+                match word.as_str() {
+                    "true" => Ok(Json::Bool(true)),
+                    "false" => Ok(Json::Bool(false)),
+                    "null" => Ok(Json::Null),
+                    _ => Err(self.error(&format!("unexpected '{}'", word))),
+                }
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                while self
+                    .chars
+                    .next_if(|c| c.is_ascii_digit() || "+-.eE".contains(*c))
+                    .is_some()
+                {}
+                Ok(Json::Number)
+            }
+            Some(c) => Err(self.error(&format!("unexpected '{}'", c))),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, ImportError> {
+        self.expect('[')?;
+        let mut items = vec![];
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&']').is_some() {
+            return Ok(Json::Array(items));
+        }
+        loop {
+            self.skip_whitespace();
+            let line = self.line;
+            items.push((line, self.value()?));
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(Json::Array(items)),
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, ImportError> {
+        self.expect('{')?;
+        let mut members = vec![];
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&'}').is_some() {
+            return Ok(Json::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.chars.peek() != Some(&'"') {
+                return Err(self.error("expected a field name"));
+            }
+            let name = self.string()?;
+            self.expect(':')?;
+            members.push((name, self.value()?));
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(Json::Object(members)),
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ImportError> {
+        self.expect('"')?;
+        let mut s = String::new();
+        loop {
+            match self.next() {
+                None | Some('\n') => return Err(self.error("unterminated string")),
+                Some('"') => return Ok(s),
+                Some('\\') => {
+                    let c = match self.next() {
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some(c @ ('"' | '\\' | '/')) => c,
+                        Some('u') => self.unicode_escape()?,
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    s.push(c);
+                }
+                Some(c) => s.push(c),
+            }
+        }
+    }
+
+    /// `\uXXXX` after the `\u`, including surrogate pairs
+    fn unicode_escape(&mut self) -> Result<char, ImportError> {
+        let high = self.hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if self.next() != Some('\\') || self.next() != Some('u') {
+                return Err(self.error("unpaired surrogate"));
+            }
+            let low = self.hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid \\u escape"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, ImportError> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self.next().and_then(|c| c.to_digit(16));
+            code = code * 16 + digit.ok_or_else(|| self.error("invalid \\u escape"))?;
+        }
+        Ok(code)
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn write_json<'a>(shortcuts: impl IntoIterator<Item = &'a Shortcut>) -> String {
+    let entries: Vec<String> = shortcuts
+        .into_iter()
+        .map(|shortcut| {
+            let values = field_values(shortcut);
+            let mut members = vec![
+                format!("\"trigger\": {}", json_string(&shortcut.trigger)),
+                format!("\"replacement\": {}", json_string(&shortcut.replacement)),
+            ];
+            for (name, value) in FIELDS.iter().zip(values) {
+                let value = if *name == "enabled" {
+                    value.to_string()
+                } else {
+                    json_string(value)
+                };
+                members.push(format!("\"{}\": {}", name, value));
+            }
+            format!("  {{{}}}", members.join(", "))
+        })
+        .collect();
+
+    if entries.is_empty() {
+        "[]\n".to_string()
+    } else {
+        format!("[\n{}\n]\n", entries.join(",\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ShortcutFormat::*;
+
+    /// Table using every non-default field value
+    fn sample_table() -> ShortcutTable {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new("vn", "Việt Nam"));
+        table.add(Shortcut::immediate("->", "→"));
+        let mut quote = Shortcut::new("q", "say \"hi\", then\tgo");
+        quote.case_mode = CaseMode::Exact;
+        quote.enabled = false;
+        table.add(quote);
+        table.add(Shortcut::telex("ww", "ưư"));
+        table.add(Shortcut::vni("a8", "ă").for_method(InputMethod::Viqr));
+        table.add(Shortcut::pattern("(\\d+)k", "{1} nghìn"));
+        table
+    }
+
+    fn assert_same(a: &ShortcutTable, b: &ShortcutTable) {
+        let a: Vec<_> = a.iter().collect();
+        let b: Vec<_> = b.iter().collect();
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(&b) {
+            assert_eq!(x.trigger, y.trigger);
+            assert_eq!(x.replacement, y.replacement);
+            assert_eq!(x.condition, y.condition);
+            assert_eq!(x.case_mode, y.case_mode);
+            assert_eq!(x.enabled, y.enabled);
+            assert_eq!(x.input_method, y.input_method);
+            assert_eq!(x.kind, y.kind);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let table = sample_table();
+        for format in [UniKey, Json, Csv] {
+            let (text, skipped) = table.export(format);
+            assert!(skipped.is_empty(), "{:?}: {:?}", format, skipped);
+            let mut imported = ShortcutTable::new();
+            let (count, errors) = imported.import(&text, format);
+            assert!(errors.is_empty(), "{:?}: {:?}", format, errors);
+            assert_eq!(count, 6, "{:?}", format);
+            assert_same(&table, &imported);
+        }
+
+        // UniKey has no room for these, the other formats keep them
+        let mut full = sample_table();
+        full.add(Shortcut::new("a:b", "colon"));
+        full.add(Shortcut::new("sig", "Thanks,\nAn"));
+        for format in [Json, Csv] {
+            let (text, skipped) = full.export(format);
+            assert!(skipped.is_empty(), "{:?}: {:?}", format, skipped);
+            let mut imported = ShortcutTable::new();
+            imported.import(&text, format);
+            assert_same(&full, &imported);
+        }
+        let (text, skipped) = full.export(UniKey);
+        let report: Vec<String> = skipped.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            report,
+            [
+                "'a:b': trigger contains ':'",
+                "'sig': replacement spans several lines"
+            ]
+        );
+        let mut imported = ShortcutTable::new();
+        assert_eq!(imported.import(&text, UniKey), (6, vec![]));
+        assert_same(&table, &imported);
+    }
+
+    #[test]
+    fn test_unikey_file() {
+        let text = "\u{feff};DO NOT DELETE THIS LINE*** version=1 ***\r\n\
+                    vn:Việt Nam\r\n\
+                    ; comment\r\n\
+                    url:http://gonhanh.org\r\n";
+        let (shortcuts, errors) = parse(text, UniKey);
+        assert!(errors.is_empty());
+        assert_eq!(shortcuts.len(), 2);
+        assert_eq!(shortcuts[0].replacement, "Việt Nam");
+        assert_eq!(shortcuts[0].condition, TriggerCondition::OnWordBoundary);
+        // Only the first ':' separates
+        assert_eq!(shortcuts[1].replacement, "http://gonhanh.org");
+    }
+
+    #[test]
+    fn test_unikey_errors() {
+        let text = "vn:Việt Nam\nno separator\n:empty\n;@ enabled=maybe\nko:không\nhn:Hà Nội";
+        let (shortcuts, errors) = parse(text, UniKey);
+        assert_eq!(shortcuts.len(), 2);
+        let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, [2, 3, 5]);
+        assert_eq!(errors[2].to_string(), "line 5: invalid enabled 'maybe'");
+
+        let (shortcuts, errors) = parse(";@ kind=pattern\n(\\d+k:x\n", UniKey);
+        assert!(shortcuts.is_empty());
+        assert_eq!(
+            errors[0].to_string(),
+            "line 2: invalid pattern '(\\d+k': missing ')' at 5"
+        );
+    }
+
+    #[test]
+    fn test_csv_quoting() {
+        let text = "trigger,replacement\n\"sig\",\"Thanks,\nAn\"\nbad\nok,\"\"\"ok\"\"\",,,,vni\n";
+        let (shortcuts, errors) = parse(text, Csv);
+        assert_eq!(shortcuts.len(), 2);
+        assert_eq!(shortcuts[0].replacement, "Thanks,\nAn");
+        assert_eq!(shortcuts[1].replacement, "\"ok\"");
+        assert_eq!(shortcuts[1].input_method, InputMethod::Vni);
+        // Record after a multi-line field reports its own line
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 4);
+
+        let (_, errors) = parse("a,\"open", Csv);
+        assert_eq!(errors[0].message, "unterminated quoted field");
+    }
+
+    #[test]
+    fn test_json_entries() {
+        let text = r#"[
+            {"trigger": "smile", "replacement": "😀", "enabled": false},
+            {"trigger": "x"},
+            42,
+            {"trigger": "tm", "replacement": "™", "condition": "immediate"}
+        ]"#;
+        let (shortcuts, errors) = parse(text, Json);
+        assert_eq!(shortcuts.len(), 2);
+        assert_eq!(shortcuts[0].replacement, "😀");
+        assert!(!shortcuts[0].enabled);
+        assert_eq!(shortcuts[1].replacement, "™");
+        assert_eq!(shortcuts[1].condition, TriggerCondition::Immediate);
+        let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, [3, 4]);
+    }
+
+    #[test]
+    fn test_json_syntax_error() {
+        let (shortcuts, errors) = parse(
+            "[\n  {\"trigger\": \"vn\",\n  \"replacement\" \"x\"}\n]",
+            Json,
+        );
+        assert!(shortcuts.is_empty());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 3);
+        assert_eq!(
+            parse("{}", Json).1[0].message,
+            "expected an array of shortcuts"
+        );
+        assert_eq!(write(std::iter::empty(), Json).0, "[]\n");
+    }
+}
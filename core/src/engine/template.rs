@@ -0,0 +1,309 @@
+//! Shortcut Templates - Dynamic replacement values
+//!
+//! Placeholders in a shortcut replacement are evaluated at expansion time:
+//!
+//! | Placeholder   | Output                                   |
+//! |---------------|------------------------------------------|
+//! | `{date}`      | `12/10/2026`                             |
+//! | `{date_long}` | `thứ Hai, ngày 12 tháng 10 năm 2026`     |
+//! | `{weekday}`   | `thứ Hai`                                |
+//! | `{time}`      | `14:05`                                  |
+//! | `{clipboard}` | Clipboard text supplied by the platform  |
+//! | `{counter}`   | 1, 2, 3... (per shortcut)                |
+//! | `{trigger}`   | Trigger as typed ("Vn", "VN")            |
+//! | `{cursor}`    | Cursor position after expansion          |
+//!
+//! `{cursor}` is kept by `expand` and handled by the shortcut table.
+//! Unknown placeholders are kept as-is. Clock and clipboard are trait objects
+//! so platforms (and tests) can inject their own.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Marks where the cursor lands after a shortcut expands
+pub const CURSOR_MARKER: &str = "{cursor}";
+
+/// Vietnam time (UTC+7), the default offset for the system clock
+pub const DEFAULT_UTC_OFFSET_MINUTES: i32 = 7 * 60;
+
+/// Weekday names, Sunday first
+const WEEKDAYS: [&str; 7] = [
+    "Chủ nhật",
+    "thứ Hai",
+    "thứ Ba",
+    "thứ Tư",
+    "thứ Năm",
+    "thứ Sáu",
+    "thứ Bảy",
+];
+
+/// Local calendar date and time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateTime {
+    pub year: i32,
+    /// 1-12
+    pub month: u8,
+    /// 1-31
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl DateTime {
+    pub fn new(year: i32, month: u8, day: u8, hour: u8, minute: u8) -> Self {
+        Self {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+        }
+    }
+
+    /// Convert seconds since the Unix epoch (already shifted to local time)
+    pub fn from_unix(secs: i64) -> Self {
+        let days = secs.div_euclid(86400);
+        let rem = secs.rem_euclid(86400);
+        // Civil-from-days (Howard Hinnant)
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+        Self::new(
+            year,
+            month,
+            day,
+            (rem / 3600) as u8,
+            (rem % 3600 / 60) as u8,
+        )
+    }
+
+    /// Day of week, 0 = Sunday
+    pub fn weekday(&self) -> usize {
+        // Sakamoto's method
+        const T: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+        let y = if self.month < 3 {
+            self.year - 1
+        } else {
+            self.year
+        };
+        let d = y + y / 4 - y / 100 + y / 400 + T[self.month as usize - 1] + self.day as i32;
+        d.rem_euclid(7) as usize
+    }
+}
+
+/// Source of the current time
+pub trait Clock: Send {
+    fn now(&self) -> DateTime;
+}
+
+/// Fixed time (tests, or platforms that push the time themselves)
+impl Clock for DateTime {
+    fn now(&self) -> DateTime {
+        *self
+    }
+}
+
+/// System time at a fixed UTC offset
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    pub utc_offset_minutes: i32,
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self {
+            utc_offset_minutes: DEFAULT_UTC_OFFSET_MINUTES,
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        DateTime::from_unix(secs + i64::from(self.utc_offset_minutes) * 60)
+    }
+}
+
+/// Source of the clipboard text
+pub trait Clipboard: Send {
+    fn text(&self) -> String;
+}
+
+/// Fixed text (pushed by the platform via FFI)
+impl Clipboard for String {
+    fn text(&self) -> String {
+        self.clone()
+    }
+}
+
+/// Template evaluation state: clock, clipboard and per-shortcut counters
+pub struct Templates {
+    clock: Box<dyn Clock>,
+    clipboard: Box<dyn Clipboard>,
+    /// Next counter value per trigger (interior mutability: matching takes &self)
+    counters: RefCell<HashMap<String, u32>>,
+}
+
+impl Default for Templates {
+    fn default() -> Self {
+        Self {
+            clock: Box::new(SystemClock::default()),
+            clipboard: Box::new(String::new()),
+            counters: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl fmt::Debug for Templates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Templates")
+            .field("counters", &self.counters)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Templates {
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    pub fn set_clipboard(&mut self, clipboard: Box<dyn Clipboard>) {
+        self.clipboard = clipboard;
+    }
+
+    /// Restart all counters at 1
+    pub fn reset_counters(&self) {
+        self.counters.borrow_mut().clear();
+    }
+
+    /// Expand placeholders in `replacement` for `trigger` (as typed)
+    pub fn expand(&self, replacement: &str, trigger: &str) -> String {
+        if !replacement.contains('{') {
+            return replacement.to_string();
+        }
+
+        let mut out = String::new();
+        let mut rest = replacement;
+        while let Some(start) = rest.find('{') {
+            let tail = &rest[start..];
+            let Some(end) = tail.find('}') else {
+                break;
+            };
+            out.push_str(&rest[..start]);
+            match self.value(&tail[1..end], trigger) {
+                Some(value) => out.push_str(&value),
+                None => out.push_str(&tail[..=end]),
+            }
+            rest = &tail[end + 1..];
+        }
+        out.push_str(rest);
+        out
+    }
+
+    fn value(&self, name: &str, trigger: &str) -> Option<String> {
+        let value = match name {
+            "date" => {
+                let now = self.clock.now();
+                format!("{:02}/{:02}/{}", now.day, now.month, now.year)
+            }
+            "date_long" => {
+                let now = self.clock.now();
+                format!(
+                    "{}, ngày {} tháng {} năm {}",
+                    WEEKDAYS[now.weekday()],
+                    now.day,
+                    now.month,
+                    now.year
+                )
+            }
+            "weekday" => WEEKDAYS[self.clock.now().weekday()].to_string(),
+            "time" => {
+                let now = self.clock.now();
+                format!("{:02}:{:02}", now.hour, now.minute)
+            }
+            "clipboard" => self.clipboard.text(),
+            "counter" => {
+                let mut counters = self.counters.borrow_mut();
+                let next = counters.entry(trigger.to_lowercase()).or_insert(1);
+                *next += 1;
+                (*next - 1).to_string()
+            }
+            "trigger" => trigger.to_string(),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn templates() -> Templates {
+        let mut t = Templates::default();
+        t.set_clock(Box::new(DateTime::new(2026, 10, 12, 9, 5)));
+        t.set_clipboard(Box::new("gonhanh.org".to_string()));
+        t
+    }
+
+    #[test]
+    fn test_date_time() {
+        let t = templates();
+        assert_eq!(t.expand("{date} {time}", "d"), "12/10/2026 09:05");
+        assert_eq!(
+            t.expand("{date_long}", "d"),
+            "thứ Hai, ngày 12 tháng 10 năm 2026"
+        );
+        assert_eq!(t.expand("{weekday}", "d"), "thứ Hai");
+    }
+
+    #[test]
+    fn test_weekday() {
+        assert_eq!(DateTime::new(1970, 1, 1, 0, 0).weekday(), 4);
+        assert_eq!(DateTime::new(2024, 2, 29, 0, 0).weekday(), 4);
+        assert_eq!(DateTime::new(2026, 10, 18, 0, 0).weekday(), 0);
+    }
+
+    #[test]
+    fn test_from_unix() {
+        assert_eq!(DateTime::from_unix(0), DateTime::new(1970, 1, 1, 0, 0));
+        // 2026-10-12 14:05 UTC
+        assert_eq!(
+            DateTime::from_unix(1_791_813_900),
+            DateTime::new(2026, 10, 12, 14, 5)
+        );
+        assert_eq!(
+            DateTime::from_unix(951_782_400),
+            DateTime::new(2000, 2, 29, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_clipboard_trigger_unknown() {
+        let t = templates();
+        assert_eq!(t.expand("see {clipboard}", "x"), "see gonhanh.org");
+        assert_eq!(t.expand("[{trigger}]", "Vn"), "[Vn]");
+        assert_eq!(t.expand("{name} {", "x"), "{name} {");
+    }
+
+    #[test]
+    fn test_counter() {
+        let t = templates();
+        assert_eq!(t.expand("#{counter}", "n"), "#1");
+        assert_eq!(t.expand("#{counter}", "N"), "#2");
+        assert_eq!(t.expand("#{counter}", "m"), "#1");
+        t.reset_counters();
+        assert_eq!(t.expand("#{counter}", "n"), "#1");
+    }
+}
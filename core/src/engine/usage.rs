@@ -0,0 +1,390 @@
+//! Usage Statistics - Shortcut counts and suggested shortcuts
+//!
+//! Counts how often each shortcut fires and, when phrase tracking is on,
+//! how often long words and phrases (2-4 words between punctuation) are
+//! typed in full. Phrases typed often enough without a shortcut become
+//! suggestions with a proposed trigger ("Hồ Chí Minh" → "hcm").
+//!
+//! Nothing leaves the engine: the platform persists the counts with
+//! `save`/`load` (tab-separated lines, see `USAGE_HEADER`).
+
+use super::shortcut::ShortcutTable;
+use super::shortcut_analysis::is_vietnamese_syllable;
+use crate::data::chars;
+use crate::utils;
+use std::collections::{HashMap, HashSet};
+
+/// First line of saved usage data
+pub const USAGE_HEADER: &str = "# gonhanh usage v1";
+
+/// Single words shorter than this (in chars) are not tracked
+const MIN_WORD_CHARS: usize = 8;
+/// Phrases shorter than this (in chars, with spaces) are not tracked
+const MIN_PHRASE_CHARS: usize = 10;
+/// Longest phrase tracked, in words
+const MAX_PHRASE_WORDS: usize = 4;
+/// Times a phrase must be typed before it is suggested
+const MIN_SUGGESTION_COUNT: u32 = 3;
+/// Tracked phrases kept before phrases seen once are dropped
+const MAX_TRACKED: usize = 5000;
+
+/// Phrase worth a shortcut
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub phrase: String,
+    /// Proposed trigger, not used by any shortcut
+    pub trigger: String,
+    /// Times the phrase was typed
+    pub count: u32,
+}
+
+#[derive(Debug, Default)]
+pub struct Usage {
+    /// Trigger (lowercase) → times fired
+    fired: HashMap<String, u32>,
+    /// Word or phrase → times typed
+    phrases: HashMap<String, u32>,
+    /// Track words and phrases (shortcut counts are always kept)
+    tracking: bool,
+    /// Words committed since the last punctuation, oldest first
+    recent: Vec<String>,
+}
+
+impl Usage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_tracking(&mut self, enabled: bool) {
+        self.tracking = enabled;
+        self.recent.clear();
+    }
+
+    pub fn is_tracking(&self) -> bool {
+        self.tracking
+    }
+
+    /// Count a shortcut firing (also ends the current phrase)
+    pub fn record_shortcut(&mut self, trigger: &str) {
+        *self.fired.entry(trigger.to_lowercase()).or_insert(0) += 1;
+        self.end_phrase();
+    }
+
+    /// Uncount a firing (the expansion was undone)
+    pub fn retract_shortcut(&mut self, trigger: &str) {
+        let trigger = trigger.to_lowercase();
+        if let Some(count) = self.fired.get_mut(&trigger) {
+            *count -= 1;
+            if *count == 0 {
+                self.fired.remove(&trigger);
+            }
+        }
+    }
+
+    /// Times a shortcut has fired
+    pub fn shortcut_count(&self, trigger: &str) -> u32 {
+        self.fired
+            .get(&trigger.to_lowercase())
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Times a word or phrase has been typed
+    pub fn phrase_count(&self, phrase: &str) -> u32 {
+        self.phrases.get(phrase).copied().unwrap_or(0)
+    }
+
+    /// Word committed with space: count it and the phrases ending with it
+    pub fn commit_word(&mut self, word: &str) {
+        if !self.tracking || word.is_empty() {
+            return;
+        }
+        if self.recent.len() == MAX_PHRASE_WORDS {
+            self.recent.remove(0);
+        }
+        self.recent.push(word.to_string());
+        for phrase in self.tail_phrases() {
+            *self.phrases.entry(phrase).or_insert(0) += 1;
+        }
+        if self.phrases.len() > MAX_TRACKED {
+            self.phrases.retain(|_, count| *count > 1);
+        }
+    }
+
+    /// Last committed word was restored for editing (backspace after space):
+    /// undo its counts so the corrected word is not counted twice
+    pub fn retract_word(&mut self) {
+        if self.recent.is_empty() {
+            return;
+        }
+        for phrase in self.tail_phrases() {
+            if let Some(count) = self.phrases.get_mut(&phrase) {
+                *count -= 1;
+                if *count == 0 {
+                    self.phrases.remove(&phrase);
+                }
+            }
+        }
+        self.recent.pop();
+    }
+
+    /// Punctuation, shortcut or cursor move: the next word starts a new phrase
+    pub fn end_phrase(&mut self) {
+        self.recent.clear();
+    }
+
+    /// Forget all counts
+    pub fn clear(&mut self) {
+        self.fired.clear();
+        self.phrases.clear();
+        self.recent.clear();
+    }
+
+    /// Tracked words and phrases ending with the last committed word
+    fn tail_phrases(&self) -> Vec<String> {
+        let mut out = vec![];
+        for start in (0..self.recent.len()).rev() {
+            let words = &self.recent[start..];
+            let phrase = words.join(" ");
+            let min = if words.len() == 1 {
+                MIN_WORD_CHARS
+            } else {
+                MIN_PHRASE_CHARS
+            };
+            if phrase.chars().count() >= min {
+                out.push(phrase);
+            }
+        }
+        out
+    }
+
+    /// Phrases typed often without a shortcut, most frequent first
+    ///
+    /// A phrase is left out when a shortcut already produces it, or when it
+    /// was only typed as part of a longer phrase ("Chí Minh" inside
+    /// "Hồ Chí Minh").
+    pub fn suggestions(&self, shortcuts: &ShortcutTable, limit: usize) -> Vec<Suggestion> {
+        let covered: HashSet<String> = shortcuts
+            .iter()
+            .map(|s| s.replacement.to_lowercase())
+            .collect();
+        let frequent: Vec<(&String, u32)> = self
+            .phrases
+            .iter()
+            .filter(|(_, &count)| count >= MIN_SUGGESTION_COUNT)
+            .map(|(phrase, &count)| (phrase, count))
+            .collect();
+        let mut candidates: Vec<(&String, u32)> = frequent
+            .iter()
+            .filter(|(phrase, count)| {
+                !covered.contains(&phrase.to_lowercase())
+                    && !frequent
+                        .iter()
+                        .any(|(longer, n)| n >= count && contains_words(longer, phrase))
+            })
+            .copied()
+            .collect();
+        candidates.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+        let mut out = vec![];
+        let mut taken = HashSet::new();
+        for (phrase, count) in candidates {
+            if out.len() == limit {
+                break;
+            }
+            let Some(trigger) = propose_trigger(phrase, shortcuts, &taken) else {
+                continue;
+            };
+            taken.insert(trigger.clone());
+            out.push(Suggestion {
+                phrase: phrase.clone(),
+                trigger,
+                count,
+            });
+        }
+        out
+    }
+
+    /// Serialize counts: header, then `s<TAB>count<TAB>trigger` and
+    /// `p<TAB>count<TAB>phrase` lines sorted by text
+    pub fn save(&self) -> String {
+        let mut out = String::from(USAGE_HEADER);
+        out.push('\n');
+        for (kind, map) in [('s', &self.fired), ('p', &self.phrases)] {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort();
+            for (text, count) in entries {
+                out.push_str(&format!("{kind}\t{count}\t{text}\n"));
+            }
+        }
+        out
+    }
+
+    /// Add counts from `save` output. Returns the number of entries read;
+    /// comments and malformed lines are skipped.
+    pub fn load(&mut self, text: &str) -> usize {
+        let mut loaded = 0;
+        for line in text.lines() {
+            let mut fields = line.splitn(3, '\t');
+            let (Some(kind), Some(count), Some(text)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+            let Ok(count) = count.parse::<u32>() else {
+                continue;
+            };
+            let map = match kind {
+                "s" => &mut self.fired,
+                "p" => &mut self.phrases,
+                _ => continue,
+            };
+            if text.is_empty() {
+                continue;
+            }
+            let entry = map.entry(text.to_string()).or_insert(0);
+            *entry = entry.saturating_add(count);
+            loaded += 1;
+        }
+        loaded
+    }
+}
+
+/// `inner` appears in `outer` as whole words
+fn contains_words(outer: &str, inner: &str) -> bool {
+    outer.len() > inner.len() && format!(" {outer} ").contains(&format!(" {inner} "))
+}
+
+/// Lowercase ASCII letter or digit for a char (diacritics and đ removed)
+fn fold(c: char) -> Option<char> {
+    if c.is_ascii_alphanumeric() {
+        return Some(c.to_ascii_lowercase());
+    }
+    chars::parse_char(c).and_then(|p| utils::key_to_char(p.key, false))
+}
+
+/// Trigger for a phrase: initials of each word ("Hồ Chí Minh" → "hcm"), or
+/// the first three letters of a single word ("chương trình" is two words,
+/// "Nguyễn" → "ngu"). A digit is appended while the trigger is taken or
+/// is itself a Vietnamese syllable.
+fn propose_trigger(
+    phrase: &str,
+    shortcuts: &ShortcutTable,
+    taken: &HashSet<String>,
+) -> Option<String> {
+    let words: Vec<&str> = phrase.split(' ').collect();
+    let base: String = if words.len() == 1 {
+        phrase.chars().filter_map(fold).take(3).collect()
+    } else {
+        words
+            .iter()
+            .filter_map(|w| w.chars().find_map(fold))
+            .collect()
+    };
+    if base.chars().count() < 2 {
+        return None;
+    }
+    let free = |t: &str| {
+        !taken.contains(t)
+            && !is_vietnamese_syllable(t)
+            && !shortcuts.iter().any(|s| s.trigger.to_lowercase() == t)
+    };
+    if free(&base) {
+        return Some(base);
+    }
+    (2..10).map(|n| format!("{base}{n}")).find(|t| free(t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::shortcut::Shortcut;
+
+    fn typed(usage: &mut Usage, text: &str, times: usize) {
+        for _ in 0..times {
+            for word in text.split(' ') {
+                usage.commit_word(word);
+            }
+            usage.end_phrase();
+        }
+    }
+
+    #[test]
+    fn test_tracking_off_by_default() {
+        let mut usage = Usage::new();
+        typed(&mut usage, "Hồ Chí Minh", 5);
+        assert_eq!(usage.phrase_count("Hồ Chí Minh"), 0);
+        usage.record_shortcut("VN");
+        assert_eq!(usage.shortcut_count("vn"), 1);
+    }
+
+    #[test]
+    fn test_phrases_and_words() {
+        let mut usage = Usage::new();
+        usage.set_tracking(true);
+        typed(&mut usage, "thành phố Hồ Chí Minh", 1);
+        assert_eq!(usage.phrase_count("phố Hồ Chí Minh"), 1);
+        assert_eq!(usage.phrase_count("Hồ Chí Minh"), 1);
+        assert_eq!(usage.phrase_count("thành phố Hồ Chí"), 1);
+        // Five words: the first drops out of the window
+        assert_eq!(usage.phrase_count("thành phố Hồ Chí Minh"), 0);
+        // Short words alone are not tracked
+        assert_eq!(usage.phrase_count("thành"), 0);
+
+        typed(&mut usage, "development", 1);
+        assert_eq!(usage.phrase_count("development"), 1);
+
+        // Word edited after backspace-over-space counts once
+        usage.commit_word("phát");
+        usage.commit_word("triểnn");
+        usage.retract_word();
+        usage.commit_word("triển");
+        assert_eq!(usage.phrase_count("phát triểnn"), 0);
+        assert_eq!(usage.phrase_count("phát triển"), 1);
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let mut usage = Usage::new();
+        usage.set_tracking(true);
+        typed(&mut usage, "Hồ Chí Minh", 4);
+        typed(&mut usage, "đại học quốc gia", 3);
+        typed(&mut usage, "cộng hoà xã hội", 2);
+
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new("dhqg", "x"));
+        let s = usage.suggestions(&table, 10);
+        let pairs: Vec<(&str, &str, u32)> = s
+            .iter()
+            .map(|s| (s.phrase.as_str(), s.trigger.as_str(), s.count))
+            .collect();
+        assert_eq!(
+            pairs,
+            [("Hồ Chí Minh", "hcm", 4), ("đại học quốc gia", "dhqg2", 3)]
+        );
+
+        // Phrases a shortcut already produces are not suggested
+        table.add(Shortcut::new("hcm", "Hồ Chí Minh"));
+        assert_eq!(usage.suggestions(&table, 10)[0].phrase, "đại học quốc gia");
+        assert_eq!(usage.suggestions(&table, 1).len(), 1);
+    }
+
+    #[test]
+    fn test_save_load() {
+        let mut usage = Usage::new();
+        usage.set_tracking(true);
+        typed(&mut usage, "Hồ Chí Minh", 2);
+        usage.record_shortcut("vn");
+        let saved = usage.save();
+        assert!(saved.starts_with(USAGE_HEADER));
+        assert!(saved.contains("s\t1\tvn\n"));
+        assert!(saved.contains("p\t2\tHồ Chí Minh\n"));
+
+        let mut other = Usage::new();
+        assert_eq!(other.load(&saved), 2);
+        assert_eq!(other.load("s\t2\tvn\nbad line\np\tx\ty\n"), 1);
+        assert_eq!(other.shortcut_count("vn"), 3);
+        assert_eq!(other.phrase_count("Hồ Chí Minh"), 2);
+        assert_eq!(other.save().lines().count(), 3);
+    }
+}
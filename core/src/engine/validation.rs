@@ -6,6 +6,7 @@
 use super::syllable::{parse, Syllable};
 use crate::data::chars::tone;
 use crate::data::constants;
+use crate::data::dictionary;
 use crate::data::keys;
 
 /// Validation result
@@ -286,6 +287,18 @@ pub fn is_valid_with_tones(keys: &[u16], tones: &[u8]) -> bool {
     validate(&snap).is_valid()
 }
 
+/// Check syllable against the bundled dictionary (optional layer over the rules)
+///
+/// `complete`: whole syllable typed - must be attested, including its mark.
+/// Otherwise it only has to start an attested syllable ("tiê" → tiếng).
+pub fn is_attested(syllable: &str, complete: bool) -> bool {
+    if complete {
+        dictionary::contains(syllable)
+    } else {
+        dictionary::is_prefix(syllable)
+    }
+}
+
 /// Quick check if buffer could be valid Vietnamese (keys only - legacy)
 ///
 /// NOTE: This cannot fully validate modifier requirements.
@@ -0,0 +1,342 @@
+//! Custom Input Method
+//!
+//! Keymap loaded at runtime from a text description, one binding per line:
+//!
+//! ```text
+//! # Telex with [ ] for ơ ư
+//! base telex
+//! horn [ o
+//! horn ] u
+//! ```
+//!
+//! Directives:
+//! - `base telex|vni|viqr` - start from a built-in method's keys and engine behaviour
+//! - `mark <key> <1-5>` - 1=sắc, 2=huyền, 3=hỏi, 4=ngã, 5=nặng
+//! - `circumflex|horn|breve <key> <targets>` - targets are vowel letters (e.g. `aou`);
+//!   a punctuation key with one target also types that vowel when the word
+//!   has none to modify (`horn ] u`: "t]" → "tư", "]" → "ư")
+//! - `stroke <key>` - d → đ
+//! - `remove <key>` - remove diacritics
+//! - `unmap <key>` - drop a binding (e.g. one inherited from base)
+//!
+//! Keys are single characters. Letters and digits work like Telex/VNI keys,
+//! punctuation keys (`[`, `'`, `?`, ...) work like VIQR symbols.
+//! Later lines override earlier bindings for the same key.
+
+use super::{Method, Symbol, ToneType};
+use crate::data::keys;
+use std::fmt;
+
+/// Keymap parse error
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeymapError {
+    /// 1-based line number
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for KeymapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Single key binding
+#[derive(Debug, Clone)]
+struct Binding {
+    key: u16,
+    /// None for letters/digits (Shift-insensitive), Some(shift) for punctuation
+    shift: Option<bool>,
+    modifier: Symbol,
+    /// Target vowels for tone modifiers
+    targets: Vec<u16>,
+    /// Punctuation tone key that types its single target vowel when
+    /// nothing takes the modifier (declared in the keymap, not inherited)
+    standalone: bool,
+}
+
+/// Data-driven input method built from a keymap description
+#[derive(Debug, Clone, Default)]
+pub struct CustomMethod {
+    /// Built-in method id used for engine behaviour (0=Telex, 1=VNI, 2=VIQR)
+    base: u8,
+    bindings: Vec<Binding>,
+}
+
+/// Characters scanned when copying bindings from a built-in method
+const LETTERS_AND_DIGITS: &str = "abcdefghijklmnopqrstuvwxyz0123456789";
+const PUNCTUATION: &str = "`-=[]\\;',./~!@#$%^&*()_+{}|:\"<>?";
+
+impl CustomMethod {
+    /// Parse keymap description
+    pub fn parse(text: &str) -> Result<Self, KeymapError> {
+        let mut method = Self::default();
+
+        for (i, line) in text.lines().enumerate() {
+            let err = |message: &str| KeymapError {
+                line: i + 1,
+                message: message.to_string(),
+            };
+
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let Some(&directive) = parts.first() else {
+                continue;
+            };
+            // Comment line ("#" as a key is still allowed: "mark # 1")
+            if directive.starts_with('#') {
+                continue;
+            }
+
+            if directive == "base" {
+                let id = match parts.get(1).copied() {
+                    Some("telex") => 0,
+                    Some("vni") => 1,
+                    Some("viqr") => 2,
+                    _ => return Err(err("base must be telex, vni or viqr")),
+                };
+                method = Self::from_builtin(id);
+                continue;
+            }
+
+            let (key, shift) = parts
+                .get(1)
+                .and_then(|k| parse_key(k))
+                .ok_or_else(|| err("expected a single-character key"))?;
+
+            let (modifier, targets) = match directive {
+                "mark" => {
+                    let val = parts
+                        .get(2)
+                        .and_then(|v| v.parse::<u8>().ok())
+                        .filter(|v| (1..=5).contains(v))
+                        .ok_or_else(|| err("mark must be 1-5"))?;
+                    (Symbol::Mark(val), vec![])
+                }
+                "circumflex" | "horn" | "breve" => {
+                    let tone_type = match directive {
+                        "circumflex" => ToneType::Circumflex,
+                        "horn" => ToneType::Horn,
+                        _ => ToneType::Breve,
+                    };
+                    let targets = parts
+                        .get(2)
+                        .and_then(|t| parse_targets(t))
+                        .ok_or_else(|| err("expected target vowels (e.g. aou)"))?;
+                    (Symbol::Tone(tone_type), targets)
+                }
+                "stroke" => (Symbol::Stroke, vec![]),
+                "remove" => (Symbol::Remove, vec![]),
+                "unmap" => {
+                    method.unbind(key, shift);
+                    continue;
+                }
+                _ => return Err(err("unknown directive")),
+            };
+
+            let standalone = shift.is_some() && targets.len() == 1;
+            method.bind(Binding {
+                key,
+                shift,
+                modifier,
+                targets,
+                standalone,
+            });
+        }
+
+        Ok(method)
+    }
+
+    /// Copy all bindings of a built-in method
+    pub fn from_builtin(id: u8) -> Self {
+        let builtin = super::get(id);
+        let mut method = Self {
+            base: id,
+            bindings: vec![],
+        };
+
+        for c in LETTERS_AND_DIGITS.chars() {
+            let Some((key, _)) = keys::from_char(c) else {
+                continue;
+            };
+            let binding = |modifier, targets: &[u16]| Binding {
+                key,
+                shift: None,
+                modifier,
+                targets: targets.to_vec(),
+                standalone: false,
+            };
+            if let Some(val) = builtin.mark(key) {
+                method.bind(binding(Symbol::Mark(val), &[]));
+            } else if let Some(tone_type) = builtin.tone(key) {
+                method.bind(binding(Symbol::Tone(tone_type), builtin.tone_targets(key)));
+            } else if builtin.stroke(key) {
+                method.bind(binding(Symbol::Stroke, &[]));
+            } else if builtin.remove(key) {
+                method.bind(binding(Symbol::Remove, &[]));
+            }
+        }
+
+        for c in PUNCTUATION.chars() {
+            let Some((key, shift)) = keys::from_char(c) else {
+                continue;
+            };
+            if let Some(modifier) = builtin.symbol(key, shift) {
+                method.bind(Binding {
+                    key,
+                    shift: Some(shift),
+                    modifier,
+                    targets: builtin.symbol_targets(key, shift).to_vec(),
+                    standalone: false,
+                });
+            }
+        }
+
+        method
+    }
+
+    /// Built-in method id used for engine behaviour
+    pub fn base(&self) -> u8 {
+        self.base
+    }
+
+    fn bind(&mut self, binding: Binding) {
+        self.unbind(binding.key, binding.shift);
+        self.bindings.push(binding);
+    }
+
+    fn unbind(&mut self, key: u16, shift: Option<bool>) {
+        self.bindings
+            .retain(|b| !(b.key == key && b.shift == shift));
+    }
+
+    fn find(&self, key: u16, shift: Option<bool>) -> Option<&Binding> {
+        self.bindings
+            .iter()
+            .find(|b| b.key == key && b.shift == shift)
+    }
+}
+
+/// Parse single-character key: letters/digits are Shift-insensitive, punctuation is not
+fn parse_key(s: &str) -> Option<(u16, Option<bool>)> {
+    let mut chars = s.chars();
+    let c = chars.next()?;
+    if chars.next().is_some() {
+        return None;
+    }
+    let (key, shift) = keys::from_char(c)?;
+    if keys::is_letter(key) || (keys::is_number(key) && !shift) {
+        Some((key, None))
+    } else if keys::is_break_ext(key, shift) {
+        Some((key, Some(shift)))
+    } else {
+        None
+    }
+}
+
+/// Parse target vowels like "aou"
+fn parse_targets(s: &str) -> Option<Vec<u16>> {
+    s.chars()
+        .map(|c| {
+            keys::from_char(c)
+                .map(|(k, _)| k)
+                .filter(|&k| keys::is_vowel(k))
+        })
+        .collect()
+}
+
+impl Method for CustomMethod {
+    fn mark(&self, key: u16) -> Option<u8> {
+        match self.find(key, None)?.modifier {
+            Symbol::Mark(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    fn tone(&self, key: u16) -> Option<ToneType> {
+        match self.find(key, None)?.modifier {
+            Symbol::Tone(tone_type) => Some(tone_type),
+            _ => None,
+        }
+    }
+
+    fn tone_targets(&self, key: u16) -> &[u16] {
+        self.find(key, None).map_or(&[], |b| &b.targets)
+    }
+
+    fn stroke(&self, key: u16) -> bool {
+        self.find(key, None)
+            .is_some_and(|b| b.modifier == Symbol::Stroke)
+    }
+
+    fn remove(&self, key: u16) -> bool {
+        self.find(key, None)
+            .is_some_and(|b| b.modifier == Symbol::Remove)
+    }
+
+    fn symbol(&self, key: u16, shift: bool) -> Option<Symbol> {
+        self.find(key, Some(shift)).map(|b| b.modifier)
+    }
+
+    fn symbol_targets(&self, key: u16, shift: bool) -> &[u16] {
+        self.find(key, Some(shift)).map_or(&[], |b| &b.targets)
+    }
+
+    fn symbol_vowel(&self, key: u16, shift: bool) -> Option<u16> {
+        self.find(key, Some(shift))
+            .filter(|b| b.standalone)
+            .map(|b| b.targets[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_copy() {
+        let m = CustomMethod::from_builtin(0);
+        assert_eq!(m.mark(keys::S), Some(1));
+        assert_eq!(m.tone(keys::W), Some(ToneType::Horn));
+        assert_eq!(m.tone_targets(keys::W), &[keys::A, keys::O, keys::U]);
+        assert!(m.stroke(keys::D));
+        assert!(m.remove(keys::Z));
+
+        let m = CustomMethod::from_builtin(2);
+        assert_eq!(m.symbol(keys::SLASH, true), Some(Symbol::Mark(3)));
+        assert_eq!(m.symbol(keys::SLASH, false), None);
+    }
+
+    #[test]
+    fn test_parse_bindings() {
+        let m = CustomMethod::parse(
+            "# Telex with [ ] for ơ ư\nbase telex\nhorn [ o\nhorn ] u\nunmap z\nmark # 1",
+        )
+        .unwrap();
+        assert_eq!(m.base(), 0);
+        assert_eq!(
+            m.symbol(keys::LBRACKET, false),
+            Some(Symbol::Tone(ToneType::Horn))
+        );
+        assert_eq!(m.symbol_targets(keys::RBRACKET, false), &[keys::U]);
+        assert_eq!(m.symbol(keys::N3, true), Some(Symbol::Mark(1)));
+        assert_eq!(m.symbol_vowel(keys::RBRACKET, false), Some(keys::U));
+        assert_eq!(m.symbol_vowel(keys::N3, true), None);
+        // Inherited and multi-target symbols only modify
+        assert_eq!(
+            CustomMethod::from_builtin(2).symbol_vowel(keys::N9, true),
+            None
+        );
+        assert!(!m.remove(keys::Z));
+        assert_eq!(m.mark(keys::S), Some(1));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = CustomMethod::parse("base telex\nmark s 9").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(CustomMethod::parse("base dvorak").is_err());
+        assert!(CustomMethod::parse("horn w xyz").is_err());
+        assert!(CustomMethod::parse("mark ss 1").is_err());
+        assert!(CustomMethod::parse("tone w aou").is_err());
+    }
+}
@@ -3,14 +3,20 @@
 //! Defines key mappings for Vietnamese input methods.
 //! Engine handles all pattern matching based on buffer scan.
 
+pub mod custom;
 pub mod telex;
+pub mod viqr;
 pub mod vni;
 
+pub use custom::CustomMethod;
 pub use telex::Telex;
+pub use viqr::Viqr;
 pub use vni::Vni;
 
 use crate::data::chars::tone;
 use crate::data::keys;
+use std::ops::Deref;
+use std::sync::Arc;
 
 /// Shared tone target constants
 pub const CIRCUMFLEX_TARGETS: &[u16] = &[keys::A, keys::E, keys::O];
@@ -25,7 +31,7 @@ pub enum ToneType {
     Circumflex,
     /// Horn: ơ, ư (and ă for Telex)
     Horn,
-    /// Breve: ă (VNI only)
+    /// Breve: ă (VNI/VIQR only)
     Breve,
 }
 
@@ -39,6 +45,22 @@ impl ToneType {
     }
 }
 
+/// Modifier triggered by a punctuation key (VIQR: ' ` ? ~ . ^ + ()
+///
+/// Punctuation modifiers depend on Shift (` = huyền, ~ = ngã share one key),
+/// so they are looked up separately from letter/number modifiers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Symbol {
+    /// Mark: 1=sắc, 2=huyền, 3=hỏi, 4=ngã, 5=nặng
+    Mark(u8),
+    /// Tone: circumflex, horn, breve
+    Tone(ToneType),
+    /// Stroke: d → đ
+    Stroke,
+    /// Remove diacritics
+    Remove,
+}
+
 /// Input method trait - defines key mappings only
 pub trait Method {
     /// Check if key is a mark modifier
@@ -51,23 +73,60 @@ pub trait Method {
 
     /// Get valid targets for tone key
     /// Returns list of vowel keys this tone can apply to
-    fn tone_targets(&self, key: u16) -> &'static [u16];
+    fn tone_targets(&self, key: u16) -> &[u16];
 
     /// Check if key is stroke modifier (d → đ)
     fn stroke(&self, key: u16) -> bool;
 
     /// Check if key removes diacritics
     fn remove(&self, key: u16) -> bool;
+
+    /// Check if punctuation key (with Shift state) is a modifier
+    /// Default: no punctuation modifiers (Telex, VNI)
+    fn symbol(&self, _key: u16, _shift: bool) -> Option<Symbol> {
+        None
+    }
+
+    /// Get valid targets for a punctuation tone modifier
+    fn symbol_targets(&self, _key: u16, _shift: bool) -> &[u16] {
+        &[]
+    }
+
+    /// Vowel a punctuation tone modifier types on its own when no vowel
+    /// of the word takes it (custom keymaps: "horn ] u", "t]" → "tư")
+    fn symbol_vowel(&self, _key: u16, _shift: bool) -> Option<u16> {
+        None
+    }
 }
 
 /// Static method instances (zero-sized types, no heap allocation)
 static TELEX: Telex = Telex;
 static VNI: Vni = Vni;
+static VIQR: Viqr = Viqr;
 
 /// Get method by id (returns static reference, no allocation)
 pub fn get(id: u8) -> &'static dyn Method {
     match id {
         1 => &VNI,
+        2 => &VIQR,
         _ => &TELEX,
     }
 }
+
+/// Method used by the engine: built-in (by id) or a loaded custom keymap
+#[derive(Clone)]
+pub enum ActiveMethod {
+    Builtin(&'static dyn Method),
+    Custom(Arc<CustomMethod>),
+}
+
+impl Deref for ActiveMethod {
+    type Target = dyn Method;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            ActiveMethod::Builtin(m) => *m,
+            ActiveMethod::Custom(m) => m.as_ref(),
+        }
+    }
+}
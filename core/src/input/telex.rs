@@ -31,7 +31,7 @@ impl Method for Telex {
         }
     }
 
-    fn tone_targets(&self, key: u16) -> &'static [u16] {
+    fn tone_targets(&self, key: u16) -> &[u16] {
         match key {
             keys::A => &[keys::A],
             keys::E => &[keys::E],
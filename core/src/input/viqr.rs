@@ -0,0 +1,102 @@
+//! VIQR Input Method
+//!
+//! Key mappings (RFC 1456 style):
+//! - Marks: '=sắc, `=huyền, ?=hỏi, ~=ngã, .=nặng
+//! - Tones: ^=circumflex, +=horn, (=breve
+//! - Stroke: dd
+//!
+//! Modifiers are punctuation keys, so they only apply while a word is being typed.
+//! Pressing the same symbol twice types it literally ("to.." → "to."),
+//! and "\" escapes the next symbol ("to\." → "to.").
+
+use super::{Method, Symbol, ToneType, BREVE_TARGETS, CIRCUMFLEX_TARGETS, HORN_TARGETS_VNI};
+use crate::data::keys;
+
+pub struct Viqr;
+
+impl Method for Viqr {
+    fn mark(&self, _key: u16) -> Option<u8> {
+        None
+    }
+
+    fn tone(&self, _key: u16) -> Option<ToneType> {
+        None
+    }
+
+    fn tone_targets(&self, _key: u16) -> &[u16] {
+        &[]
+    }
+
+    fn stroke(&self, key: u16) -> bool {
+        key == keys::D
+    }
+
+    fn remove(&self, _key: u16) -> bool {
+        false
+    }
+
+    fn symbol(&self, key: u16, shift: bool) -> Option<Symbol> {
+        match (key, shift) {
+            (keys::QUOTE, false) => Some(Symbol::Mark(1)), // ' sắc
+            (keys::BACKQUOTE, false) => Some(Symbol::Mark(2)), // ` huyền
+            (keys::SLASH, true) => Some(Symbol::Mark(3)),  // ? hỏi
+            (keys::BACKQUOTE, true) => Some(Symbol::Mark(4)), // ~ ngã
+            (keys::DOT, false) => Some(Symbol::Mark(5)),   // . nặng
+            (keys::N6, true) => Some(Symbol::Tone(ToneType::Circumflex)), // ^
+            (keys::EQUAL, true) => Some(Symbol::Tone(ToneType::Horn)), // +
+            (keys::N9, true) => Some(Symbol::Tone(ToneType::Breve)), // (
+            _ => None,
+        }
+    }
+
+    fn symbol_targets(&self, key: u16, shift: bool) -> &[u16] {
+        match (key, shift) {
+            (keys::N6, true) => CIRCUMFLEX_TARGETS,
+            (keys::EQUAL, true) => HORN_TARGETS_VNI,
+            (keys::N9, true) => BREVE_TARGETS,
+            _ => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marks() {
+        let v = Viqr;
+        assert_eq!(v.symbol(keys::QUOTE, false), Some(Symbol::Mark(1)));
+        assert_eq!(v.symbol(keys::BACKQUOTE, false), Some(Symbol::Mark(2)));
+        assert_eq!(v.symbol(keys::SLASH, true), Some(Symbol::Mark(3)));
+        assert_eq!(v.symbol(keys::BACKQUOTE, true), Some(Symbol::Mark(4)));
+        assert_eq!(v.symbol(keys::DOT, false), Some(Symbol::Mark(5)));
+        assert_eq!(v.symbol(keys::SLASH, false), None);
+        assert_eq!(v.mark(keys::S), None);
+    }
+
+    #[test]
+    fn test_tones() {
+        let v = Viqr;
+        assert_eq!(
+            v.symbol(keys::N6, true),
+            Some(Symbol::Tone(ToneType::Circumflex))
+        );
+        assert_eq!(
+            v.symbol(keys::EQUAL, true),
+            Some(Symbol::Tone(ToneType::Horn))
+        );
+        assert_eq!(
+            v.symbol(keys::N9, true),
+            Some(Symbol::Tone(ToneType::Breve))
+        );
+        assert_eq!(v.symbol(keys::N6, false), None);
+    }
+
+    #[test]
+    fn test_stroke() {
+        let v = Viqr;
+        assert!(v.stroke(keys::D));
+        assert!(!v.stroke(keys::N9));
+    }
+}
@@ -32,7 +32,7 @@ impl Method for Vni {
         }
     }
 
-    fn tone_targets(&self, key: u16) -> &'static [u16] {
+    fn tone_targets(&self, key: u16) -> &[u16] {
         match key {
             keys::N6 => CIRCUMFLEX_TARGETS,
             keys::N7 => HORN_TARGETS_VNI,
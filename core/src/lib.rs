@@ -1,13 +1,13 @@
 //! Gõ Nhanh Vietnamese IME Core
 //!
-//! Simple Vietnamese input method engine supporting Telex and VNI.
+//! Simple Vietnamese input method engine supporting Telex, VNI and VIQR.
 //!
 //! # FFI Usage
 //!
 //! ```c
 //! // Initialize once at app start
 //! ime_init();
-//! ime_method(0);  // 0=Telex, 1=VNI
+//! ime_method(0);  // 0=Telex, 1=VNI, 2=VIQR
 //!
 //! // Process each keystroke
 //! ImeResult* r = ime_key(keycode, is_shift, is_ctrl);
@@ -19,24 +19,68 @@
 //! // Clean up on word boundary
 //! ime_clear();
 //! ```
+//!
+//! For one engine per window or input context, use handles: every engine
+//! function has an `ime_engine_*` form taking the handle first.
+//!
+//! ```c
+//! ImeEngine* e = ime_engine_new();
+//! ime_engine_method(e, 1);
+//! ImeResult* r = ime_engine_key(e, keycode, is_shift, is_ctrl);
+//! ime_free(r);
+//! ime_engine_free(e);
+//! ```
 
+pub mod accent;
+pub mod convert;
 pub mod data;
 pub mod engine;
 pub mod input;
+pub mod reconvert;
 pub mod updater;
 pub mod utils;
 
 use engine::{Engine, Result};
+use std::ops::{Deref, DerefMut};
 use std::sync::Mutex;
 
+/// Engine instance behind an FFI handle (opaque to C)
+///
+/// Each handle has its own buffer, settings and shortcuts. A handle is not
+/// locked: use it from one thread at a time.
+pub struct ImeEngine(Engine);
+
+impl Deref for ImeEngine {
+    type Target = Engine;
+
+    fn deref(&self) -> &Engine {
+        &self.0
+    }
+}
+
+impl DerefMut for ImeEngine {
+    fn deref_mut(&mut self) -> &mut Engine {
+        &mut self.0
+    }
+}
+
 // Global engine instance (thread-safe via Mutex)
-static ENGINE: Mutex<Option<Engine>> = Mutex::new(None);
+static ENGINE: Mutex<Option<ImeEngine>> = Mutex::new(None);
 
 /// Lock the engine mutex, recovering from poisoned state if needed (for tests)
-fn lock_engine() -> std::sync::MutexGuard<'static, Option<Engine>> {
+fn lock_engine() -> std::sync::MutexGuard<'static, Option<ImeEngine>> {
     ENGINE.lock().unwrap_or_else(|e| e.into_inner())
 }
 
+/// Run a handle function on the global engine (null handle if not initialized)
+fn with_global<R>(f: impl FnOnce(*mut ImeEngine) -> R) -> R {
+    let mut guard = lock_engine();
+    let handle = guard
+        .as_mut()
+        .map_or(std::ptr::null_mut(), |e| e as *mut ImeEngine);
+    f(handle)
+}
+
 // ============================================================
 // FFI Interface
 // ============================================================
@@ -51,7 +95,35 @@ fn lock_engine() -> std::sync::MutexGuard<'static, Option<Engine>> {
 #[no_mangle]
 pub extern "C" fn ime_init() {
     let mut guard = lock_engine();
-    *guard = Some(Engine::new());
+    *guard = Some(ImeEngine(Engine::new()));
+}
+
+// ============================================================
+// Engine Handle FFI
+// ============================================================
+//
+// Every `ime_*` engine function has an `ime_engine_*` twin taking a handle
+// as first argument, for one engine per window or input context. The
+// global functions above and below use the engine from `ime_init`.
+
+/// Create an independent engine instance.
+///
+/// # Returns
+/// Handle for the `ime_engine_*` functions (free with `ime_engine_free`).
+#[no_mangle]
+pub extern "C" fn ime_engine_new() -> *mut ImeEngine {
+    Box::into_raw(Box::new(ImeEngine(Engine::new())))
+}
+
+/// Free an engine instance.
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be null.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_free(handle: *mut ImeEngine) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
 }
 
 /// Process a key event and return the result.
@@ -70,14 +142,33 @@ pub extern "C" fn ime_init() {
 /// * `backspace`: number of characters to delete
 /// * `chars`: UTF-32 codepoints to insert
 /// * `count`: number of valid chars
+/// * `flags`: 0x01 = key consumed, 0x02 = more output (see `ime_next_output`)
+/// * `cursor_left`: characters to move the cursor left after inserting
+///   (shortcut `{cursor}` marker; on the last chunk)
+/// * `delete_after`: characters after the cursor to delete before inserting
+///   (editing inside a word, see `ime_set_surrounding_text`)
 ///
 /// # Note
 /// For VNI mode with Shift+number keys (to type @, #, $ etc.),
 /// use `ime_key_ext` with the shift parameter.
 #[no_mangle]
 pub extern "C" fn ime_key(key: u16, caps: bool, ctrl: bool) -> *mut Result {
-    let mut guard = lock_engine();
-    if let Some(ref mut e) = *guard {
+    with_global(|h| unsafe { ime_engine_key(h, key, caps, ctrl) })
+}
+
+/// Handle version of [`ime_key`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_key(
+    handle: *mut ImeEngine,
+    key: u16,
+    caps: bool,
+    ctrl: bool,
+) -> *mut Result {
+    if let Some(e) = handle.as_mut() {
         let r = e.on_key(key, caps, ctrl);
         Box::into_raw(Box::new(r))
     } else {
@@ -105,8 +196,23 @@ pub extern "C" fn ime_key(key: u16, caps: bool, ctrl: bool) -> *mut Result {
 /// - etc.
 #[no_mangle]
 pub extern "C" fn ime_key_ext(key: u16, caps: bool, ctrl: bool, shift: bool) -> *mut Result {
-    let mut guard = lock_engine();
-    if let Some(ref mut e) = *guard {
+    with_global(|h| unsafe { ime_engine_key_ext(h, key, caps, ctrl, shift) })
+}
+
+/// Handle version of [`ime_key_ext`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_key_ext(
+    handle: *mut ImeEngine,
+    key: u16,
+    caps: bool,
+    ctrl: bool,
+    shift: bool,
+) -> *mut Result {
+    if let Some(e) = handle.as_mut() {
         let r = e.on_key_ext(key, caps, ctrl, shift);
         Box::into_raw(Box::new(r))
     } else {
@@ -114,28 +220,221 @@ pub extern "C" fn ime_key_ext(key: u16, caps: bool, ctrl: bool, shift: bool) ->
     }
 }
 
+/// Process a key event given as a native keycode.
+///
+/// The keycode is translated in the core, so frontends need no keycode table.
+///
+/// # Arguments
+/// * `source` - 0=macOS keycode, 1=Windows VK, 2=X11/XKB keysym, 3=Linux evdev
+/// * `code` - Keycode in that system
+/// * `mods` - Modifier bits: 0x01 = Shift, 0x02 = Caps Lock, 0x04 = Cmd/Ctrl/Alt
+///
+/// # Returns
+/// * Pointer to `Result` struct (caller must free with `ime_free`);
+///   action 0 for keys the engine doesn't use or an unknown source
+/// * `null` if engine not initialized
+#[no_mangle]
+pub extern "C" fn ime_key_code(source: u8, code: u32, mods: u8) -> *mut Result {
+    with_global(|h| unsafe { ime_engine_key_code(h, source, code, mods) })
+}
+
+/// Handle version of [`ime_key_code`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_key_code(
+    handle: *mut ImeEngine,
+    source: u8,
+    code: u32,
+    mods: u8,
+) -> *mut Result {
+    let Some(e) = handle.as_mut() else {
+        return std::ptr::null_mut();
+    };
+    let r = match data::keycodes::KeySource::from_id(source) {
+        Some(source) => e.on_key_code(source, code, mods),
+        None => Result::none(),
+    };
+    Box::into_raw(Box::new(r))
+}
+
+/// Process a key event given as the character it types.
+///
+/// For frontends that receive text rather than keycodes. Case and Shift
+/// come from the character; a character with no key on a US layout
+/// ends the current word.
+///
+/// # Arguments
+/// * `codepoint` - Unicode codepoint typed
+/// * `mods` - Modifier bits (only 0x04 = Cmd/Ctrl/Alt is used)
+///
+/// # Returns
+/// * Pointer to `Result` struct (caller must free with `ime_free`);
+///   action 0 for an invalid codepoint
+/// * `null` if engine not initialized
+#[no_mangle]
+pub extern "C" fn ime_key_char(codepoint: u32, mods: u8) -> *mut Result {
+    with_global(|h| unsafe { ime_engine_key_char(h, codepoint, mods) })
+}
+
+/// Handle version of [`ime_key_char`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_key_char(
+    handle: *mut ImeEngine,
+    codepoint: u32,
+    mods: u8,
+) -> *mut Result {
+    let Some(e) = handle.as_mut() else {
+        return std::ptr::null_mut();
+    };
+    let r = match char::from_u32(codepoint) {
+        Some(c) => e.on_char(c, mods),
+        None => Result::none(),
+    };
+    Box::into_raw(Box::new(r))
+}
+
+/// Fetch the next chunk of a long output.
+///
+/// Call after any `Result` with `flags & 0x02` (more output) until the flag
+/// is clear. Chunks have `backspace` 0: insert `chars` after the previous
+/// chunk. Pending chunks are dropped on the next key.
+///
+/// # Returns
+/// * Pointer to `Result` struct (caller must free with `ime_free`);
+///   action 0 if nothing is pending
+/// * `null` if engine not initialized
+#[no_mangle]
+pub extern "C" fn ime_next_output() -> *mut Result {
+    with_global(|h| unsafe { ime_engine_next_output(h) })
+}
+
+/// Handle version of [`ime_next_output`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_next_output(handle: *mut ImeEngine) -> *mut Result {
+    if let Some(e) = handle.as_mut() {
+        Box::into_raw(Box::new(e.next_output()))
+    } else {
+        std::ptr::null_mut()
+    }
+}
+
 /// Set the input method.
 ///
 /// # Arguments
-/// * `method` - 0 for Telex, 1 for VNI
+/// * `method` - 0 for Telex, 1 for VNI, 2 for VIQR
 ///
 /// No-op if engine not initialized.
 #[no_mangle]
 pub extern "C" fn ime_method(method: u8) {
-    let mut guard = lock_engine();
-    if let Some(ref mut e) = *guard {
+    with_global(|h| unsafe { ime_engine_method(h, method) })
+}
+
+/// Handle version of [`ime_method`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_method(handle: *mut ImeEngine, method: u8) {
+    if let Some(e) = handle.as_mut() {
         e.set_method(method);
     }
 }
 
+/// Load a user-defined keymap (see `input::custom` for the format).
+///
+/// Replaces the built-in key mappings until `ime_method` is called again.
+///
+/// # Arguments
+/// * `keymap` - C string with the keymap description, e.g. "base telex\nhorn [ o"
+/// * `error` - If non-null, receives the parse error as "line N: message"
+///   (free with `ime_free_string`), or null if there is none
+///
+/// # Returns
+/// true if the keymap was parsed and loaded, false otherwise
+/// (invalid pointer/UTF-8, parse error, or engine not initialized).
+///
+/// # Safety
+/// * `keymap` must be a valid null-terminated UTF-8 string, or null
+/// * `error` must be a valid pointer, or null
+#[no_mangle]
+pub unsafe extern "C" fn ime_load_keymap(
+    keymap: *const std::os::raw::c_char,
+    error: *mut *mut std::os::raw::c_char,
+) -> bool {
+    with_global(|h| unsafe { ime_engine_load_keymap(h, keymap, error) })
+}
+
+/// Handle version of [`ime_load_keymap`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// * `keymap` must be a valid null-terminated UTF-8 string, or null
+/// * `error` must be a valid pointer, or null
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_load_keymap(
+    handle: *mut ImeEngine,
+    keymap: *const std::os::raw::c_char,
+    error: *mut *mut std::os::raw::c_char,
+) -> bool {
+    if !error.is_null() {
+        *error = std::ptr::null_mut();
+    }
+    if keymap.is_null() {
+        return false;
+    }
+    let keymap_str = match std::ffi::CStr::from_ptr(keymap).to_str() {
+        Ok(s) => s,
+        Err(_) => return false,
+    };
+    let method = match input::CustomMethod::parse(keymap_str) {
+        Ok(m) => m,
+        Err(err) => {
+            if !error.is_null() {
+                if let Ok(s) = std::ffi::CString::new(err.to_string()) {
+                    *error = s.into_raw();
+                }
+            }
+            return false;
+        }
+    };
+
+    if let Some(e) = handle.as_mut() {
+        e.set_custom_method(method);
+        return true;
+    }
+    false
+}
+
 /// Enable or disable the engine.
 ///
 /// When disabled, `ime_key` returns action=0 (pass through).
 /// No-op if engine not initialized.
 #[no_mangle]
 pub extern "C" fn ime_enabled(enabled: bool) {
-    let mut guard = lock_engine();
-    if let Some(ref mut e) = *guard {
+    with_global(|h| unsafe { ime_engine_enabled(h, enabled) })
+}
+
+/// Handle version of [`ime_enabled`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_enabled(handle: *mut ImeEngine, enabled: bool) {
+    if let Some(e) = handle.as_mut() {
         e.set_enabled(enabled);
     }
 }
@@ -147,8 +446,17 @@ pub extern "C" fn ime_enabled(enabled: bool) {
 /// No-op if engine not initialized.
 #[no_mangle]
 pub extern "C" fn ime_skip_w_shortcut(skip: bool) {
-    let mut guard = lock_engine();
-    if let Some(ref mut e) = *guard {
+    with_global(|h| unsafe { ime_engine_skip_w_shortcut(h, skip) })
+}
+
+/// Handle version of [`ime_skip_w_shortcut`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_skip_w_shortcut(handle: *mut ImeEngine, skip: bool) {
+    if let Some(e) = handle.as_mut() {
         e.set_skip_w_shortcut(skip);
     }
 }
@@ -160,8 +468,17 @@ pub extern "C" fn ime_skip_w_shortcut(skip: bool) {
 /// No-op if engine not initialized.
 #[no_mangle]
 pub extern "C" fn ime_esc_restore(enabled: bool) {
-    let mut guard = lock_engine();
-    if let Some(ref mut e) = *guard {
+    with_global(|h| unsafe { ime_engine_esc_restore(h, enabled) })
+}
+
+/// Handle version of [`ime_esc_restore`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_esc_restore(handle: *mut ImeEngine, enabled: bool) {
+    if let Some(e) = handle.as_mut() {
         e.set_esc_restore(enabled);
     }
 }
@@ -174,8 +491,17 @@ pub extern "C" fn ime_esc_restore(enabled: bool) {
 /// No-op if engine not initialized.
 #[no_mangle]
 pub extern "C" fn ime_free_tone(enabled: bool) {
-    let mut guard = lock_engine();
-    if let Some(ref mut e) = *guard {
+    with_global(|h| unsafe { ime_engine_free_tone(h, enabled) })
+}
+
+/// Handle version of [`ime_free_tone`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_free_tone(handle: *mut ImeEngine, enabled: bool) {
+    if let Some(e) = handle.as_mut() {
         e.set_free_tone(enabled);
     }
 }
@@ -187,8 +513,17 @@ pub extern "C" fn ime_free_tone(enabled: bool) {
 /// No-op if engine not initialized.
 #[no_mangle]
 pub extern "C" fn ime_modern(modern: bool) {
-    let mut guard = lock_engine();
-    if let Some(ref mut e) = *guard {
+    with_global(|h| unsafe { ime_engine_modern(h, modern) })
+}
+
+/// Handle version of [`ime_modern`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_modern(handle: *mut ImeEngine, modern: bool) {
+    if let Some(e) = handle.as_mut() {
         e.set_modern_tone(modern);
     }
 }
@@ -201,173 +536,1434 @@ pub extern "C" fn ime_modern(modern: bool) {
 /// No-op if engine not initialized.
 #[no_mangle]
 pub extern "C" fn ime_english_auto_restore(enabled: bool) {
-    let mut guard = lock_engine();
-    if let Some(ref mut e) = *guard {
-        e.set_english_auto_restore(enabled);
-    }
+    with_global(|h| unsafe { ime_engine_english_auto_restore(h, enabled) })
 }
 
-/// Enable/disable auto-capitalize after sentence-ending punctuation.
+/// Handle version of [`ime_english_auto_restore`].
 ///
-/// When `enabled` is true, automatically capitalizes the first letter
-/// after sentence-ending punctuation (. ! ? Enter).
-/// When `enabled` is false (default), no auto-capitalize happens.
-/// No-op if engine not initialized.
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
 #[no_mangle]
-pub extern "C" fn ime_auto_capitalize(enabled: bool) {
-    let mut guard = lock_engine();
-    if let Some(ref mut e) = *guard {
-        e.set_auto_capitalize(enabled);
+pub unsafe extern "C" fn ime_engine_english_auto_restore(handle: *mut ImeEngine, enabled: bool) {
+    if let Some(e) = handle.as_mut() {
+        e.set_english_auto_restore(enabled);
     }
 }
 
-/// Clear the input buffer.
+/// Enable/disable dictionary-backed syllable validation.
 ///
-/// Call on word boundaries (space, punctuation).
-/// Preserves word history for backspace-after-space feature.
+/// When `enabled` is true, syllables must also appear in the bundled
+/// dictionary of attested Vietnamese syllables: fake-looking ones
+/// (e.g. "cón" from "cons") count as invalid for auto-restore.
+/// When `enabled` is false (default), only spelling rules apply.
 /// No-op if engine not initialized.
 #[no_mangle]
-pub extern "C" fn ime_clear() {
-    let mut guard = lock_engine();
-    if let Some(ref mut e) = *guard {
-        e.clear();
-    }
+pub extern "C" fn ime_dictionary_validation(enabled: bool) {
+    with_global(|h| unsafe { ime_engine_dictionary_validation(h, enabled) })
 }
 
-/// Clear everything including word history.
+/// Handle version of [`ime_dictionary_validation`].
 ///
-/// Call when cursor position changes (mouse click, arrow keys, focus change).
-/// This prevents accidental restore from stale history.
-/// No-op if engine not initialized.
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
 #[no_mangle]
-pub extern "C" fn ime_clear_all() {
-    let mut guard = lock_engine();
-    if let Some(ref mut e) = *guard {
-        e.clear_all();
+pub unsafe extern "C" fn ime_engine_dictionary_validation(handle: *mut ImeEngine, enabled: bool) {
+    if let Some(e) = handle.as_mut() {
+        e.set_dictionary_validation(enabled);
     }
 }
 
-/// Get the full composed buffer as UTF-32 codepoints.
+/// Load extra English words for auto-restore (see `data::english`).
 ///
-/// Used for "Select All + Replace" injection method where the entire
-/// buffer content is needed instead of incremental backspace + chars.
+/// Words are added to the bundled list until `ime_clear_english_words`.
 ///
 /// # Arguments
-/// * `out` - Pointer to output buffer for UTF-32 codepoints
-/// * `max_len` - Maximum number of codepoints to write
+/// * `words` - C string, whitespace-separated words (`#` starts a comment line)
 ///
 /// # Returns
-/// Number of codepoints written to `out`.
+/// Number of new words, 0 on invalid pointer/UTF-8 or engine not initialized.
 ///
 /// # Safety
-/// `out` must point to valid memory of at least `max_len * sizeof(u32)` bytes.
+/// Pointer must be a valid null-terminated UTF-8 string.
 #[no_mangle]
-pub unsafe extern "C" fn ime_get_buffer(out: *mut u32, max_len: i64) -> i64 {
-    if out.is_null() || max_len <= 0 {
+pub unsafe extern "C" fn ime_load_english_words(words: *const std::os::raw::c_char) -> u32 {
+    with_global(|h| unsafe { ime_engine_load_english_words(h, words) })
+}
+
+/// Handle version of [`ime_load_english_words`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_load_english_words(
+    handle: *mut ImeEngine,
+    words: *const std::os::raw::c_char,
+) -> u32 {
+    if words.is_null() {
         return 0;
     }
+    let words_str = match std::ffi::CStr::from_ptr(words).to_str() {
+        Ok(s) => s,
+        Err(_) => return 0,
+    };
 
-    let guard = lock_engine();
-    if let Some(ref e) = *guard {
-        let full = e.get_buffer_string();
-        let utf32: Vec<u32> = full.chars().map(|c| c as u32).collect();
-        let len = utf32.len().min(max_len as usize);
-        std::ptr::copy_nonoverlapping(utf32.as_ptr(), out, len);
-        len as i64
-    } else {
-        0
+    if let Some(e) = handle.as_mut() {
+        return e.english_words_mut().load(words_str) as u32;
     }
+    0
 }
 
-/// Free a result pointer returned by `ime_key`.
+/// Drop English words loaded with `ime_load_english_words`.
+///
+/// The bundled list stays. No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_clear_english_words() {
+    with_global(|h| unsafe { ime_engine_clear_english_words(h) })
+}
+
+/// Handle version of [`ime_clear_english_words`].
 ///
 /// # Safety
-/// * `r` must be a pointer returned by `ime_key`, or null
-/// * Must be called exactly once per non-null `ime_key` return
-/// * Do not use `r` after calling this function
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
 #[no_mangle]
-pub unsafe extern "C" fn ime_free(r: *mut Result) {
-    if !r.is_null() {
-        drop(Box::from_raw(r));
+pub unsafe extern "C" fn ime_engine_clear_english_words(handle: *mut ImeEngine) {
+    if let Some(e) = handle.as_mut() {
+        e.english_words_mut().clear();
     }
 }
 
-// ============================================================
-// Shortcut FFI
-// ============================================================
+/// Enable/disable auto-capitalize after sentence-ending punctuation.
+///
+/// When `enabled` is true, automatically capitalizes the first letter
+/// after sentence-ending punctuation (. ! ? Enter).
+/// When `enabled` is false (default), no auto-capitalize happens.
+/// No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_auto_capitalize(enabled: bool) {
+    with_global(|h| unsafe { ime_engine_auto_capitalize(h, enabled) })
+}
 
-/// Add a shortcut to the engine.
+/// Handle version of [`ime_auto_capitalize`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_auto_capitalize(handle: *mut ImeEngine, enabled: bool) {
+    if let Some(e) = handle.as_mut() {
+        e.set_auto_capitalize(enabled);
+    }
+}
+
+/// Set output encoding.
 ///
 /// # Arguments
-/// * `trigger` - C string for trigger (e.g., "vn")
-/// * `replacement` - C string for replacement (e.g., "Việt Nam")
+/// * `encoding` - 0=Unicode precomposed (NFC, default), 1=Unicode decomposed (NFD),
+///   2=TCVN3 (ABC), 3=VNI-Windows, 4=VISCII
+///
+/// All results (including shortcuts and restores) are emitted in this encoding,
+/// with backspace counts in units of that encoding (VNI-Windows: up to 2 per letter).
+/// Legacy charset bytes are sent as the Windows-1252 characters carrying them.
+/// No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_output_encoding(encoding: u8) {
+    with_global(|h| unsafe { ime_engine_output_encoding(h, encoding) })
+}
+
+/// Handle version of [`ime_output_encoding`].
 ///
 /// # Safety
-/// Both pointers must be valid null-terminated UTF-8 strings.
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
 #[no_mangle]
-pub unsafe extern "C" fn ime_add_shortcut(
-    trigger: *const std::os::raw::c_char,
-    replacement: *const std::os::raw::c_char,
-) {
-    if trigger.is_null() || replacement.is_null() {
-        return;
+pub unsafe extern "C" fn ime_engine_output_encoding(handle: *mut ImeEngine, encoding: u8) {
+    if let Some(e) = handle.as_mut() {
+        e.set_output_encoding(data::encoding::Encoding::from_id(encoding));
     }
+}
 
-    let trigger_str = match std::ffi::CStr::from_ptr(trigger).to_str() {
-        Ok(s) => s,
-        Err(_) => return,
-    };
-    let replacement_str = match std::ffi::CStr::from_ptr(replacement).to_str() {
-        Ok(s) => s,
-        Err(_) => return,
-    };
+/// Enable or disable suggestion mode.
+///
+/// When enabled, the engine keeps a ranked list of alternative spellings
+/// for the current word after each key (see `ime_get_candidate`).
+/// No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_suggestions(enabled: bool) {
+    with_global(|h| unsafe { ime_engine_suggestions(h, enabled) })
+}
+
+/// Handle version of [`ime_suggestions`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_suggestions(handle: *mut ImeEngine, enabled: bool) {
+    if let Some(e) = handle.as_mut() {
+        e.set_suggestions(enabled);
+    }
+}
+
+/// Number of suggestions for the current word.
+///
+/// Returns 0 if suggestion mode is off or engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_candidate_count() -> u32 {
+    with_global(|h| unsafe { ime_engine_candidate_count(h) })
+}
+
+/// Handle version of [`ime_candidate_count`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_candidate_count(handle: *mut ImeEngine) -> u32 {
+    handle.as_ref().map_or(0, |e| e.candidates().len() as u32)
+}
+
+/// Get suggestion `index` as UTF-32 codepoints (Unicode, for display).
+///
+/// # Arguments
+/// * `index` - Candidate index (0 = best)
+/// * `out` - Pointer to output buffer for UTF-32 codepoints
+/// * `max_len` - Maximum number of codepoints to write
+///
+/// # Returns
+/// Number of codepoints written to `out` (0 if no such candidate).
+///
+/// # Safety
+/// `out` must point to valid memory of at least `max_len * sizeof(u32)` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ime_get_candidate(index: u32, out: *mut u32, max_len: i64) -> i64 {
+    with_global(|h| unsafe { ime_engine_get_candidate(h, index, out, max_len) })
+}
+
+/// Handle version of [`ime_get_candidate`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// `out` must point to valid memory of at least `max_len * sizeof(u32)` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_get_candidate(
+    handle: *mut ImeEngine,
+    index: u32,
+    out: *mut u32,
+    max_len: i64,
+) -> i64 {
+    if out.is_null() || max_len <= 0 {
+        return 0;
+    }
+
+    let Some(word) = handle
+        .as_ref()
+        .and_then(|e| e.candidates().get(index as usize))
+    else {
+        return 0;
+    };
+    let utf32: Vec<u32> = word.chars().map(|c| c as u32).collect();
+    let len = utf32.len().min(max_len as usize);
+    std::ptr::copy_nonoverlapping(utf32.as_ptr(), out, len);
+    len as i64
+}
+
+/// Replace the current word with suggestion `index`.
+///
+/// # Returns
+/// * Pointer to `Result` with the backspace/chars replacement
+///   (action 0 if no such candidate); caller must free with `ime_free`
+/// * `null` if engine not initialized
+#[no_mangle]
+pub extern "C" fn ime_pick_candidate(index: u32) -> *mut Result {
+    with_global(|h| unsafe { ime_engine_pick_candidate(h, index) })
+}
+
+/// Handle version of [`ime_pick_candidate`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_pick_candidate(
+    handle: *mut ImeEngine,
+    index: u32,
+) -> *mut Result {
+    if let Some(e) = handle.as_mut() {
+        let r = e.pick_candidate(index as usize);
+        Box::into_raw(Box::new(r))
+    } else {
+        std::ptr::null_mut()
+    }
+}
+
+/// Clear the input buffer.
+///
+/// Call on word boundaries (space, punctuation).
+/// Preserves word history for backspace-after-space feature.
+/// No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_clear() {
+    with_global(|h| unsafe { ime_engine_clear(h) })
+}
+
+/// Handle version of [`ime_clear`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_clear(handle: *mut ImeEngine) {
+    if let Some(e) = handle.as_mut() {
+        e.clear();
+    }
+}
+
+/// Clear everything including word history.
+///
+/// Call when cursor position changes (mouse click, arrow keys, focus change).
+/// This prevents accidental restore from stale history.
+/// No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_clear_all() {
+    with_global(|h| unsafe { ime_engine_clear_all(h) })
+}
+
+/// Handle version of [`ime_clear_all`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_clear_all(handle: *mut ImeEngine) {
+    if let Some(e) = handle.as_mut() {
+        e.clear_all();
+    }
+}
+
+/// Get the full composed buffer as UTF-32 codepoints.
+///
+/// Used for "Select All + Replace" injection method where the entire
+/// buffer content is needed instead of incremental backspace + chars.
+///
+/// # Arguments
+/// * `out` - Pointer to output buffer for UTF-32 codepoints
+/// * `max_len` - Maximum number of codepoints to write
+///
+/// # Returns
+/// Number of codepoints written to `out`.
+///
+/// # Safety
+/// `out` must point to valid memory of at least `max_len * sizeof(u32)` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ime_get_buffer(out: *mut u32, max_len: i64) -> i64 {
+    with_global(|h| unsafe { ime_engine_get_buffer(h, out, max_len) })
+}
+
+/// Handle version of [`ime_get_buffer`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// `out` must point to valid memory of at least `max_len * sizeof(u32)` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_get_buffer(
+    handle: *mut ImeEngine,
+    out: *mut u32,
+    max_len: i64,
+) -> i64 {
+    if out.is_null() || max_len <= 0 {
+        return 0;
+    }
+
+    if let Some(e) = handle.as_mut() {
+        let full = e.get_buffer_string();
+        let utf32: Vec<u32> = full.chars().map(|c| c as u32).collect();
+        let len = utf32.len().min(max_len as usize);
+        std::ptr::copy_nonoverlapping(utf32.as_ptr(), out, len);
+        len as i64
+    } else {
+        0
+    }
+}
+
+/// Free a result pointer returned by `ime_key`.
+///
+/// # Safety
+/// * `r` must be a pointer returned by `ime_key`, or null
+/// * Must be called exactly once per non-null `ime_key` return
+/// * Do not use `r` after calling this function
+#[no_mangle]
+pub unsafe extern "C" fn ime_free(r: *mut Result) {
+    if !r.is_null() {
+        drop(Box::from_raw(r));
+    }
+}
+
+// ============================================================
+// Profile FFI
+// ============================================================
+
+/// Read an optional C string argument (null → "")
+///
+/// # Safety
+/// `s` must be a valid null-terminated string, or null.
+unsafe fn str_arg<'a>(s: *const std::os::raw::c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return Some("");
+    }
+    std::ffi::CStr::from_ptr(s).to_str().ok()
+}
+
+/// Tell the engine which app has focus.
+///
+/// Switches to the profile assigned to the app (settings + shortcuts) and
+/// restores the input method and on/off state the app had when last left.
+///
+/// # Arguments
+/// * `app_id` - App identifier (bundle id, executable name, ...)
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_set_context(app_id: *const std::os::raw::c_char) {
+    with_global(|h| unsafe { ime_engine_set_context(h, app_id) })
+}
+
+/// Handle version of [`ime_set_context`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_set_context(
+    handle: *mut ImeEngine,
+    app_id: *const std::os::raw::c_char,
+) {
+    let Some(app_id) = str_arg(app_id) else {
+        return;
+    };
+    if let Some(e) = handle.as_mut() {
+        e.set_context(app_id);
+    }
+}
+
+/// Create an empty profile (default settings, no shortcuts).
+///
+/// # Returns
+/// false if the name is empty or already used.
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_add_profile(name: *const std::os::raw::c_char) -> bool {
+    with_global(|h| unsafe { ime_engine_add_profile(h, name) })
+}
+
+/// Handle version of [`ime_add_profile`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_add_profile(
+    handle: *mut ImeEngine,
+    name: *const std::os::raw::c_char,
+) -> bool {
+    let Some(name) = str_arg(name) else {
+        return false;
+    };
+    match handle.as_mut() {
+        Some(e) => e.add_profile(name),
+        None => false,
+    }
+}
+
+/// Delete a profile (not "default" nor the active one).
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_remove_profile(name: *const std::os::raw::c_char) -> bool {
+    with_global(|h| unsafe { ime_engine_remove_profile(h, name) })
+}
+
+/// Handle version of [`ime_remove_profile`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_remove_profile(
+    handle: *mut ImeEngine,
+    name: *const std::os::raw::c_char,
+) -> bool {
+    let Some(name) = str_arg(name) else {
+        return false;
+    };
+    match handle.as_mut() {
+        Some(e) => e.remove_profile(name),
+        None => false,
+    }
+}
+
+/// Make a profile active. Setting and shortcut calls then edit it.
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_use_profile(name: *const std::os::raw::c_char) -> bool {
+    with_global(|h| unsafe { ime_engine_use_profile(h, name) })
+}
+
+/// Handle version of [`ime_use_profile`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_use_profile(
+    handle: *mut ImeEngine,
+    name: *const std::os::raw::c_char,
+) -> bool {
+    let Some(name) = str_arg(name) else {
+        return false;
+    };
+    match handle.as_mut() {
+        Some(e) => e.use_profile(name),
+        None => false,
+    }
+}
+
+/// Assign an app to a profile.
+///
+/// # Arguments
+/// * `app_id` - App identifier
+/// * `name` - Profile name, or null/empty to use the default profile
+///
+/// # Safety
+/// Pointers must be valid null-terminated UTF-8 strings (`name` may be null).
+#[no_mangle]
+pub unsafe extern "C" fn ime_assign_profile(
+    app_id: *const std::os::raw::c_char,
+    name: *const std::os::raw::c_char,
+) -> bool {
+    with_global(|h| unsafe { ime_engine_assign_profile(h, app_id, name) })
+}
+
+/// Handle version of [`ime_assign_profile`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// Pointers must be valid null-terminated UTF-8 strings (`name` may be null).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_assign_profile(
+    handle: *mut ImeEngine,
+    app_id: *const std::os::raw::c_char,
+    name: *const std::os::raw::c_char,
+) -> bool {
+    let (Some(app_id), Some(name)) = (str_arg(app_id), str_arg(name)) else {
+        return false;
+    };
+    match handle.as_mut() {
+        Some(e) => e.assign_profile(app_id, name),
+        None => false,
+    }
+}
+
+// ============================================================
+// Shortcut FFI
+// ============================================================
+
+/// Add a shortcut to the engine.
+///
+/// # Arguments
+/// * `trigger` - C string for trigger (e.g., "vn")
+/// * `replacement` - C string for replacement (e.g., "Việt Nam")
+///
+/// # Safety
+/// Both pointers must be valid null-terminated UTF-8 strings.
+#[no_mangle]
+pub unsafe extern "C" fn ime_add_shortcut(
+    trigger: *const std::os::raw::c_char,
+    replacement: *const std::os::raw::c_char,
+) {
+    with_global(|h| unsafe { ime_engine_add_shortcut(h, trigger, replacement) })
+}
+
+/// Handle version of [`ime_add_shortcut`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// Both pointers must be valid null-terminated UTF-8 strings.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_add_shortcut(
+    handle: *mut ImeEngine,
+    trigger: *const std::os::raw::c_char,
+    replacement: *const std::os::raw::c_char,
+) {
+    if trigger.is_null() || replacement.is_null() {
+        return;
+    }
+
+    let trigger_str = match std::ffi::CStr::from_ptr(trigger).to_str() {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+    let replacement_str = match std::ffi::CStr::from_ptr(replacement).to_str() {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+
+    if let Some(e) = handle.as_mut() {
+        // Auto-detect shortcut type:
+        // - If trigger contains only non-letter chars (like "->", "=>"), use immediate trigger
+        // - Otherwise use word boundary trigger (traditional abbreviations like "vn" → "Việt Nam")
+        let is_symbol_trigger = trigger_str.chars().all(|c| !c.is_alphabetic());
+        let shortcut = if is_symbol_trigger {
+            engine::shortcut::Shortcut::immediate(trigger_str, replacement_str)
+        } else {
+            engine::shortcut::Shortcut::new(trigger_str, replacement_str)
+        };
+        e.shortcuts_mut().add(shortcut);
+    }
+}
+
+/// Add a pattern shortcut (e.g. `(\d+)k` → `{1} nghìn`).
+///
+/// Patterns are checked after literal triggers, against the word as shown
+/// on screen. `{1}`-`{9}` in the replacement insert the capture groups.
+///
+/// # Arguments
+/// * `pattern` - Trigger pattern (see `engine::pattern` for the syntax)
+/// * `replacement` - Replacement text
+/// * `immediate` - Expand as soon as the pattern matches (else on space)
+///
+/// # Returns
+/// false if the pattern is invalid or the engine is not initialized.
+///
+/// # Safety
+/// Both pointers must be valid null-terminated UTF-8 strings.
+#[no_mangle]
+pub unsafe extern "C" fn ime_add_pattern_shortcut(
+    pattern: *const std::os::raw::c_char,
+    replacement: *const std::os::raw::c_char,
+    immediate: bool,
+) -> bool {
+    with_global(|h| unsafe { ime_engine_add_pattern_shortcut(h, pattern, replacement, immediate) })
+}
+
+/// Handle version of [`ime_add_pattern_shortcut`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// Both pointers must be valid null-terminated UTF-8 strings.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_add_pattern_shortcut(
+    handle: *mut ImeEngine,
+    pattern: *const std::os::raw::c_char,
+    replacement: *const std::os::raw::c_char,
+    immediate: bool,
+) -> bool {
+    if pattern.is_null() || replacement.is_null() {
+        return false;
+    }
+    let Ok(pattern_str) = std::ffi::CStr::from_ptr(pattern).to_str() else {
+        return false;
+    };
+    let Ok(replacement_str) = std::ffi::CStr::from_ptr(replacement).to_str() else {
+        return false;
+    };
+    if engine::pattern::Pattern::parse(pattern_str).is_err() {
+        return false;
+    }
+
+    let Some(e) = handle.as_mut() else {
+        return false;
+    };
+    let mut shortcut = engine::shortcut::Shortcut::pattern(pattern_str, replacement_str);
+    if immediate {
+        shortcut.condition = engine::shortcut::TriggerCondition::Immediate;
+    }
+    e.shortcuts_mut().add(shortcut);
+    true
+}
+
+/// Remove a shortcut from the engine.
+///
+/// # Arguments
+/// * `trigger` - C string for trigger to remove
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_remove_shortcut(trigger: *const std::os::raw::c_char) {
+    with_global(|h| unsafe { ime_engine_remove_shortcut(h, trigger) })
+}
+
+/// Handle version of [`ime_remove_shortcut`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_remove_shortcut(
+    handle: *mut ImeEngine,
+    trigger: *const std::os::raw::c_char,
+) {
+    if trigger.is_null() {
+        return;
+    }
+
+    let trigger_str = match std::ffi::CStr::from_ptr(trigger).to_str() {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+
+    if let Some(e) = handle.as_mut() {
+        e.shortcuts_mut().remove(trigger_str);
+    }
+}
+
+/// Clear all shortcuts from the engine.
+#[no_mangle]
+pub extern "C" fn ime_clear_shortcuts() {
+    with_global(|h| unsafe { ime_engine_clear_shortcuts(h) })
+}
+
+/// Handle version of [`ime_clear_shortcuts`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_clear_shortcuts(handle: *mut ImeEngine) {
+    if let Some(e) = handle.as_mut() {
+        e.shortcuts_mut().clear();
+    }
+}
+
+/// Configure shortcut undo.
+///
+/// When enabled, pressing `key` right after a shortcut expands puts back
+/// the typed trigger ("không " → "ko ") and the word is not expanded again.
+/// Off by default.
+///
+/// # Arguments
+/// * `enabled` - false turns undo off
+/// * `key` - Undo key (virtual keycode, usually Backspace)
+///
+/// No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_shortcut_undo(enabled: bool, key: u16) {
+    with_global(|h| unsafe { ime_engine_shortcut_undo(h, enabled, key) })
+}
+
+/// Handle version of [`ime_shortcut_undo`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_shortcut_undo(handle: *mut ImeEngine, enabled: bool, key: u16) {
+    if let Some(e) = handle.as_mut() {
+        e.set_shortcut_undo_key(enabled.then_some(key));
+    }
+}
+
+/// Import shortcuts from text (existing triggers are replaced).
+///
+/// # Arguments
+/// * `text` - C string with the shortcut file contents
+/// * `format` - 0=UniKey macro file, 1=JSON, 2=CSV
+/// * `errors` - If non-null, receives the rejected entries as
+///   "line N: message" lines (free with `ime_free_string`), or null if none
+///
+/// # Returns
+/// Number of shortcuts imported, or -1 on invalid input or unknown format.
+///
+/// # Safety
+/// * `text` must be a valid null-terminated UTF-8 string, or null
+/// * `errors` must be a valid pointer, or null
+#[no_mangle]
+pub unsafe extern "C" fn ime_import_shortcuts(
+    text: *const std::os::raw::c_char,
+    format: u8,
+    errors: *mut *mut std::os::raw::c_char,
+) -> i64 {
+    with_global(|h| unsafe { ime_engine_import_shortcuts(h, text, format, errors) })
+}
+
+/// Handle version of [`ime_import_shortcuts`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// * `text` must be a valid null-terminated UTF-8 string, or null
+/// * `errors` must be a valid pointer, or null
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_import_shortcuts(
+    handle: *mut ImeEngine,
+    text: *const std::os::raw::c_char,
+    format: u8,
+    errors: *mut *mut std::os::raw::c_char,
+) -> i64 {
+    if !errors.is_null() {
+        *errors = std::ptr::null_mut();
+    }
+    if text.is_null() {
+        return -1;
+    }
+    let text_str = match std::ffi::CStr::from_ptr(text).to_str() {
+        Ok(s) => s,
+        Err(_) => return -1,
+    };
+    let Some(format) = engine::shortcut_io::ShortcutFormat::from_id(format) else {
+        return -1;
+    };
+
+    let Some(e) = handle.as_mut() else {
+        return -1;
+    };
+    let (count, rejected) = e.shortcuts_mut().import(text_str, format);
+
+    if !errors.is_null() && !rejected.is_empty() {
+        let report: Vec<String> = rejected.iter().map(|err| err.to_string()).collect();
+        if let Ok(s) = std::ffi::CString::new(report.join("\n")) {
+            *errors = s.into_raw();
+        }
+    }
+    count as i64
+}
+
+/// Export all shortcuts as text.
+///
+/// # Arguments
+/// * `format` - 0=UniKey macro file, 1=JSON, 2=CSV
+/// * `errors` - If non-null, receives the entries the format cannot hold
+///   as "'trigger': message" lines (free with `ime_free_string`), or null
+///   if none. These entries are left out of the text.
+///
+/// # Returns
+/// Newly allocated UTF-8 C string (free with `ime_free_string`), or null
+/// if the engine is not initialized or the format is unknown.
+///
+/// # Safety
+/// `errors` must be a valid pointer, or null
+#[no_mangle]
+pub unsafe extern "C" fn ime_export_shortcuts(
+    format: u8,
+    errors: *mut *mut std::os::raw::c_char,
+) -> *mut std::os::raw::c_char {
+    with_global(|h| unsafe { ime_engine_export_shortcuts(h, format, errors) })
+}
+
+/// Handle version of [`ime_export_shortcuts`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// * `errors` must be a valid pointer, or null
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_export_shortcuts(
+    handle: *mut ImeEngine,
+    format: u8,
+    errors: *mut *mut std::os::raw::c_char,
+) -> *mut std::os::raw::c_char {
+    if !errors.is_null() {
+        *errors = std::ptr::null_mut();
+    }
+    let Some(format) = engine::shortcut_io::ShortcutFormat::from_id(format) else {
+        return std::ptr::null_mut();
+    };
+    let Some(e) = handle.as_mut() else {
+        return std::ptr::null_mut();
+    };
+    let (text, skipped) = e.shortcuts().export(format);
+
+    if !errors.is_null() && !skipped.is_empty() {
+        let report: Vec<String> = skipped.iter().map(|err| err.to_string()).collect();
+        if let Ok(s) = std::ffi::CString::new(report.join("\n")) {
+            *errors = s.into_raw();
+        }
+    }
+    match std::ffi::CString::new(text) {
+        Ok(s) => s.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Set the text used by `{clipboard}` in shortcut replacements.
+///
+/// Platforms call this when the clipboard changes (or before each key).
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string, or null (empty).
+#[no_mangle]
+pub unsafe extern "C" fn ime_set_clipboard(text: *const std::os::raw::c_char) {
+    with_global(|h| unsafe { ime_engine_set_clipboard(h, text) })
+}
+
+/// Handle version of [`ime_set_clipboard`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// Pointer must be a valid null-terminated UTF-8 string, or null (empty).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_set_clipboard(
+    handle: *mut ImeEngine,
+    text: *const std::os::raw::c_char,
+) {
+    let text_str = if text.is_null() {
+        ""
+    } else {
+        match std::ffi::CStr::from_ptr(text).to_str() {
+            Ok(s) => s,
+            Err(_) => return,
+        }
+    };
+
+    if let Some(e) = handle.as_mut() {
+        e.shortcuts_mut()
+            .templates_mut()
+            .set_clipboard(Box::new(text_str.to_string()));
+    }
+}
+
+/// Set the local time zone for `{date}`/`{time}` in shortcut replacements.
+///
+/// # Arguments
+/// * `minutes` - Offset from UTC in minutes (default 420 = UTC+7)
+#[no_mangle]
+pub extern "C" fn ime_set_utc_offset(minutes: i32) {
+    with_global(|h| unsafe { ime_engine_set_utc_offset(h, minutes) })
+}
+
+/// Handle version of [`ime_set_utc_offset`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_set_utc_offset(handle: *mut ImeEngine, minutes: i32) {
+    if let Some(e) = handle.as_mut() {
+        let clock = engine::template::SystemClock {
+            utc_offset_minutes: minutes,
+        };
+        e.shortcuts_mut().templates_mut().set_clock(Box::new(clock));
+    }
+}
+
+// ============================================================
+// Usage Statistics FFI
+// ============================================================
+
+/// Enable or disable phrase tracking (for suggested shortcuts).
+///
+/// When enabled, long words and phrases typed in full are counted.
+/// Shortcut firing counts are always kept. All data stays in the engine;
+/// use `ime_export_usage` to persist it.
+/// No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_phrase_tracking(enabled: bool) {
+    with_global(|h| unsafe { ime_engine_phrase_tracking(h, enabled) })
+}
+
+/// Handle version of [`ime_phrase_tracking`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_phrase_tracking(handle: *mut ImeEngine, enabled: bool) {
+    if let Some(e) = handle.as_mut() {
+        e.usage_mut().set_tracking(enabled);
+    }
+}
+
+/// Number of times a shortcut has fired.
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string, or null (returns 0).
+#[no_mangle]
+pub unsafe extern "C" fn ime_shortcut_usage(trigger: *const std::os::raw::c_char) -> u32 {
+    with_global(|h| unsafe { ime_engine_shortcut_usage(h, trigger) })
+}
+
+/// Handle version of [`ime_shortcut_usage`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// Pointer must be a valid null-terminated UTF-8 string, or null (returns 0).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_shortcut_usage(
+    handle: *mut ImeEngine,
+    trigger: *const std::os::raw::c_char,
+) -> u32 {
+    if trigger.is_null() {
+        return 0;
+    }
+    let Ok(trigger_str) = std::ffi::CStr::from_ptr(trigger).to_str() else {
+        return 0;
+    };
+    handle
+        .as_mut()
+        .as_ref()
+        .map_or(0, |e| e.usage().shortcut_count(trigger_str))
+}
+
+/// Suggested shortcuts for phrases typed often, most frequent first.
+///
+/// # Arguments
+/// * `limit` - Maximum number of suggestions
+///
+/// # Returns
+/// Newly allocated UTF-8 C string (free with `ime_free_string`) with one
+/// "phrase\ttrigger\tcount" line per suggestion (empty if none), or null
+/// if the engine is not initialized.
+#[no_mangle]
+pub extern "C" fn ime_shortcut_suggestions(limit: u32) -> *mut std::os::raw::c_char {
+    with_global(|h| unsafe { ime_engine_shortcut_suggestions(h, limit) })
+}
+
+/// Handle version of [`ime_shortcut_suggestions`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_shortcut_suggestions(
+    handle: *mut ImeEngine,
+    limit: u32,
+) -> *mut std::os::raw::c_char {
+    let Some(e) = handle.as_mut() else {
+        return std::ptr::null_mut();
+    };
+    let lines: Vec<String> = e
+        .shortcut_suggestions(limit as usize)
+        .iter()
+        .map(|s| format!("{}\t{}\t{}", s.phrase, s.trigger, s.count))
+        .collect();
+    match std::ffi::CString::new(lines.join("\n")) {
+        Ok(s) => s.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Export shortcut counts and tracked phrases as text.
+///
+/// # Returns
+/// Newly allocated UTF-8 C string (free with `ime_free_string`), or null
+/// if the engine is not initialized.
+#[no_mangle]
+pub extern "C" fn ime_export_usage() -> *mut std::os::raw::c_char {
+    with_global(|h| unsafe { ime_engine_export_usage(h) })
+}
+
+/// Handle version of [`ime_export_usage`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_export_usage(
+    handle: *mut ImeEngine,
+) -> *mut std::os::raw::c_char {
+    let Some(e) = handle.as_mut() else {
+        return std::ptr::null_mut();
+    };
+    match std::ffi::CString::new(e.usage().save()) {
+        Ok(s) => s.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Import usage data from `ime_export_usage` (counts are added).
+///
+/// # Returns
+/// Number of entries read, or -1 on invalid input.
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string, or null.
+#[no_mangle]
+pub unsafe extern "C" fn ime_import_usage(text: *const std::os::raw::c_char) -> i64 {
+    with_global(|h| unsafe { ime_engine_import_usage(h, text) })
+}
+
+/// Handle version of [`ime_import_usage`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// Pointer must be a valid null-terminated UTF-8 string, or null.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_import_usage(
+    handle: *mut ImeEngine,
+    text: *const std::os::raw::c_char,
+) -> i64 {
+    if text.is_null() {
+        return -1;
+    }
+    let Ok(text_str) = std::ffi::CStr::from_ptr(text).to_str() else {
+        return -1;
+    };
+    let Some(e) = handle.as_mut() else {
+        return -1;
+    };
+    e.usage_mut().load(text_str) as i64
+}
+
+/// Forget all shortcut counts and tracked phrases.
+#[no_mangle]
+pub extern "C" fn ime_clear_usage() {
+    with_global(|h| unsafe { ime_engine_clear_usage(h) })
+}
+
+/// Handle version of [`ime_clear_usage`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_clear_usage(handle: *mut ImeEngine) {
+    if let Some(e) = handle.as_mut() {
+        e.usage_mut().clear();
+    }
+}
+
+// ============================================================
+// Emoji FFI
+// ============================================================
+
+/// Enable or disable built-in emoji/symbol shortcodes.
+///
+/// When enabled, `:name:` expands on the closing colon using Vietnamese or
+/// English aliases (`:tim:` / `:heart:` → ❤️, `:mui-ten:` → →).
+/// No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_emoji(enabled: bool) {
+    with_global(|h| unsafe { ime_engine_emoji(h, enabled) })
+}
+
+/// Handle version of [`ime_emoji`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_emoji(handle: *mut ImeEngine, enabled: bool) {
+    if let Some(e) = handle.as_mut() {
+        e.set_emoji(enabled);
+    }
+}
 
-    let mut guard = lock_engine();
-    if let Some(ref mut e) = *guard {
-        // Auto-detect shortcut type:
-        // - If trigger contains only non-letter chars (like "->", "=>"), use immediate trigger
-        // - Otherwise use word boundary trigger (traditional abbreviations like "vn" → "Việt Nam")
-        let is_symbol_trigger = trigger_str.chars().all(|c| !c.is_alphabetic());
-        let shortcut = if is_symbol_trigger {
-            engine::shortcut::Shortcut::immediate(trigger_str, replacement_str)
-        } else {
-            engine::shortcut::Shortcut::new(trigger_str, replacement_str)
-        };
-        e.shortcuts_mut().add(shortcut);
+/// Emoji aliases starting with a prefix, for a completion list.
+///
+/// # Arguments
+/// * `prefix` - Alias prefix without the colon (case-insensitive, "" for all)
+/// * `limit` - Maximum number of entries
+///
+/// # Returns
+/// Newly allocated UTF-8 C string (free with `ime_free_string`) with one
+/// "alias\temoji" line per entry in alias order (empty if none), or null
+/// on invalid input.
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string, or null.
+#[no_mangle]
+pub unsafe extern "C" fn ime_emoji_search(
+    prefix: *const std::os::raw::c_char,
+    limit: u32,
+) -> *mut std::os::raw::c_char {
+    if prefix.is_null() {
+        return std::ptr::null_mut();
+    }
+    let Ok(prefix_str) = std::ffi::CStr::from_ptr(prefix).to_str() else {
+        return std::ptr::null_mut();
+    };
+    let lines: Vec<String> = data::emoji::search(prefix_str, limit as usize)
+        .iter()
+        .map(|(alias, symbol)| format!("{alias}\t{symbol}"))
+        .collect();
+    match std::ffi::CString::new(lines.join("\n")) {
+        Ok(s) => s.into_raw(),
+        Err(_) => std::ptr::null_mut(),
     }
 }
 
-/// Remove a shortcut from the engine.
+// ============================================================
+// Preedit FFI
+// ============================================================
+
+/// Enable or disable preedit mode.
+///
+/// In preedit mode the word being typed is not written into the app:
+/// results flagged `0x04` mean the composition string changed (fetch it
+/// with `ime_get_preedit`), and `chars` holds text to commit, after
+/// deleting `backspace` already committed chars. Keys with `flags & 0x01`
+/// clear must still reach the app (Enter, arrows, Backspace with nothing
+/// composed). Switching drops the current word.
+/// No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_preedit_mode(enabled: bool) {
+    with_global(|h| unsafe { ime_engine_preedit_mode(h, enabled) })
+}
+
+/// Handle version of [`ime_preedit_mode`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_preedit_mode(handle: *mut ImeEngine, enabled: bool) {
+    if let Some(e) = handle.as_mut() {
+        e.set_preedit_mode(enabled);
+    }
+}
+
+/// Get the preedit (composition string) as UTF-32 codepoints.
 ///
 /// # Arguments
-/// * `trigger` - C string for trigger to remove
+/// * `out` - Pointer to output buffer for UTF-32 codepoints
+/// * `max_len` - Maximum number of codepoints to write
+/// * `cursor` - Receives the cursor position in codepoints (may be null)
+///
+/// # Returns
+/// Number of codepoints written to `out` (0 when nothing is composed).
+///
+/// # Safety
+/// `out` must point to valid memory of at least `max_len * sizeof(u32)` bytes.
+/// `cursor` must be a valid pointer or null.
+#[no_mangle]
+pub unsafe extern "C" fn ime_get_preedit(out: *mut u32, max_len: i64, cursor: *mut u32) -> i64 {
+    with_global(|h| unsafe { ime_engine_get_preedit(h, out, max_len, cursor) })
+}
+
+/// Handle version of [`ime_get_preedit`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// `out` must point to valid memory of at least `max_len * sizeof(u32)` bytes.
+/// `cursor` must be a valid pointer or null.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_get_preedit(
+    handle: *mut ImeEngine,
+    out: *mut u32,
+    max_len: i64,
+    cursor: *mut u32,
+) -> i64 {
+    if out.is_null() || max_len <= 0 {
+        return 0;
+    }
+
+    if let Some(e) = handle.as_ref() {
+        let utf32: Vec<u32> = e.preedit().chars().map(|c| c as u32).collect();
+        let len = utf32.len().min(max_len as usize);
+        std::ptr::copy_nonoverlapping(utf32.as_ptr(), out, len);
+        if !cursor.is_null() {
+            *cursor = e.preedit_cursor().min(len) as u32;
+        }
+        len as i64
+    } else {
+        0
+    }
+}
+
+/// Commit the preedit as it is (focus lost, mouse click, mode switch).
+///
+/// # Returns
+/// * Pointer to `Result` struct (caller must free with `ime_free`) with the
+///   text to commit in `chars`; action 0 if nothing is composed
+/// * `null` if engine not initialized
+#[no_mangle]
+pub extern "C" fn ime_commit_preedit() -> *mut Result {
+    with_global(|h| unsafe { ime_engine_commit_preedit(h) })
+}
+
+/// Handle version of [`ime_commit_preedit`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_commit_preedit(handle: *mut ImeEngine) -> *mut Result {
+    if let Some(e) = handle.as_mut() {
+        Box::into_raw(Box::new(e.commit_preedit()))
+    } else {
+        std::ptr::null_mut()
+    }
+}
+
+// ============================================================
+// Auto-restore Exception FFI
+// ============================================================
+
+/// Add a word to an auto-restore exception list.
+///
+/// # Arguments
+/// * `list` - 0 = always restore to raw keystrokes (e.g., "docs"),
+///   1 = never restore (e.g., "tét" or its keystrokes "test")
+/// * `word` - C string for the word
 ///
 /// # Safety
 /// Pointer must be a valid null-terminated UTF-8 string.
 #[no_mangle]
-pub unsafe extern "C" fn ime_remove_shortcut(trigger: *const std::os::raw::c_char) {
-    if trigger.is_null() {
+pub unsafe extern "C" fn ime_add_restore_exception(list: u8, word: *const std::os::raw::c_char) {
+    with_global(|h| unsafe { ime_engine_add_restore_exception(h, list, word) })
+}
+
+/// Handle version of [`ime_add_restore_exception`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_add_restore_exception(
+    handle: *mut ImeEngine,
+    list: u8,
+    word: *const std::os::raw::c_char,
+) {
+    if word.is_null() {
         return;
     }
+    let Some(list) = engine::exceptions::ExceptionList::from_id(list) else {
+        return;
+    };
+    let word_str = match std::ffi::CStr::from_ptr(word).to_str() {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+
+    if let Some(e) = handle.as_mut() {
+        e.restore_exceptions_mut().add(list, word_str);
+    }
+}
 
-    let trigger_str = match std::ffi::CStr::from_ptr(trigger).to_str() {
+/// Remove a word from an auto-restore exception list.
+///
+/// # Arguments
+/// * `list` - 0 = always restore, 1 = never restore
+/// * `word` - C string for the word to remove
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_remove_restore_exception(list: u8, word: *const std::os::raw::c_char) {
+    with_global(|h| unsafe { ime_engine_remove_restore_exception(h, list, word) })
+}
+
+/// Handle version of [`ime_remove_restore_exception`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_remove_restore_exception(
+    handle: *mut ImeEngine,
+    list: u8,
+    word: *const std::os::raw::c_char,
+) {
+    if word.is_null() {
+        return;
+    }
+    let Some(list) = engine::exceptions::ExceptionList::from_id(list) else {
+        return;
+    };
+    let word_str = match std::ffi::CStr::from_ptr(word).to_str() {
         Ok(s) => s,
         Err(_) => return,
     };
 
-    let mut guard = lock_engine();
-    if let Some(ref mut e) = *guard {
-        e.shortcuts_mut().remove(trigger_str);
+    if let Some(e) = handle.as_mut() {
+        e.restore_exceptions_mut().remove(list, word_str);
     }
 }
 
-/// Clear all shortcuts from the engine.
+/// Clear an auto-restore exception list (0 = always restore, 1 = never restore).
 #[no_mangle]
-pub extern "C" fn ime_clear_shortcuts() {
-    let mut guard = lock_engine();
-    if let Some(ref mut e) = *guard {
-        e.shortcuts_mut().clear();
+pub extern "C" fn ime_clear_restore_exceptions(list: u8) {
+    with_global(|h| unsafe { ime_engine_clear_restore_exceptions(h, list) })
+}
+
+/// Handle version of [`ime_clear_restore_exceptions`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_clear_restore_exceptions(handle: *mut ImeEngine, list: u8) {
+    let Some(list) = engine::exceptions::ExceptionList::from_id(list) else {
+        return;
+    };
+    if let Some(e) = handle.as_mut() {
+        e.restore_exceptions_mut().clear(list);
+    }
+}
+
+/// Add many words to an auto-restore exception list.
+///
+/// # Arguments
+/// * `list` - 0 = always restore, 1 = never restore
+/// * `words` - C string, whitespace-separated words (`#` starts a comment line)
+///
+/// # Returns
+/// Number of new words, 0 on invalid list/pointer/UTF-8 or engine not initialized.
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_load_restore_exceptions(
+    list: u8,
+    words: *const std::os::raw::c_char,
+) -> u32 {
+    with_global(|h| unsafe { ime_engine_load_restore_exceptions(h, list, words) })
+}
+
+/// Handle version of [`ime_load_restore_exceptions`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_load_restore_exceptions(
+    handle: *mut ImeEngine,
+    list: u8,
+    words: *const std::os::raw::c_char,
+) -> u32 {
+    if words.is_null() {
+        return 0;
     }
+    let Some(list) = engine::exceptions::ExceptionList::from_id(list) else {
+        return 0;
+    };
+    let words_str = match std::ffi::CStr::from_ptr(words).to_str() {
+        Ok(s) => s,
+        Err(_) => return 0,
+    };
+
+    if let Some(e) = handle.as_mut() {
+        return e.restore_exceptions_mut().load(list, words_str) as u32;
+    }
+    0
 }
 
 // ============================================================
@@ -387,6 +1983,20 @@ pub extern "C" fn ime_clear_shortcuts() {
 /// Pointer must be a valid null-terminated UTF-8 string.
 #[no_mangle]
 pub unsafe extern "C" fn ime_restore_word(word: *const std::os::raw::c_char) {
+    with_global(|h| unsafe { ime_engine_restore_word(h, word) })
+}
+
+/// Handle version of [`ime_restore_word`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_restore_word(
+    handle: *mut ImeEngine,
+    word: *const std::os::raw::c_char,
+) {
     if word.is_null() {
         return;
     }
@@ -394,12 +2004,153 @@ pub unsafe extern "C" fn ime_restore_word(word: *const std::os::raw::c_char) {
         Ok(s) => s,
         Err(_) => return,
     };
-    let mut guard = lock_engine();
-    if let Some(ref mut e) = *guard {
+    if let Some(e) = handle.as_mut() {
         e.restore_word(word_str);
     }
 }
 
+/// Tell the engine the text around the cursor (after a click, arrow keys
+/// or focus change).
+///
+/// When the cursor is in or right after a Vietnamese word, the next keys edit
+/// that word in place: `Result.backspace` then reaches into it (e.g. "chao"
+/// and "f" → backspace 2, "ào"). Inside a word, `Result.delete_after` chars
+/// after the cursor are deleted too and `cursor_left` puts the cursor back
+/// (e.g. "Vi|et" and "j" → delete_after 2, "ệt", cursor_left 2). Otherwise a
+/// new word starts.
+///
+/// # Arguments
+/// * `text` - Text near the cursor (at least the current line), in the
+///   output encoding
+/// * `cursor` - Cursor position in `text`, in codepoints
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string, or null.
+#[no_mangle]
+pub unsafe extern "C" fn ime_set_surrounding_text(text: *const std::os::raw::c_char, cursor: u32) {
+    with_global(|h| unsafe { ime_engine_set_surrounding_text(h, text, cursor) })
+}
+
+/// Handle version of [`ime_set_surrounding_text`].
+///
+/// # Safety
+/// `handle` must come from `ime_engine_new` and not be freed yet, or be
+/// null (treated as an uninitialized engine).
+/// Pointer must be a valid null-terminated UTF-8 string, or null.
+#[no_mangle]
+pub unsafe extern "C" fn ime_engine_set_surrounding_text(
+    handle: *mut ImeEngine,
+    text: *const std::os::raw::c_char,
+    cursor: u32,
+) {
+    if text.is_null() {
+        return;
+    }
+    let Ok(text_str) = std::ffi::CStr::from_ptr(text).to_str() else {
+        return;
+    };
+    if let Some(e) = handle.as_mut() {
+        e.set_surrounding_text(text_str, cursor as usize);
+    }
+}
+
+// ============================================================
+// Converter FFI
+// ============================================================
+
+/// Convert text between Vietnamese charsets.
+///
+/// Does not touch the engine (usable without `ime_init`).
+///
+/// # Arguments
+/// * `text` - C string (UTF-8) to convert
+/// * `from`, `to` - Charset: 0=Unicode, 1=Unicode NFD, 2=TCVN3 (ABC),
+///   3=VNI-Windows, 4=VISCII, 5=VIQR, 6=Windows-1258
+///
+/// Legacy charset bytes are carried by their Windows-1252 characters.
+///
+/// # Returns
+/// Newly allocated UTF-8 C string (free with `ime_free_string`), or null
+/// on invalid input or unknown charset.
+///
+/// # Safety
+/// `text` must be a valid null-terminated string, or null.
+#[no_mangle]
+pub unsafe extern "C" fn ime_convert(
+    text: *const std::os::raw::c_char,
+    from: u8,
+    to: u8,
+) -> *mut std::os::raw::c_char {
+    if text.is_null() {
+        return std::ptr::null_mut();
+    }
+    let text_str = match std::ffi::CStr::from_ptr(text).to_str() {
+        Ok(s) => s,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    let (Some(from), Some(to)) = (
+        convert::Charset::from_id(from),
+        convert::Charset::from_id(to),
+    ) else {
+        return std::ptr::null_mut();
+    };
+    match std::ffi::CString::new(convert::convert(text_str, from, to)) {
+        Ok(s) => s.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Reconvert selected text between raw keystrokes and Vietnamese.
+///
+/// For text typed with the IME in the wrong state, e.g. bound to a hotkey
+/// on the selection. Does not touch the engine (usable without `ime_init`).
+///
+/// # Arguments
+/// * `text` - C string (UTF-8) to convert
+/// * `method` - Input method of the keystrokes: 0=Telex, 1=VNI, 2=VIQR
+/// * `direction` - 0=auto (keystrokes if the text has Vietnamese diacritics,
+///   Vietnamese otherwise), 1=keystrokes → Vietnamese, 2=Vietnamese → keystrokes
+///
+/// # Returns
+/// Newly allocated UTF-8 C string (Unicode NFC; free with `ime_free_string`),
+/// or null on invalid input or unknown direction.
+///
+/// # Safety
+/// `text` must be a valid null-terminated string, or null.
+#[no_mangle]
+pub unsafe extern "C" fn ime_reconvert(
+    text: *const std::os::raw::c_char,
+    method: u8,
+    direction: u8,
+) -> *mut std::os::raw::c_char {
+    if text.is_null() {
+        return std::ptr::null_mut();
+    }
+    let Ok(text_str) = std::ffi::CStr::from_ptr(text).to_str() else {
+        return std::ptr::null_mut();
+    };
+    let Some(direction) = reconvert::Direction::from_id(direction) else {
+        return std::ptr::null_mut();
+    };
+    match std::ffi::CString::new(reconvert::reconvert(text_str, method, direction)) {
+        Ok(s) => s.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Free a string returned by `ime_convert`, `ime_reconvert`,
+/// `ime_export_shortcuts` or `ime_import_shortcuts`.
+///
+/// # Safety
+/// * `s` must be a pointer returned by one of those functions, or null
+/// * Must be called exactly once per non-null return
+#[no_mangle]
+pub unsafe extern "C" fn ime_free_string(s: *mut std::os::raw::c_char) {
+    if !s.is_null() {
+        drop(std::ffi::CString::from_raw(s));
+    }
+}
+
 // ============================================================
 // Tests
 // ============================================================
@@ -408,28 +2159,145 @@ pub unsafe extern "C" fn ime_restore_word(word: *const std::os::raw::c_char) {
 mod tests {
     use super::*;
     use crate::data::keys;
+    use crate::engine::exceptions::ExceptionList;
     use serial_test::serial;
     use std::ffi::CString;
 
     #[test]
-    #[serial]
-    fn test_ffi_flow() {
-        ime_init();
-        ime_method(0); // Telex
+    #[serial]
+    fn test_ffi_flow() {
+        ime_init();
+        ime_method(0); // Telex
+
+        // Type 'a' + 's' -> á
+        let r1 = ime_key(keys::A, false, false);
+        assert!(!r1.is_null());
+        unsafe { ime_free(r1) };
+
+        let r2 = ime_key(keys::S, false, false);
+        assert!(!r2.is_null());
+        unsafe {
+            assert_eq!((*r2).chars[0], 'á' as u32);
+            ime_free(r2);
+        }
+
+        ime_clear();
+    }
+
+    #[test]
+    fn test_engine_handles() {
+        // Two instances with their own method, buffer and shortcuts
+        let telex = ime_engine_new();
+        let vni = ime_engine_new();
+        unsafe {
+            ime_engine_method(telex, 0);
+            ime_engine_method(vni, 1);
+            let trigger = CString::new("vn").unwrap();
+            let replacement = CString::new("Việt Nam").unwrap();
+            ime_engine_add_shortcut(vni, trigger.as_ptr(), replacement.as_ptr());
+
+            for (handle, key) in [(telex, keys::A), (vni, keys::A), (telex, keys::S)] {
+                ime_free(ime_engine_key(handle, key, false, false));
+            }
+            let r = ime_engine_key(vni, keys::N1, false, false);
+            assert_eq!((*r).chars[0], 'á' as u32);
+            ime_free(r);
+
+            let mut buf = [0u32; 8];
+            assert_eq!(ime_engine_get_buffer(telex, buf.as_mut_ptr(), 8), 1);
+            assert_eq!(buf[0], 'á' as u32);
+
+            ime_engine_clear(vni);
+            for key in [keys::V, keys::N] {
+                ime_free(ime_engine_key(vni, key, false, false));
+            }
+            let r = ime_engine_key(vni, keys::SPACE, false, false);
+            assert_eq!((*r).action, 1); // Send
+            ime_free(r);
+
+            // The shortcut belongs to the VNI instance only
+            ime_engine_clear(telex);
+            for key in [keys::V, keys::N] {
+                ime_free(ime_engine_key(telex, key, false, false));
+            }
+            let r = ime_engine_key(telex, keys::SPACE, false, false);
+            assert_eq!((*r).action, 0);
+            ime_free(r);
+
+            // Null handle behaves like an uninitialized engine
+            assert!(ime_engine_key(std::ptr::null_mut(), keys::A, false, false).is_null());
+            ime_engine_method(std::ptr::null_mut(), 1);
+
+            ime_engine_free(telex);
+            ime_engine_free(vni);
+            ime_engine_free(std::ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_key_code_and_char_ffi() {
+        let e = ime_engine_new();
+        unsafe {
+            // X11 keysyms "a", "s" → "á"
+            ime_free(ime_engine_key_code(e, 2, 'a' as u32, 0));
+            let r = ime_engine_key_code(e, 2, 's' as u32, 0);
+            assert_eq!((*r).chars[0], 'á' as u32);
+            ime_free(r);
 
-        // Type 'a' + 's' -> á
-        let r1 = ime_key(keys::A, false, false);
-        assert!(!r1.is_null());
-        unsafe { ime_free(r1) };
+            // Windows VK_BACK, then evdev KEY_A with Caps Lock
+            ime_free(ime_engine_key_code(e, 1, 0x08, 0));
+            ime_free(ime_engine_key_code(e, 3, 30, 0x02));
+            let r = ime_engine_key_char(e, 'f' as u32, 0);
+            assert_eq!((*r).chars[0], 'À' as u32);
+            ime_free(r);
 
-        let r2 = ime_key(keys::S, false, false);
-        assert!(!r2.is_null());
-        unsafe {
-            assert_eq!((*r2).chars[0], 'á' as u32);
-            ime_free(r2);
+            // Unknown source and invalid codepoint
+            for r in [
+                ime_engine_key_code(e, 9, 0, 0),
+                ime_engine_key_char(e, 0xD800, 0),
+            ] {
+                assert_eq!((*r).action, 0);
+                ime_free(r);
+            }
+            assert!(ime_engine_key_char(std::ptr::null_mut(), 'a' as u32, 0).is_null());
+            ime_engine_free(e);
         }
+    }
 
-        ime_clear();
+    #[test]
+    fn test_preedit_ffi() {
+        let e = ime_engine_new();
+        unsafe {
+            ime_engine_preedit_mode(e, true);
+            ime_free(ime_engine_key(e, keys::V, false, false));
+            ime_free(ime_engine_key(e, keys::I, false, false));
+            let r = ime_engine_key(e, keys::S, false, false);
+            assert_eq!(((*r).count, (*r).flags), (0, 0x05));
+            ime_free(r);
+
+            let mut out = [0u32; 8];
+            let mut cursor = 0u32;
+            assert_eq!(
+                ime_engine_get_preedit(e, out.as_mut_ptr(), 8, &mut cursor),
+                2
+            );
+            assert_eq!(out[..2], ['v' as u32, 'í' as u32]);
+            assert_eq!(cursor, 2);
+
+            let r = ime_engine_commit_preedit(e);
+            assert_eq!((*r).count, 2);
+            assert_eq!((*r).chars[1], 'í' as u32);
+            ime_free(r);
+            assert_eq!(
+                ime_engine_get_preedit(e, out.as_mut_ptr(), 8, std::ptr::null_mut()),
+                0
+            );
+
+            let r = ime_engine_commit_preedit(e);
+            assert_eq!((*r).action, 0);
+            ime_free(r);
+            ime_engine_free(e);
+        }
     }
 
     #[test]
@@ -646,6 +2514,448 @@ mod tests {
         ime_clear();
     }
 
+    #[test]
+    #[serial]
+    fn test_english_words_ffi() {
+        ime_init();
+        ime_clear_english_words();
+
+        let words = CString::new("lox\nserde lox").unwrap();
+        unsafe {
+            assert_eq!(ime_load_english_words(words.as_ptr()), 2);
+            assert_eq!(ime_load_english_words(std::ptr::null()), 0);
+        }
+        {
+            let guard = lock_engine();
+            let e = guard.as_ref().unwrap();
+            assert!(e.english_words().contains("serde"));
+        }
+
+        ime_clear_english_words();
+        let guard = lock_engine();
+        assert!(!guard.as_ref().unwrap().english_words().contains("serde"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_restore_exceptions_ffi() {
+        ime_init();
+        ime_method(0); // Telex
+        ime_english_auto_restore(true);
+        ime_clear_restore_exceptions(0);
+        ime_clear_restore_exceptions(1);
+
+        let always = CString::new("test").unwrap();
+        let never = CString::new("# keep\nmĩ bõ").unwrap();
+        unsafe {
+            ime_add_restore_exception(0, always.as_ptr());
+            ime_add_restore_exception(9, always.as_ptr()); // invalid list: ignored
+            assert_eq!(ime_load_restore_exceptions(1, never.as_ptr()), 2);
+        }
+        {
+            let guard = lock_engine();
+            let ex = guard.as_ref().unwrap().restore_exceptions();
+            assert!(ex.contains(ExceptionList::AlwaysRestore, "test"));
+            assert!(ex.contains(ExceptionList::NeverRestore, "bõ"));
+        }
+
+        unsafe {
+            ime_remove_restore_exception(0, always.as_ptr());
+        }
+        ime_clear_restore_exceptions(1);
+        {
+            let guard = lock_engine();
+            assert!(guard.as_ref().unwrap().restore_exceptions().is_empty());
+        }
+        ime_english_auto_restore(false);
+    }
+
+    #[test]
+    #[serial]
+    fn test_shortcut_import_export_ffi() {
+        ime_init();
+        ime_clear_shortcuts();
+
+        let csv = CString::new("trigger,replacement\nvn,Việt Nam\nbad\n").unwrap();
+        let mut errors: *mut std::os::raw::c_char = std::ptr::null_mut();
+        unsafe {
+            assert_eq!(ime_import_shortcuts(csv.as_ptr(), 2, &mut errors), 1);
+            assert!(!errors.is_null());
+            let report = std::ffi::CStr::from_ptr(errors).to_str().unwrap();
+            assert_eq!(report, "line 3: expected trigger,replacement");
+            ime_free_string(errors);
+
+            assert_eq!(ime_import_shortcuts(csv.as_ptr(), 9, &mut errors), -1);
+            assert_eq!(ime_import_shortcuts(std::ptr::null(), 0, &mut errors), -1);
+            assert!(errors.is_null());
+        }
+
+        unsafe {
+            let exported = ime_export_shortcuts(0, &mut errors);
+            assert!(!exported.is_null());
+            assert!(errors.is_null());
+            let text = std::ffi::CStr::from_ptr(exported).to_str().unwrap();
+            assert!(text.ends_with("\nvn:Việt Nam\n"));
+            ime_free_string(exported);
+
+            let trigger = CString::new("a:b").unwrap();
+            let replacement = CString::new("x").unwrap();
+            ime_add_shortcut(trigger.as_ptr(), replacement.as_ptr());
+            let exported = ime_export_shortcuts(0, &mut errors);
+            let text = std::ffi::CStr::from_ptr(exported).to_str().unwrap();
+            assert!(!text.contains("a:b"));
+            let report = std::ffi::CStr::from_ptr(errors).to_str().unwrap();
+            assert_eq!(report, "'a:b': trigger contains ':'");
+            ime_free_string(exported);
+            ime_free_string(errors);
+
+            assert!(ime_export_shortcuts(9, std::ptr::null_mut()).is_null());
+        }
+
+        ime_clear_shortcuts();
+    }
+
+    #[test]
+    #[serial]
+    fn test_shortcut_clipboard_ffi() {
+        ime_init();
+        ime_method(0); // Telex
+        ime_clear_shortcuts();
+
+        let trigger = CString::new("cb").unwrap();
+        let replacement = CString::new("<{clipboard}>").unwrap();
+        let clip = CString::new("Hà Nội").unwrap();
+        unsafe {
+            ime_add_shortcut(trigger.as_ptr(), replacement.as_ptr());
+            ime_set_clipboard(clip.as_ptr());
+        }
+
+        ime_key(keys::C, false, false);
+        ime_key(keys::B, false, false);
+        let r = ime_key(keys::SPACE, false, false);
+        unsafe {
+            let r = &*r;
+            let out: String = r.chars[..r.count as usize]
+                .iter()
+                .filter_map(|&c| char::from_u32(c))
+                .collect();
+            assert_eq!(out, "<Hà Nội> ");
+            ime_free(r as *const _ as *mut _);
+        }
+
+        unsafe { ime_set_clipboard(std::ptr::null()) };
+        ime_clear_shortcuts();
+        ime_clear();
+    }
+
+    #[test]
+    #[serial]
+    fn test_pattern_shortcut_ffi() {
+        ime_init();
+        ime_method(0); // Telex
+        ime_clear_shortcuts();
+
+        let pattern = CString::new("(\\d+)k").unwrap();
+        let replacement = CString::new("{1} nghìn").unwrap();
+        let bad = CString::new("(\\d+k").unwrap();
+        unsafe {
+            assert!(ime_add_pattern_shortcut(
+                pattern.as_ptr(),
+                replacement.as_ptr(),
+                false
+            ));
+            assert!(!ime_add_pattern_shortcut(
+                bad.as_ptr(),
+                replacement.as_ptr(),
+                false
+            ));
+        }
+
+        for key in [keys::N5, keys::N0, keys::K] {
+            let r = ime_key(key, false, false);
+            unsafe { ime_free(r) };
+        }
+        let r = ime_key(keys::SPACE, false, false);
+        unsafe {
+            let r = &*r;
+            let out: String = r.chars[..r.count as usize]
+                .iter()
+                .filter_map(|&c| char::from_u32(c))
+                .collect();
+            assert_eq!(r.backspace, 3);
+            assert_eq!(out, "50 nghìn ");
+            ime_free(r as *const _ as *mut _);
+        }
+
+        ime_clear_shortcuts();
+        ime_clear_all();
+    }
+
+    #[test]
+    #[serial]
+    fn test_usage_ffi() {
+        ime_init();
+        ime_method(0); // Telex
+        ime_clear_shortcuts();
+        ime_clear_usage();
+
+        let trigger = CString::new("vn").unwrap();
+        let replacement = CString::new("Việt Nam").unwrap();
+        unsafe { ime_add_shortcut(trigger.as_ptr(), replacement.as_ptr()) };
+        for key in [keys::V, keys::N, keys::SPACE] {
+            let r = ime_key(key, false, false);
+            unsafe { ime_free(r) };
+        }
+        assert_eq!(unsafe { ime_shortcut_usage(trigger.as_ptr()) }, 1);
+
+        let saved = CString::new("# gonhanh usage v1\np\t5\tHồ Chí Minh\n").unwrap();
+        assert_eq!(unsafe { ime_import_usage(saved.as_ptr()) }, 1);
+        unsafe {
+            let s = ime_shortcut_suggestions(10);
+            let text = std::ffi::CStr::from_ptr(s).to_str().unwrap().to_string();
+            ime_free_string(s);
+            assert_eq!(text, "Hồ Chí Minh\thcm\t5");
+
+            let s = ime_export_usage();
+            let text = std::ffi::CStr::from_ptr(s).to_str().unwrap().to_string();
+            ime_free_string(s);
+            assert!(text.contains("s\t1\tvn\n"));
+        }
+
+        ime_clear_usage();
+        assert_eq!(unsafe { ime_shortcut_usage(trigger.as_ptr()) }, 0);
+        ime_clear_shortcuts();
+        ime_clear();
+    }
+
+    #[test]
+    #[serial]
+    fn test_shortcut_undo_ffi() {
+        ime_init();
+        ime_method(0); // Telex
+        ime_clear_shortcuts();
+        ime_shortcut_undo(true, keys::DELETE);
+        let trigger = CString::new("ko").unwrap();
+        let replacement = CString::new("không").unwrap();
+        unsafe { ime_add_shortcut(trigger.as_ptr(), replacement.as_ptr()) };
+
+        let type_and_undo = || {
+            for key in [keys::K, keys::O, keys::SPACE] {
+                let r = ime_key(key, false, false);
+                unsafe { ime_free(r) };
+            }
+            let r = ime_key(keys::DELETE, false, false);
+            let out = unsafe {
+                let r = &*r;
+                (r.backspace, r.count, r.chars[0])
+            };
+            unsafe { ime_free(r) };
+            ime_clear();
+            out
+        };
+        // "không " (6) → "ko " (3)
+        assert_eq!(type_and_undo(), (6, 3, 'k' as u32));
+
+        ime_shortcut_undo(false, keys::DELETE);
+        assert_eq!(type_and_undo().1, 0);
+
+        ime_clear_shortcuts();
+    }
+
+    #[test]
+    #[serial]
+    fn test_emoji_ffi() {
+        ime_init();
+        ime_method(0); // Telex
+        ime_clear_shortcuts();
+        ime_emoji(true);
+
+        for (key, shift) in [
+            (keys::SEMICOLON, true),
+            (keys::T, false),
+            (keys::I, false),
+            (keys::M, false),
+        ] {
+            let r = ime_key_ext(key, false, false, shift);
+            unsafe { ime_free(r) };
+        }
+        let r = ime_key_ext(keys::SEMICOLON, false, false, true);
+        let (action, backspace, output) = unsafe {
+            let res = &*r;
+            let output: String = res.chars[..res.count as usize]
+                .iter()
+                .filter_map(|&c| char::from_u32(c))
+                .collect();
+            (res.action, res.backspace, output)
+        };
+        unsafe { ime_free(r) };
+        assert_eq!(action, 1); // Send
+        assert_eq!(backspace, 4); // ":tim"
+        assert_eq!(output, "❤️");
+
+        let prefix = CString::new("mui-ten-").unwrap();
+        unsafe {
+            let s = ime_emoji_search(prefix.as_ptr(), 2);
+            let text = std::ffi::CStr::from_ptr(s).to_str().unwrap().to_string();
+            ime_free_string(s);
+            assert_eq!(text, "mui-ten-len\t↑\nmui-ten-phai\t→");
+            assert!(ime_emoji_search(std::ptr::null(), 5).is_null());
+        }
+
+        ime_emoji(false);
+        ime_clear();
+    }
+
+    #[test]
+    #[serial]
+    fn test_profiles_ffi() {
+        ime_init();
+        ime_method(0); // Telex
+
+        let code = CString::new("code").unwrap();
+        let term = CString::new("com.apple.Terminal").unwrap();
+        let chat = CString::new("com.tinyspeck.slackmacgap").unwrap();
+        let trigger = CString::new("fn").unwrap();
+        let replacement = CString::new("function").unwrap();
+        unsafe {
+            assert!(ime_add_profile(code.as_ptr()));
+            assert!(!ime_add_profile(code.as_ptr()));
+            // Configure "code", then go back to default
+            assert!(ime_use_profile(code.as_ptr()));
+            ime_english_auto_restore(true);
+            ime_add_shortcut(trigger.as_ptr(), replacement.as_ptr());
+            let default = CString::new("default").unwrap();
+            assert!(ime_use_profile(default.as_ptr()));
+            assert!(ime_assign_profile(term.as_ptr(), code.as_ptr()));
+
+            ime_set_context(term.as_ptr());
+        }
+        {
+            let guard = lock_engine();
+            let e = guard.as_ref().unwrap();
+            assert_eq!(e.profile(), "code");
+            assert!(e.settings().english_auto_restore);
+            assert_eq!(e.shortcuts().len(), 1);
+        }
+
+        // Method is remembered per app
+        ime_method(1); // VNI in the terminal
+        unsafe { ime_set_context(chat.as_ptr()) };
+        {
+            let guard = lock_engine();
+            let e = guard.as_ref().unwrap();
+            assert_eq!(e.profile(), "default");
+            assert!(!e.settings().english_auto_restore);
+            assert!(e.shortcuts().is_empty());
+        }
+        ime_method(0);
+        unsafe { ime_set_context(term.as_ptr()) };
+        ime_key(keys::A, false, false);
+        let r = ime_key(keys::N1, false, false);
+        unsafe {
+            assert_eq!((*r).chars[0], 'á' as u32);
+            ime_free(r);
+        }
+
+        unsafe {
+            assert!(!ime_remove_profile(code.as_ptr())); // active
+            ime_set_context(chat.as_ptr());
+            assert!(ime_remove_profile(code.as_ptr()));
+            ime_set_context(term.as_ptr());
+        }
+        let guard = lock_engine();
+        assert_eq!(guard.as_ref().unwrap().profile(), "default");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_keymap_ffi() {
+        ime_init();
+        ime_method(0); // Telex
+
+        // Telex with [ for ơ: "o" + "[" → "ơ"
+        let keymap = CString::new("base telex\nhorn [ o").unwrap();
+        unsafe {
+            assert!(ime_load_keymap(keymap.as_ptr(), std::ptr::null_mut()));
+        }
+        let r = ime_key(keys::O, false, false);
+        unsafe { ime_free(r) };
+        let r = ime_key(keys::LBRACKET, false, false);
+        unsafe {
+            assert_eq!((*r).action, 1, "Should send replacement");
+            assert_eq!((*r).chars[0], 'ơ' as u32);
+            assert!((*r).key_consumed(), "Symbol should not be typed");
+            ime_free(r);
+        }
+
+        // Invalid keymap is rejected, with the failing line
+        let bad = CString::new("base telex\nmark s 9").unwrap();
+        unsafe {
+            let mut error: *mut std::os::raw::c_char = std::ptr::null_mut();
+            assert!(!ime_load_keymap(bad.as_ptr(), &mut error));
+            assert_eq!(
+                std::ffi::CStr::from_ptr(error).to_str().unwrap(),
+                "line 2: mark must be 1-5"
+            );
+            ime_free_string(error);
+            assert!(!ime_load_keymap(std::ptr::null(), &mut error));
+            assert!(error.is_null());
+        }
+
+        ime_method(0);
+        ime_clear();
+    }
+
+    #[test]
+    #[serial]
+    fn test_output_encoding_ffi() {
+        ime_init();
+        ime_method(0); // Telex
+        ime_output_encoding(1); // NFD
+
+        let r = ime_key(keys::A, false, false);
+        unsafe { ime_free(r) };
+        let r = ime_key(keys::S, false, false);
+        unsafe {
+            assert_eq!((*r).action, 1, "Should send replacement");
+            assert_eq!((*r).backspace, 1);
+            assert_eq!((*r).count, 2);
+            assert_eq!((*r).chars[0], 'a' as u32);
+            assert_eq!((*r).chars[1], 0x0301);
+            ime_free(r);
+        }
+
+        ime_output_encoding(0);
+        ime_clear();
+    }
+
+    #[test]
+    fn test_surrounding_text_ffi() {
+        let e = ime_engine_new();
+        let text = CString::new("Xin chao Viet Nam").unwrap();
+        unsafe {
+            // Cursor after "chao"
+            ime_engine_set_surrounding_text(e, text.as_ptr(), 8);
+            let r = ime_engine_key(e, keys::F, false, false);
+            assert_eq!((*r).backspace, 2);
+            assert_eq!(((*r).chars[0], (*r).chars[1]), ('à' as u32, 'o' as u32));
+            ime_free(r);
+
+            // Inside "Nam": the text after the cursor is rewritten too
+            ime_engine_set_surrounding_text(e, text.as_ptr(), 15);
+            let r = ime_engine_key(e, keys::S, false, false);
+            assert_eq!((*r).action, 1);
+            assert_eq!(((*r).backspace, (*r).delete_after), (0, 2));
+            assert_eq!(((*r).chars[0], (*r).chars[1]), ('á' as u32, 'm' as u32));
+            assert_eq!((*r).cursor_left, 2);
+            ime_free(r);
+
+            ime_engine_set_surrounding_text(e, std::ptr::null(), 0);
+            ime_set_surrounding_text(std::ptr::null(), 0);
+            ime_engine_free(e);
+        }
+    }
+
     #[test]
     #[serial]
     fn test_restore_word_ffi_null_safety() {
@@ -663,4 +2973,84 @@ mod tests {
 
         ime_clear();
     }
+
+    #[test]
+    fn test_convert_ffi() {
+        let text = CString::new("Tiếng Việt").unwrap();
+        unsafe {
+            let out = ime_convert(text.as_ptr(), 0, 3); // → VNI-Windows
+            assert!(!out.is_null());
+            assert_eq!(
+                std::ffi::CStr::from_ptr(out).to_str().unwrap(),
+                "Tieáng Vieät"
+            );
+            ime_free_string(out);
+
+            assert!(ime_convert(text.as_ptr(), 0, 99).is_null());
+            assert!(ime_convert(std::ptr::null(), 0, 1).is_null());
+            ime_free_string(std::ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_reconvert_ffi() {
+        let text = CString::new("Tieengs Vieetj").unwrap();
+        unsafe {
+            let out = ime_reconvert(text.as_ptr(), 0, 0);
+            assert_eq!(
+                std::ffi::CStr::from_ptr(out).to_str().unwrap(),
+                "Tiếng Việt"
+            );
+            let back = ime_reconvert(out, 1, 2);
+            assert_eq!(
+                std::ffi::CStr::from_ptr(back).to_str().unwrap(),
+                "Tie6ng1 Vie6t5"
+            );
+            ime_free_string(out);
+            ime_free_string(back);
+
+            assert!(ime_reconvert(text.as_ptr(), 0, 9).is_null());
+            assert!(ime_reconvert(std::ptr::null(), 0, 0).is_null());
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_candidates_ffi() {
+        ime_init();
+        ime_method(0); // Telex
+        ime_suggestions(true);
+
+        for key in [keys::D, keys::U, keys::O, keys::C] {
+            let r = ime_key(key, false, false);
+            unsafe { ime_free(r) };
+        }
+        assert!(ime_candidate_count() >= 3);
+
+        let mut buf = [0u32; 16];
+        let len = unsafe { ime_get_candidate(0, buf.as_mut_ptr(), 16) };
+        let best: String = buf[..len as usize]
+            .iter()
+            .filter_map(|&c| char::from_u32(c))
+            .collect();
+        assert_eq!(best, "được");
+
+        let r = ime_pick_candidate(0);
+        unsafe {
+            assert_eq!((*r).action, 1);
+            assert_eq!((*r).backspace, 4);
+            assert_eq!((*r).count, 4);
+            ime_free(r);
+        }
+
+        let r = ime_pick_candidate(99);
+        unsafe {
+            assert_eq!((*r).action, 0);
+            ime_free(r);
+        }
+
+        ime_suggestions(false);
+        assert_eq!(ime_candidate_count(), 0);
+        ime_clear();
+    }
 }
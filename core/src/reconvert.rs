@@ -0,0 +1,165 @@
+//! Reconvert Selected Text
+//!
+//! Fixes text typed with the IME in the wrong state (like UniKey's
+//! "convert clipboard"): raw keystrokes typed with the IME off become
+//! Vietnamese, and Vietnamese typed by mistake goes back to its keystrokes.
+//!
+//! Keystrokes → Vietnamese replays the text through a fresh `Engine`, so it
+//! matches typing it with default settings. Vietnamese → keystrokes types each
+//! letter's base key followed by its tone and stroke keys, and the mark key at
+//! the end of the word (Telex "việt" → "vieetj", VNI → "vie6t5").
+//!
+//! ```
+//! use gonhanh_core::reconvert::{reconvert, Direction};
+//! assert_eq!(reconvert("Vieetj Nam", 0, Direction::ToVietnamese), "Việt Nam");
+//! assert_eq!(reconvert("Việt Nam", 0, Direction::Auto), "Vieetj Nam");
+//! ```
+
+use crate::data::chars::{self, mark, tone};
+use crate::data::{encoding, keys};
+use crate::engine::{Action, Engine, Result};
+use crate::input::{self, Symbol, ToneType};
+
+/// Which way to convert
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    /// Vietnamese if the text has no diacritics, keystrokes otherwise
+    Auto,
+    /// Raw keystrokes → Vietnamese
+    ToVietnamese,
+    /// Vietnamese → raw keystrokes
+    ToKeys,
+}
+
+impl Direction {
+    /// FFI id: 0=auto, 1=to Vietnamese, 2=to keystrokes
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::Auto),
+            1 => Some(Self::ToVietnamese),
+            2 => Some(Self::ToKeys),
+            _ => None,
+        }
+    }
+}
+
+/// Convert text for an input method (0=Telex, 1=VNI, 2=VIQR)
+pub fn reconvert(text: &str, method: u8, direction: Direction) -> String {
+    let to_keys = match direction {
+        Direction::Auto => has_diacritics(text),
+        Direction::ToVietnamese => false,
+        Direction::ToKeys => true,
+    };
+    if to_keys {
+        to_keystrokes(text, method)
+    } else {
+        to_vietnamese(text, method)
+    }
+}
+
+/// Any Vietnamese letter with a tone, mark or stroke
+fn has_diacritics(text: &str) -> bool {
+    encoding::compose(text)
+        .chars()
+        .filter_map(chars::parse_char)
+        .any(|p| p.tone != tone::NONE || p.mark != mark::NONE || p.stroke)
+}
+
+/// Type `text` into a fresh engine and return what ends up on screen
+///
+/// Runs in preedit mode so the engine tracks the word itself: commits are
+/// appended, and keys it lets through (Enter, unmapped chars) are copied.
+pub fn to_vietnamese(text: &str, method: u8) -> String {
+    let mut e = Engine::new();
+    e.set_method(method);
+    e.set_preedit_mode(true);
+
+    let mut out: Vec<char> = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        if keys::from_char(c).is_some() {
+            let r = e.on_char(c, 0);
+            commit(&mut e, &r, &mut out);
+            if r.key_consumed() {
+                continue;
+            }
+        } else {
+            let r = e.commit_preedit();
+            commit(&mut e, &r, &mut out);
+        }
+        out.push(c);
+    }
+    let r = e.commit_preedit();
+    commit(&mut e, &r, &mut out);
+    out.into_iter().collect()
+}
+
+/// Apply a preedit-mode Result (and its further chunks) to the output
+fn commit(e: &mut Engine, r: &Result, out: &mut Vec<char>) {
+    if r.action != Action::Send as u8 {
+        return;
+    }
+    out.truncate(out.len().saturating_sub(r.backspace as usize));
+    out.extend(
+        r.chars[..r.count as usize]
+            .iter()
+            .filter_map(|&c| char::from_u32(c)),
+    );
+    if r.has_more() {
+        let next = e.next_output();
+        commit(e, &next, out);
+    }
+}
+
+/// Keystrokes that type `text` with an input method
+///
+/// Tone and stroke keys follow their letter, the mark key ends the word, as
+/// it is usually typed ("người" → "nguwowif"). Other chars are kept; with
+/// VIQR a modifier symbol right after a word is escaped (`Việt.` →
+/// `Vie^t.\.`).
+pub fn to_keystrokes(text: &str, method: u8) -> String {
+    let m = input::get(method);
+    let mut out = String::with_capacity(text.len() * 2);
+    // Mark key of the current word, typed when it ends
+    let mut word_mark: Option<char> = None;
+    let mut in_word = false;
+    for c in encoding::compose(text).chars() {
+        let Some(p) = chars::parse_char(c) else {
+            out.extend(word_mark.take());
+            let symbol = keys::from_char(c).is_some_and(|(k, shift)| m.symbol(k, shift).is_some());
+            if in_word && (symbol || c == '\\') {
+                out.push('\\');
+            }
+            out.push(c);
+            in_word = false;
+            continue;
+        };
+        let modifier =
+            |found: Option<char>| found.map(|k| if p.caps { k.to_ascii_uppercase() } else { k });
+        out.push(chars::to_char(p.key, p.caps, 0, 0).unwrap_or(c));
+        if p.tone != tone::NONE {
+            out.extend(modifier(find_key(|k, shift| {
+                let tone_of = |t: ToneType| t.value() == p.tone;
+                (!shift && m.tone(k).is_some_and(tone_of) && m.tone_targets(k).contains(&p.key))
+                    || (matches!(m.symbol(k, shift), Some(Symbol::Tone(t)) if tone_of(t))
+                        && m.symbol_targets(k, shift).contains(&p.key))
+            })));
+        }
+        if p.stroke {
+            out.extend(modifier(find_key(|k, shift| !shift && m.stroke(k))));
+        }
+        if p.mark != mark::NONE {
+            word_mark = modifier(find_key(|k, shift| {
+                (!shift && m.mark(k) == Some(p.mark))
+                    || m.symbol(k, shift) == Some(Symbol::Mark(p.mark))
+            }));
+        }
+        in_word = true;
+    }
+    out.extend(word_mark);
+    out
+}
+
+/// First key (with Shift state) that matches, as the char it types
+fn find_key(matches: impl Fn(u16, bool) -> bool) -> Option<char> {
+    (' '..='~').find(|&c| keys::from_char(c).is_some_and(|(k, shift)| matches(k, shift)))
+}
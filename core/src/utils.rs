@@ -84,7 +84,21 @@ pub fn key_to_char_ext(key: u16, caps: bool, shift: bool) -> Option<char> {
             _ => key_to_char(key, caps),
         };
     }
-    key_to_char(key, caps)
+    // Unshifted punctuation (VIQR modifiers like ' ` . are recorded in raw input)
+    match key {
+        keys::MINUS => Some('-'),
+        keys::EQUAL => Some('='),
+        keys::SEMICOLON => Some(';'),
+        keys::QUOTE => Some('\''),
+        keys::COMMA => Some(','),
+        keys::DOT => Some('.'),
+        keys::SLASH => Some('/'),
+        keys::BACKSLASH => Some('\\'),
+        keys::LBRACKET => Some('['),
+        keys::RBRACKET => Some(']'),
+        keys::BACKQUOTE => Some('`'),
+        _ => key_to_char(key, caps),
+    }
 }
 
 /// Collect vowels from buffer with phonological info
@@ -146,7 +160,7 @@ mod test_utils {
     //! Used by `#[cfg(test)]` modules throughout the crate.
 
     use crate::data::keys;
-    use crate::engine::{Action, Engine};
+    use crate::engine::{Action, Engine, Result};
 
     // ============================================================
     // KEY MAPPING
@@ -233,6 +247,20 @@ mod test_utils {
     // TYPING SIMULATION
     // ============================================================
 
+    /// Append the remaining chunks of a long output (FLAG_MORE_OUTPUT)
+    fn push_chunks(e: &mut Engine, r: &Result, screen: &mut String) {
+        let mut more = r.has_more();
+        while more {
+            let chunk = e.next_output();
+            screen.extend(
+                chunk.chars[..chunk.count as usize]
+                    .iter()
+                    .filter_map(|&c| char::from_u32(c)),
+            );
+            more = chunk.has_more();
+        }
+    }
+
     /// Simulate typing, returns screen output
     pub fn type_word(e: &mut Engine, input: &str) -> String {
         let mut screen = String::new();
@@ -276,6 +304,7 @@ mod test_utils {
                             screen.push(ch);
                         }
                     }
+                    push_chunks(e, &r, &mut screen);
                 } else {
                     // Normal backspace - just remove last char
                     screen.pop();
@@ -295,6 +324,7 @@ mod test_utils {
                             screen.push(ch);
                         }
                     }
+                    push_chunks(e, &r, &mut screen);
                 }
                 continue;
             }
@@ -312,6 +342,7 @@ mod test_utils {
                             screen.push(ch);
                         }
                     }
+                    push_chunks(e, &r, &mut screen);
                 } else {
                     // No shortcut - just add space
                     screen.push(' ');
@@ -329,6 +360,7 @@ mod test_utils {
                         screen.push(ch);
                     }
                 }
+                push_chunks(e, &r, &mut screen);
                 // For break keys (punctuation), add the character after auto-restore
                 // The restored text doesn't include the break character
                 // Use is_break_ext to handle shifted symbols like @, !, #, etc.
@@ -395,6 +427,16 @@ mod test_utils {
         }
     }
 
+    /// Run VIQR test cases
+    pub fn viqr(cases: &[(&str, &str)]) {
+        for (input, expected) in cases {
+            let mut e = Engine::new();
+            e.set_method(2);
+            let result = type_word(&mut e, input);
+            assert_eq!(result, *expected, "[VIQR] '{}' → '{}'", input, result);
+        }
+    }
+
     /// Run Telex test cases with traditional tone placement (hòa, thúy style)
     pub fn telex_traditional(cases: &[(&str, &str)]) {
         for (input, expected) in cases {
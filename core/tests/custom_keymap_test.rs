@@ -0,0 +1,113 @@
+//! Custom Keymap Tests - User-defined key mappings loaded at runtime
+
+mod common;
+use common::type_word;
+use gonhanh_core::engine::Engine;
+use gonhanh_core::input::CustomMethod;
+
+fn run(keymap: &str, cases: &[(&str, &str)]) {
+    for (input, expected) in cases {
+        let mut e = Engine::new();
+        e.set_custom_method(CustomMethod::parse(keymap).unwrap());
+        let result = type_word(&mut e, input);
+        assert_eq!(result, *expected, "[Custom] '{}' → '{}'", input, result);
+    }
+}
+
+// ============================================================
+// TELEX VARIANTS
+// ============================================================
+
+const TELEX_BRACKETS: &str = "base telex\nhorn [ o\nhorn ] u";
+
+const TELEX_BRACKETS_CASES: &[(&str, &str)] = &[
+    ("o[", "ơ"),
+    ("u]", "ư"),
+    ("mo[", "mơ"),
+    ("tu]s", "tứ"),
+    // Telex keys still work
+    ("vieetj", "việt"),
+    ("dduowngf", "đường"),
+    // Repeated symbol types it literally
+    ("o[[", "o["),
+    // No vowel to modify: the key types its vowel
+    ("t]", "tư"),
+    ("]", "ư"),
+    ("t[", "tơ"),
+    ("ng]o[i ", "ngươi "),
+    ("ng]o[if ", "người "),
+    ("nh]ngx", "những"),
+    // Unless the word can't take it
+    ("k] ", "k] "),
+];
+
+const TELEX_NO_Z: &str = "base telex\nunmap z\nremove 0";
+
+const TELEX_NO_Z_CASES: &[(&str, &str)] = &[("asz", "áz"), ("as0", "a")];
+
+const TELEX_NO_W: &str = "base telex\nunmap w\nhorn [ aou";
+
+const TELEX_NO_W_CASES: &[(&str, &str)] = &[
+    // w is a plain letter: no standalone ư
+    ("w", "w"),
+    ("nhw", "nhw"),
+    ("tuw", "tuw"),
+    ("ww", "ww"),
+    ("nhu[", "như"),
+    ("u[o[ngf", "ường"),
+];
+
+// ============================================================
+// VNI VARIANTS
+// ============================================================
+
+const VNI_CUSTOM_MARKS: &str = "base vni\nmark q 1\nstroke 9";
+
+const VNI_CUSTOM_MARKS_CASES: &[(&str, &str)] = &[("aq", "á"), ("a1", "á"), ("d9a", "đa")];
+
+// ============================================================
+// FROM SCRATCH
+// ============================================================
+
+const MINIMAL: &str = "# marks only\nmark ' 1\nmark ` 2\ncircumflex ^ aeo\nstroke -";
+
+const MINIMAL_CASES: &[(&str, &str)] = &[
+    ("a'", "á"),
+    ("e^`", "ề"),
+    ("d-", "đ"),
+    // No Telex keys
+    ("as", "as"),
+];
+
+#[test]
+fn custom_telex_brackets() {
+    run(TELEX_BRACKETS, TELEX_BRACKETS_CASES);
+}
+
+#[test]
+fn custom_telex_remove_key() {
+    run(TELEX_NO_Z, TELEX_NO_Z_CASES);
+}
+
+#[test]
+fn custom_telex_unmap_w() {
+    run(TELEX_NO_W, TELEX_NO_W_CASES);
+}
+
+#[test]
+fn custom_vni_marks() {
+    run(VNI_CUSTOM_MARKS, VNI_CUSTOM_MARKS_CASES);
+}
+
+#[test]
+fn custom_minimal() {
+    run(MINIMAL, MINIMAL_CASES);
+}
+
+#[test]
+fn set_method_drops_custom_keymap() {
+    let mut e = Engine::new();
+    e.set_custom_method(CustomMethod::parse(TELEX_BRACKETS).unwrap());
+    e.set_method(0);
+    assert_eq!(type_word(&mut e, "o["), "o[");
+}
@@ -0,0 +1,73 @@
+//! Dictionary Validation Tests
+//!
+//! With dictionary validation on, syllables that pass the spelling rules but
+//! are not attested Vietnamese ("tón" from "tons") are restored as English.
+
+use gonhanh_core::engine::Engine;
+use gonhanh_core::utils::type_word;
+
+fn engine(dictionary: bool) -> Engine {
+    let mut e = Engine::new();
+    e.set_english_auto_restore(true);
+    e.set_dictionary_validation(dictionary);
+    e
+}
+
+fn run(dictionary: bool, cases: &[(&str, &str)]) {
+    for (input, expected) in cases {
+        let mut e = engine(dictionary);
+        let result = type_word(&mut e, input);
+        assert_eq!(
+            result, *expected,
+            "[dictionary={}] '{}' → '{}'",
+            dictionary, input, result
+        );
+    }
+}
+
+#[test]
+fn unattested_syllables_restore() {
+    run(
+        true,
+        &[
+            ("tons ", "tons "),
+            ("cons ", "cons "),
+            ("dons ", "dons "),
+            ("maps ", "maps "),
+        ],
+    );
+}
+
+#[test]
+fn rules_only_keep_plausible_syllables() {
+    run(
+        false,
+        &[("tons ", "tón "), ("cons ", "cón "), ("dons ", "dón ")],
+    );
+}
+
+#[test]
+fn attested_syllables_stay() {
+    run(
+        true,
+        &[
+            ("tieengs ", "tiếng "),
+            ("nguwowif ", "người "),
+            ("ddwowcj ", "được "),
+            ("hoaf ", "hoà "),
+            ("mons ", "món "),
+            ("tws ", "tứ "),
+            ("choangf ", "choàng "),
+            ("tops ", "tóp "),
+            ("tuyps ", "tuýp "),
+            ("nhoofm ", "nhồm "),
+        ],
+    );
+}
+
+#[test]
+fn default_is_off() {
+    let mut e = Engine::new();
+    e.set_english_auto_restore(true);
+    assert_eq!(type_word(&mut e, "tons "), "tón ");
+}
@@ -0,0 +1,61 @@
+//! Emoji Tests
+//!
+//! Built-in `:name:` shortcodes expanding on the closing colon.
+
+use gonhanh_core::engine::Engine;
+use gonhanh_core::utils::type_word;
+
+fn engine(method: u8) -> Engine {
+    let mut e = Engine::new();
+    e.set_method(method);
+    e.set_emoji(true);
+    e
+}
+
+#[test]
+fn vietnamese_and_english_aliases() {
+    let mut e = engine(0);
+    assert_eq!(type_word(&mut e, ":tim: "), "❤️ ");
+    // Telex would turn "heart" into "hẻat"; the name is read from the keys
+    assert_eq!(type_word(&mut e, ":heart: "), "❤️ ");
+    assert_eq!(type_word(&mut e, ":mui-ten: "), "→ ");
+    assert_eq!(type_word(&mut e, ":HEART:"), "❤️");
+}
+
+#[test]
+fn vni_aliases() {
+    let mut e = engine(1);
+    assert_eq!(type_word(&mut e, ":cam-on: "), "🙏 ");
+    assert_eq!(type_word(&mut e, ":100:"), "💯");
+}
+
+#[test]
+fn inside_text() {
+    let mut e = engine(0);
+    assert_eq!(type_word(&mut e, "yeeu :tim: nhieeuf"), "yêu ❤️ nhiều");
+    assert_eq!(type_word(&mut e, "ok:like:"), "ok:like:");
+    assert_eq!(type_word(&mut e, "(:tim:)"), "(❤️)");
+}
+
+#[test]
+fn unknown_or_disabled() {
+    let mut e = engine(0);
+    assert_eq!(type_word(&mut e, ":khongco: "), ":khongco: ");
+    assert_eq!(type_word(&mut e, "10:30:"), "10:30:");
+
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, ":tim: "), ":tim: ");
+
+    // Engine off: emoji on but no expansion
+    let mut e = engine(0);
+    e.set_enabled(false);
+    assert_eq!(type_word(&mut e, ":tim: "), ":tim: ");
+}
+
+#[test]
+fn counted_as_shortcut() {
+    let mut e = engine(0);
+    type_word(&mut e, ":tim: :heart: ");
+    assert_eq!(e.usage().shortcut_count(":tim:"), 1);
+    assert_eq!(e.usage().shortcut_count(":heart:"), 1);
+}
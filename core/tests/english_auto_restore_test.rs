@@ -29,6 +29,8 @@
 
 mod common;
 use common::telex_auto_restore;
+use gonhanh_core::engine::Engine;
+use gonhanh_core::utils::type_word;
 
 // =============================================================================
 // PATTERN 1: MODIFIER FOLLOWED BY CONSONANT
@@ -420,22 +422,89 @@ fn air_stays_vietnamese() {
 }
 
 // =============================================================================
-// WORDS THAT CANNOT BE AUTO-DETECTED (DOCUMENTATION)
-// These produce structurally valid Vietnamese
+// ENGLISH WORD LIST
+// Valid Vietnamese structures restored because the raw input is a known word
 // =============================================================================
 
-/// Documents words that CANNOT be auto-detected without a dictionary.
-/// These produce structurally valid Vietnamese and are intentionally NOT restored.
+#[test]
+fn english_word_list_restores() {
+    telex_auto_restore(&[
+        ("days ", "days "), // d + a + y + s(sắc) → dáy
+        ("Days ", "Days "),
+        ("docs ", "docs "), // dóc: attested, but not a common syllable
+        ("mix ", "mix "),   // mĩ
+        ("box ", "box "),   // bõ
+        ("has ", "has "),   // há
+        ("list ", "list "), // lít
+        ("must ", "must "), // mút
+        ("chair ", "chair "),
+        ("his ", "his "),
+        ("of ", "of "),
+        ("us ", "us "),
+        ("seem ", "seem "), // s + ee(ê) + m → sêm
+        ("soon ", "soon "), // s + oo(ô) + n → sôn
+        ("down ", "down "), // d + ow(ơ) + n → dơn
+        ("fix ", "fix "),   // F is invalid initial, never transformed
+    ]);
+}
+
+#[test]
+fn english_word_list_keeps_common_syllables() {
+    // Listed English words whose result is a common Vietnamese syllable
+    telex_auto_restore(&[
+        ("too ", "tô "),
+        ("rust ", "rút "),
+        ("last ", "lát "),
+        ("char ", "chả "),
+        ("beer ", "bể "),
+        ("its ", "ít "),
+        ("car ", "cả "),
+        ("cars ", "cá "),
+        ("hair ", "hải "),
+        ("there ", "thể "),
+        ("these ", "thế "),
+    ]);
+}
+
+#[test]
+fn english_word_list_loaded() {
+    let mut e = Engine::new();
+    e.set_english_auto_restore(true);
+    assert_eq!(type_word(&mut e, "sox "), "sõ ");
+
+    e.english_words_mut().load("# extra words\nsox");
+    assert_eq!(type_word(&mut e, "sox "), "sox ");
+}
+
+#[test]
+fn user_exception_lists() {
+    use gonhanh_core::engine::exceptions::ExceptionList::*;
+
+    let mut e = Engine::new();
+    e.set_english_auto_restore(true);
+    e.restore_exceptions_mut().add(AlwaysRestore, "test");
+    e.restore_exceptions_mut().load(NeverRestore, "dáy\ncore");
+    assert_eq!(type_word(&mut e, "test "), "test "); // tét otherwise
+    assert_eq!(type_word(&mut e, "days "), "dáy "); // word list otherwise
+    assert_eq!(type_word(&mut e, "core "), "coẻ "); // matched by keystrokes
+    assert_eq!(type_word(&mut e, "seem "), "seem ");
+
+    // Lists only apply while auto-restore is on
+    e.set_english_auto_restore(false);
+    assert_eq!(type_word(&mut e, "test "), "tét ");
+}
+
+/// Documents words that stay transformed: the result is a common Vietnamese
+/// syllable, or the English word is not in the word list.
 /// Users should use raw mode prefix (\word) or Esc to get English spelling.
 #[test]
 fn words_that_stay_transformed() {
-    // These produce valid Vietnamese structures - NOT auto-restored by design
     telex_auto_restore(&[
-        ("mix ", "mĩ "), // m + i + x(ngã) → mĩ (valid Vietnamese: "beautiful" in Sino-Vietnamese)
-        ("box ", "bõ "), // b + o + x(ngã) → bõ (valid Vietnamese structure)
-        ("six ", "sĩ "), // s + i + x(ngã) → sĩ (valid Vietnamese: "scholar/official")
-        ("tax ", "tã "), // t + a + x(ngã) → tã (valid Vietnamese: "diaper")
-        ("max ", "mã "), // m + a + x(ngã) → mã (valid Vietnamese: "horse/code")
+        ("six ", "sĩ "),  // s + i + x(ngã) → sĩ (listed, common: "scholar/official")
+        ("sex ", "sẽ "),  // s + e + x(ngã) → sẽ (listed, common: "will")
+        ("cos ", "có "),  // c + o + s(sắc) → có (valid Vietnamese: "have")
+        ("tax ", "tã "),  // t + a + x(ngã) → tã (valid Vietnamese: "diaper")
+        ("max ", "mã "),  // m + a + x(ngã) → mã (valid Vietnamese: "horse/code")
         ("fox ", "fox "), // F is invalid initial → auto-restores to "fox"
     ]);
 }
@@ -219,11 +219,11 @@ fn vietnamese_preserved_on() {
 // =============================================================================
 
 #[test]
-fn edge_case_mix_stays_vietnamese() {
-    // "mix" → "mĩ" is valid Vietnamese, should NOT restore even when ON
+fn edge_case_mix_restores_when_on() {
+    // "mix" → "mĩ" is valid but rare Vietnamese, "mix" is in the English word list
     let mut e = engine_on();
     let result = type_word(&mut e, "mix ");
-    assert_eq!(result, "mĩ ", "ON: 'mix' stays as 'mĩ' (valid Vietnamese)");
+    assert_eq!(result, "mix ", "ON: 'mix' restored (English word list)");
 }
 
 #[test]
@@ -754,7 +754,7 @@ fn telex_shift_not_affected() {
 // SHORTCUT TESTS
 // ============================================================
 
-use gonhanh_core::engine::shortcut::Shortcut;
+use gonhanh_core::engine::shortcut::{Shortcut, TriggerCondition};
 
 #[test]
 fn shortcut_triggers_on_space() {
@@ -816,6 +816,167 @@ fn shortcut_tphcm_raw_result() {
     assert_eq!(r.count, 22, "count should be 22 chars");
 }
 
+#[test]
+fn shortcut_long_replacement_in_chunks() {
+    let mut e = Engine::new();
+    let signature = "Trân trọng,\nNguyễn Văn An\nPhòng Kỹ thuật - Công ty Cổ phần Gõ Nhanh\nĐiện thoại: 0123 456 789";
+    assert!(signature.chars().count() > 64);
+    e.shortcuts_mut().add(Shortcut::new("ky", signature));
+
+    e.on_key(keys::K, false, false);
+    e.on_key(keys::Y, false, false);
+    let r = e.on_key(keys::SPACE, false, false);
+    assert_eq!(r.backspace, 2);
+    assert_eq!(r.count, 64);
+    assert!(r.has_more(), "long output should continue in chunks");
+
+    let mut output: String = r.chars.iter().filter_map(|&c| char::from_u32(c)).collect();
+    let chunk = e.next_output();
+    assert_eq!(chunk.backspace, 0);
+    assert!(!chunk.has_more());
+    output.extend(
+        chunk.chars[..chunk.count as usize]
+            .iter()
+            .filter_map(|&c| char::from_u32(c)),
+    );
+    assert_eq!(output, format!("{} ", signature));
+    assert_eq!(e.next_output().action, Action::None as u8);
+
+    // Typing simulator follows the chunks
+    assert_eq!(type_word(&mut e, "ky "), format!("{} ", signature));
+}
+
+#[test]
+fn shortcut_multi_line_with_cursor() {
+    let mut e = Engine::new();
+    e.shortcuts_mut().add(Shortcut::new(
+        "kg",
+        "Kính gửi anh/chị {cursor},\n\nTrân trọng,\nGõ Nhanh",
+    ));
+
+    e.on_key(keys::K, false, false);
+    e.on_key(keys::G, false, false);
+    let r = e.on_key(keys::SPACE, false, false);
+    let output: String = r.chars[..r.count as usize]
+        .iter()
+        .filter_map(|&c| char::from_u32(c))
+        .collect();
+    assert_eq!(output, "Kính gửi anh/chị ,\n\nTrân trọng,\nGõ Nhanh ");
+    // Cursor lands right after "anh/chị "
+    let tail = ",\n\nTrân trọng,\nGõ Nhanh ";
+    assert_eq!(r.cursor_left as usize, tail.chars().count());
+}
+
+#[test]
+fn shortcut_cursor_on_last_chunk() {
+    let mut e = Engine::new();
+    let body = "x".repeat(100);
+    e.shortcuts_mut()
+        .add(Shortcut::immediate("->", &format!("{}{{cursor}}!", body)));
+
+    e.on_key_ext(keys::MINUS, false, false, false);
+    let r = e.on_key_ext(keys::DOT, false, false, true);
+    assert!(r.has_more());
+    assert_eq!(r.cursor_left, 0);
+    let last = e.next_output();
+    assert!(!last.has_more());
+    assert_eq!(last.cursor_left, 1);
+}
+
+#[test]
+fn pattern_shortcut_number_suffix() {
+    let mut e = Engine::new();
+    e.shortcuts_mut()
+        .add(Shortcut::pattern("(\\d+)k", "{1} nghìn"));
+    assert_eq!(type_word(&mut e, "giá 100k nhé "), "giá 100 nghìn nhé ");
+    assert_eq!(type_word(&mut e, "(25k "), "(25 nghìn ");
+    // Only from a word start, and only on space
+    assert_eq!(type_word(&mut e, "a100k "), "a100k ");
+    assert_eq!(type_word(&mut e, "100k."), "100k.");
+}
+
+#[test]
+fn pattern_shortcut_disabled_engine() {
+    let mut e = Engine::new();
+    e.shortcuts_mut()
+        .add(Shortcut::pattern("(\\d+)k", "{1} nghìn"));
+    e.set_enabled(false);
+    assert_eq!(type_word(&mut e, "100k "), "100k ");
+}
+
+#[test]
+fn pattern_shortcut_immediate() {
+    let mut e = Engine::new();
+    let mut emoji = Shortcut::pattern(":smile:", "😄");
+    emoji.condition = TriggerCondition::Immediate;
+    e.shortcuts_mut().add(emoji);
+    assert_eq!(type_word(&mut e, "ddax :smile: vui"), "đã 😄 vui");
+}
+
+#[test]
+fn literal_shortcut_before_pattern() {
+    let mut e = Engine::new();
+    e.shortcuts_mut().add(Shortcut::new("hn", "Hà Nội"));
+    e.shortcuts_mut().add(Shortcut::pattern("h\\w", "x"));
+    assert_eq!(type_word(&mut e, "hn hk "), "Hà Nội x ");
+}
+
+/// Engine with Backspace undoing the last expansion
+fn undo_engine(shortcut: Shortcut) -> Engine {
+    let mut e = Engine::new();
+    e.set_shortcut_undo_key(Some(keys::DELETE));
+    e.shortcuts_mut().add(shortcut);
+    e
+}
+
+#[test]
+fn shortcut_undo_with_backspace() {
+    let mut e = undo_engine(Shortcut::new("ko", "không"));
+    assert_eq!(type_word(&mut e, "ko <"), "ko ");
+    assert_eq!(e.usage().shortcut_count("ko"), 0);
+
+    // The word is not expanded again after editing back into it
+    let mut e = undo_engine(Shortcut::new("ko", "không"));
+    assert_eq!(type_word(&mut e, "ko << "), "ko ");
+    assert_eq!(type_word(&mut e, "ko "), "không ");
+
+    // Only right after the expansion; later backspaces delete as usual
+    let mut e = undo_engine(Shortcut::new("ko", "không"));
+    assert_eq!(type_word(&mut e, "ko a<<"), "không");
+}
+
+#[test]
+fn shortcut_undo_immediate_and_pattern() {
+    let mut e = undo_engine(Shortcut::immediate("->", "→"));
+    assert_eq!(type_word(&mut e, "-><"), "->");
+
+    let mut e = undo_engine(Shortcut::pattern("(\\d+)k", "{1} nghìn"));
+    assert_eq!(type_word(&mut e, "149k <"), "149k ");
+}
+
+#[test]
+fn shortcut_undo_skips_long_expansion() {
+    // 300 chars can't be erased by one Result: Backspace just deletes
+    let body = "x".repeat(300);
+    let mut e = undo_engine(Shortcut::new("sg", &body));
+    assert_eq!(type_word(&mut e, "sg <"), body);
+    assert_eq!(e.usage().shortcut_count("sg"), 1);
+}
+
+#[test]
+fn shortcut_undo_key_configurable() {
+    // Off by default: Backspace just deletes
+    let mut e = Engine::new();
+    e.shortcuts_mut().add(Shortcut::new("ko", "không"));
+    assert_eq!(type_word(&mut e, "ko <"), "không");
+
+    e.set_shortcut_undo_key(Some(keys::ESC));
+    assert_eq!(type_word(&mut e, "ko \x1b"), "ko ");
+
+    e.set_shortcut_undo_key(None);
+    assert_eq!(type_word(&mut e, "ko <"), "không");
+}
+
 #[test]
 fn shortcut_does_not_trigger_without_space() {
     let mut e = Engine::new();
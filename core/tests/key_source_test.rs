@@ -0,0 +1,114 @@
+//! Key Source Tests
+//!
+//! Native keycodes (Windows VK, X11 keysym, evdev) and typed characters
+//! give the same text as macOS keycodes.
+
+use gonhanh_core::data::keycodes::{KeySource, MOD_CAPS_LOCK, MOD_SHIFT};
+use gonhanh_core::engine::{Action, Engine, Result};
+use gonhanh_core::utils::type_word;
+
+/// Apply a Result to the screen; `typed` is the key's own char
+fn apply(screen: &mut String, r: &Result, typed: char) {
+    if r.action == Action::Send as u8 {
+        for _ in 0..r.backspace {
+            screen.pop();
+        }
+        screen.extend(
+            r.chars[..r.count as usize]
+                .iter()
+                .filter_map(|&c| char::from_u32(c)),
+        );
+        // Letters are part of the replacement; break keys follow it
+        if r.key_consumed() || typed.is_alphanumeric() || typed == ' ' {
+            return;
+        }
+    }
+    screen.push(typed);
+}
+
+/// Type ASCII text as native keycodes of `source`
+fn type_codes(source: KeySource, input: &str, code: impl Fn(char) -> u32) -> String {
+    let mut e = Engine::new();
+    let mut screen = String::new();
+    for c in input.chars() {
+        let mods = if c.is_ascii_uppercase() { MOD_SHIFT } else { 0 };
+        let r = e.on_key_code(source, code(c), mods);
+        apply(&mut screen, &r, c);
+    }
+    screen
+}
+
+fn windows_vk(c: char) -> u32 {
+    match c {
+        ' ' => 0x20,
+        _ => c.to_ascii_uppercase() as u32,
+    }
+}
+
+fn x11_keysym(c: char) -> u32 {
+    c as u32
+}
+
+fn evdev(c: char) -> u32 {
+    let rows = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+    let starts = [16, 30, 44];
+    if c == ' ' {
+        return 57;
+    }
+    let c = c.to_ascii_lowercase();
+    rows.iter()
+        .zip(starts)
+        .find_map(|(row, start)| row.find(c).map(|i| start + i as u32))
+        .unwrap()
+}
+
+#[test]
+fn same_text_from_every_source() {
+    let input = "Vieetj Nam tuyeetj vowif";
+    let expected = type_word(&mut Engine::new(), input);
+    assert_eq!(expected, "Việt Nam tuyệt vời");
+    assert_eq!(type_codes(KeySource::Windows, input, windows_vk), expected);
+    assert_eq!(type_codes(KeySource::X11, input, x11_keysym), expected);
+    assert_eq!(type_codes(KeySource::Evdev, input, evdev), expected);
+
+    let mut e = Engine::new();
+    let mut screen = String::new();
+    for c in input.chars() {
+        let r = e.on_char(c, 0);
+        apply(&mut screen, &r, c);
+    }
+    assert_eq!(screen, expected);
+}
+
+#[test]
+fn caps_lock_and_shift() {
+    let mut e = Engine::new();
+    let mut screen = String::new();
+    // Caps Lock on: "DDA" + Shift+S (lowercase under Caps Lock, still a tone key)
+    for (sym, mods) in [('d', 0), ('d', 0), ('a', 0), ('s', MOD_SHIFT)] {
+        let r = e.on_key_code(KeySource::X11, sym as u32, mods | MOD_CAPS_LOCK);
+        apply(&mut screen, &r, sym.to_ascii_uppercase());
+    }
+    assert_eq!(screen, "ĐÁ");
+}
+
+#[test]
+fn foreign_char_ends_word() {
+    let mut e = Engine::new();
+    let mut screen = String::new();
+    for c in "aé".chars() {
+        let r = e.on_char(c, 0);
+        apply(&mut screen, &r, c);
+    }
+    // "é" has no key: the word "a" is closed, "s" starts a new one
+    let r = e.on_char('s', 0);
+    apply(&mut screen, &r, 's');
+    assert_eq!(screen, "aés");
+
+    // Unknown keycodes pass through without touching the word
+    let mut e = Engine::new();
+    e.on_key_code(KeySource::Windows, 0x41, 0);
+    assert_eq!(e.on_key_code(KeySource::Windows, 0x70, 0).action, 0); // F1
+    let r = e.on_key_code(KeySource::Windows, 0x53, 0);
+    assert_eq!(r.chars[0], 'á' as u32);
+}
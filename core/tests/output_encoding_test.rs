@@ -0,0 +1,256 @@
+//! Output Encoding Tests - Results emitted in NFD and legacy charsets
+
+mod common;
+use common::type_word;
+use gonhanh_core::data::encoding::{self, Encoding};
+use gonhanh_core::engine::Engine;
+
+fn nfd(s: &str) -> String {
+    let mut out = Vec::new();
+    for c in s.chars() {
+        encoding::decompose(c, &mut out);
+    }
+    out.into_iter().collect()
+}
+
+/// Encode expected NFC text the way the engine emits it
+fn encoded(s: &str, enc: Encoding) -> String {
+    let mut out = Vec::new();
+    for c in s.chars() {
+        encoding::encode(c, enc, &mut out);
+    }
+    out.into_iter().collect()
+}
+
+/// Type with a legacy charset, compare against encoded expected text
+fn telex_legacy(enc: Encoding, cases: &[(&str, &str)]) {
+    for (input, expected) in cases {
+        let mut e = Engine::new();
+        e.set_output_encoding(enc);
+        let result = type_word(&mut e, input);
+        assert_eq!(
+            result,
+            encoded(expected, enc),
+            "[Telex {:?}] '{}' → '{:?}'",
+            enc,
+            input,
+            result
+        );
+    }
+}
+
+/// Type with NFD output, compare against NFD of expected NFC text
+fn telex_nfd(cases: &[(&str, &str)]) {
+    for (input, expected) in cases {
+        let mut e = Engine::new();
+        e.set_output_encoding(Encoding::UnicodeNfd);
+        let result = type_word(&mut e, input);
+        assert_eq!(
+            result,
+            nfd(expected),
+            "[Telex NFD] '{}' → '{:?}'",
+            input,
+            result
+        );
+    }
+}
+
+// ============================================================
+// UNICODE NFD
+// ============================================================
+
+const NFD_BASIC: &[(&str, &str)] = &[
+    ("as", "á"),
+    ("aa", "â"),
+    ("aw", "ă"),
+    ("ow", "ơ"),
+    ("dd", "đ"),
+    ("aas", "ấ"),
+    ("owj", "ợ"),
+    ("aaj", "ậ"),
+];
+
+const NFD_WORDS: &[(&str, &str)] = &[
+    ("vieetj nam", "việt nam"),
+    ("dduowngf", "đường"),
+    ("nguowif", "người"),
+    // Mark moves when vowel pattern changes
+    ("hoaf", "hoà"),
+    ("huyeenf", "huyền"),
+    // Mark and tone changes on an already-decomposed char
+    ("asf", "à"),
+    ("aasf", "ầ"),
+    ("oow", "ơ"),
+    // Reverts
+    ("ass", "as"),
+    ("aaa", "aa"),
+];
+
+#[test]
+fn nfd_basic() {
+    telex_nfd(NFD_BASIC);
+}
+
+#[test]
+fn nfd_words() {
+    telex_nfd(NFD_WORDS);
+}
+
+#[test]
+fn nfd_shortcut() {
+    let mut e = Engine::new();
+    e.set_output_encoding(Encoding::UnicodeNfd);
+    e.shortcuts_mut()
+        .add(gonhanh_core::engine::shortcut::Shortcut::new(
+            "vn",
+            "Việt Nam",
+        ));
+    assert_eq!(type_word(&mut e, "vn "), nfd("Việt Nam "));
+}
+
+#[test]
+fn nfd_auto_restore() {
+    let mut e = Engine::new();
+    e.set_output_encoding(Encoding::UnicodeNfd);
+    e.set_english_auto_restore(true);
+    assert_eq!(type_word(&mut e, "text "), "text ");
+}
+
+#[test]
+fn nfd_esc_restore() {
+    let mut e = Engine::new();
+    e.set_output_encoding(Encoding::UnicodeNfd);
+    e.set_esc_restore(true);
+    assert_eq!(type_word(&mut e, "vieetj\x1b"), "vieetj");
+}
+
+#[test]
+fn nfd_restore_word() {
+    let mut e = Engine::new();
+    e.set_output_encoding(Encoding::UnicodeNfd);
+    e.restore_word(&nfd("việt"));
+    assert_eq!(e.get_buffer_string(), nfd("việt"));
+}
+
+// ============================================================
+// LEGACY CHARSETS
+// ============================================================
+
+#[test]
+fn tcvn3_words() {
+    telex_legacy(Encoding::Tcvn3, NFD_BASIC);
+    telex_legacy(Encoding::Tcvn3, NFD_WORDS);
+}
+
+#[test]
+fn vni_windows_words() {
+    telex_legacy(Encoding::VniWin, NFD_BASIC);
+    telex_legacy(Encoding::VniWin, NFD_WORDS);
+}
+
+#[test]
+fn viscii_words() {
+    telex_legacy(Encoding::Viscii, NFD_BASIC);
+    telex_legacy(Encoding::Viscii, NFD_WORDS);
+}
+
+#[test]
+fn legacy_expected_bytes() {
+    let mut e = Engine::new();
+    e.set_output_encoding(Encoding::VniWin);
+    assert_eq!(type_word(&mut e, "Vieetj Nam"), "Vieät Nam");
+
+    let mut e = Engine::new();
+    e.set_output_encoding(Encoding::Tcvn3);
+    assert_eq!(type_word(&mut e, "Vieetj"), "Vi\u{D6}t");
+}
+
+#[test]
+fn vni_windows_backspace_units() {
+    let mut e = Engine::new();
+    e.set_output_encoding(Encoding::VniWin);
+    type_word(&mut e, "aa"); // "aâ" on screen
+    let r = e.on_key_ext(gonhanh_core::data::keys::S, false, false, false);
+    assert_eq!(r.backspace, 2);
+
+    // Plain a → ă ("aê"): one unit deleted, two typed
+    let mut e = Engine::new();
+    e.set_output_encoding(Encoding::VniWin);
+    type_word(&mut e, "a");
+    let r = e.on_key_ext(gonhanh_core::data::keys::W, false, false, false);
+    assert_eq!(r.backspace, 1);
+    assert_eq!(r.count, 2);
+}
+
+#[test]
+fn legacy_shortcut() {
+    let mut e = Engine::new();
+    e.set_output_encoding(Encoding::VniWin);
+    e.shortcuts_mut()
+        .add(gonhanh_core::engine::shortcut::Shortcut::new(
+            "vn",
+            "Việt Nam",
+        ));
+    assert_eq!(type_word(&mut e, "vn "), "Vieät Nam ");
+}
+
+#[test]
+fn legacy_long_shortcut() {
+    // VNI-Windows doubles most Vietnamese letters: output spans two chunks
+    let mut e = Engine::new();
+    e.set_output_encoding(Encoding::VniWin);
+    let text = "Cộng hòa xã hội chủ nghĩa Việt Nam - Độc lập, Tự do, Hạnh phúc";
+    e.shortcuts_mut()
+        .add(gonhanh_core::engine::shortcut::Shortcut::new("qh", text));
+    let expected = gonhanh_core::convert::convert(
+        text,
+        gonhanh_core::convert::Charset::Unicode,
+        gonhanh_core::convert::Charset::VniWin,
+    );
+    assert!(expected.chars().count() > 64);
+    assert_eq!(type_word(&mut e, "qh "), format!("{} ", expected));
+}
+
+#[test]
+fn legacy_shortcut_cursor() {
+    // Cursor move counts encoded units: "ộ" is two chars in VNI-Windows
+    let mut e = Engine::new();
+    e.set_output_encoding(Encoding::VniWin);
+    e.shortcuts_mut()
+        .add(gonhanh_core::engine::shortcut::Shortcut::new(
+            "hd",
+            "({cursor}hộ)",
+        ));
+    e.on_key(gonhanh_core::data::keys::H, false, false);
+    e.on_key(gonhanh_core::data::keys::D, false, false);
+    let r = e.on_key(gonhanh_core::data::keys::SPACE, false, false);
+    // "hộ) " → h, o, ä, ), space
+    assert_eq!(r.cursor_left, 5);
+}
+
+#[test]
+fn legacy_pattern_shortcut() {
+    // Backspace counts encoded units of the whole token, not just the buffer
+    let mut e = Engine::new();
+    e.set_output_encoding(Encoding::VniWin);
+    e.shortcuts_mut()
+        .add(gonhanh_core::engine::shortcut::Shortcut::pattern(
+            "(\\w+)-(\\d+)k",
+            "{1} {2} nghìn",
+        ));
+    assert_eq!(
+        type_word(&mut e, "hooj-1k "),
+        encoded("hộ 1 nghìn ", Encoding::VniWin)
+    );
+}
+
+#[test]
+fn legacy_shortcut_undo() {
+    // Undo erases the encoded expansion and puts back the encoded trigger
+    let mut e = Engine::new();
+    e.set_output_encoding(Encoding::VniWin);
+    e.set_shortcut_undo_key(Some(gonhanh_core::data::keys::DELETE));
+    e.shortcuts_mut()
+        .add(gonhanh_core::engine::shortcut::Shortcut::new("ko", "không"));
+    assert_eq!(type_word(&mut e, "ko <"), "ko ");
+}
@@ -0,0 +1,150 @@
+//! Preedit Tests
+//!
+//! Preedit mode: the word is shown as a composition string and committed
+//! on word boundaries, with no backspaces into the app.
+
+use gonhanh_core::data::keys;
+use gonhanh_core::engine::shortcut::Shortcut;
+use gonhanh_core::engine::{Action, Engine, Result, FLAG_PREEDIT};
+
+/// App text as a preedit frontend sees it
+#[derive(Default)]
+struct App {
+    committed: String,
+    /// Preedit after each key
+    preedits: Vec<String>,
+}
+
+impl App {
+    /// Apply a Result; `typed` is what the app does with an unconsumed key
+    fn apply(&mut self, e: &Engine, r: &Result, typed: Option<char>) {
+        if r.action == Action::Send as u8 {
+            for _ in 0..r.backspace {
+                self.committed.pop();
+            }
+            self.committed.extend(
+                r.chars[..r.count as usize]
+                    .iter()
+                    .filter_map(|&c| char::from_u32(c)),
+            );
+        }
+        if !r.key_consumed() {
+            match typed {
+                Some('<') => {
+                    self.committed.pop();
+                }
+                Some(c) => self.committed.push(c),
+                None => {}
+            }
+        }
+        self.preedits.push(e.preedit());
+    }
+}
+
+/// Type text in preedit mode: '<' is Backspace, '\n' is Enter
+fn type_preedit(e: &mut Engine, input: &str) -> App {
+    let mut app = App::default();
+    for c in input.chars() {
+        let (r, typed) = match c {
+            '<' => (e.on_key_ext(keys::DELETE, false, false, false), Some('<')),
+            '\n' => (e.on_key_ext(keys::RETURN, false, false, false), Some('\n')),
+            _ => (e.on_char(c, 0), Some(c)),
+        };
+        app.apply(e, &r, typed);
+    }
+    app
+}
+
+fn engine() -> Engine {
+    let mut e = Engine::new();
+    e.set_preedit_mode(true);
+    e
+}
+
+#[test]
+fn composes_and_commits_on_space() {
+    let mut e = engine();
+    let app = type_preedit(&mut e, "vieetj nam");
+    assert_eq!(app.committed, "việt ");
+    assert_eq!(e.preedit(), "nam");
+    assert_eq!(e.preedit_cursor(), 3);
+    assert_eq!(app.preedits[..6], ["v", "vi", "vie", "viê", "viêt", "việt"]);
+    assert_eq!(app.preedits[6], "");
+}
+
+#[test]
+fn key_results() {
+    let mut e = engine();
+    // Composing keys are consumed and only change the preedit
+    let r = e.on_char('a', 0);
+    assert_eq!((r.count, r.flags), (0, FLAG_PREEDIT | 0x01));
+    let r = e.on_char('s', 0);
+    assert_eq!((r.count, r.flags), (0, FLAG_PREEDIT | 0x01));
+    assert_eq!(e.preedit(), "á");
+    // Punctuation commits the word with itself
+    let r = e.on_char(',', 0);
+    assert_eq!(r.count, 2);
+    assert_eq!(r.backspace, 0);
+    assert!(r.key_consumed());
+    assert_eq!(e.preedit(), "");
+}
+
+#[test]
+fn backspace() {
+    let mut e = engine();
+    // Inside the preedit: edits it
+    let app = type_preedit(&mut e, "vieetj<");
+    assert_eq!(app.committed, "");
+    assert_eq!(e.preedit(), "việ");
+
+    // Nothing composed: the app deletes committed text
+    let mut e = engine();
+    let app = type_preedit(&mut e, "ab <<");
+    assert_eq!(app.committed, "a");
+    assert_eq!(e.preedit(), "");
+}
+
+#[test]
+fn enter_commits_and_passes_through() {
+    let mut e = engine();
+    let app = type_preedit(&mut e, "dduowngf\n");
+    assert_eq!(app.committed, "đường\n");
+    assert_eq!(e.preedit(), "");
+}
+
+#[test]
+fn shortcut_commits_expansion() {
+    let mut e = engine();
+    e.shortcuts_mut().add(Shortcut::new("vn", "Việt Nam"));
+    let app = type_preedit(&mut e, "vn ");
+    assert_eq!(app.committed, "Việt Nam ");
+    assert_eq!(e.preedit(), "");
+}
+
+#[test]
+fn commit_preedit() {
+    let mut e = engine();
+    type_preedit(&mut e, "chaof");
+    let r = e.commit_preedit();
+    let text: String = r.chars[..r.count as usize]
+        .iter()
+        .filter_map(|&c| char::from_u32(c))
+        .collect();
+    assert_eq!(text, "chào");
+    assert_eq!(e.preedit(), "");
+    // The next word starts fresh
+    let app = type_preedit(&mut e, "as");
+    assert_eq!(app.preedits.last().unwrap(), "á");
+    assert_eq!(e.commit_preedit().count, 1);
+    assert_eq!(e.commit_preedit().action, 0);
+}
+
+#[test]
+fn modifier_symbols_keep_word_open() {
+    // VIQR modifiers are punctuation keys but don't end the word
+    let mut e = engine();
+    e.set_method(2);
+    let app = type_preedit(&mut e, "Vie^.t ");
+    assert_eq!(app.committed, "Việt ");
+    assert_eq!(app.preedits[3], "Viê");
+}
@@ -0,0 +1,104 @@
+//! Profile Tests
+//!
+//! Per-app profiles: each profile has its own settings and shortcut table,
+//! and each app keeps its own input method and on/off state.
+
+use gonhanh_core::engine::shortcut::Shortcut;
+use gonhanh_core::engine::Engine;
+use gonhanh_core::utils::type_word;
+
+const TERMINAL: &str = "com.apple.Terminal";
+const CHAT: &str = "com.tinyspeck.slackmacgap";
+
+/// Engine with a "code" profile (auto-restore, code shortcuts) for the
+/// terminal and a "chat" profile (auto-capitalize, casual shortcuts)
+fn engine() -> Engine {
+    let mut e = Engine::new();
+
+    assert!(e.add_profile("code"));
+    e.use_profile("code");
+    e.set_english_auto_restore(true);
+    e.shortcuts_mut().add(Shortcut::new("fn", "function"));
+
+    assert!(e.add_profile("chat"));
+    e.use_profile("chat");
+    e.set_auto_capitalize(true);
+    e.shortcuts_mut().add(Shortcut::new("ko", "không"));
+
+    e.use_profile("default");
+    assert!(e.assign_profile(TERMINAL, "code"));
+    assert!(e.assign_profile(CHAT, "chat"));
+    e
+}
+
+#[test]
+fn profile_settings_follow_context() {
+    let mut e = engine();
+
+    e.set_context(TERMINAL);
+    assert_eq!(e.profile(), "code");
+    assert_eq!(type_word(&mut e, "text "), "text ");
+    assert_eq!(type_word(&mut e, "fn "), "function ");
+    assert_eq!(type_word(&mut e, "ko "), "ko ");
+
+    e.set_context(CHAT);
+    assert_eq!(e.profile(), "chat");
+    assert_eq!(type_word(&mut e, "ko ok. ok "), "không ok. Ok ");
+    assert_eq!(type_word(&mut e, "fn "), "fn ");
+
+    // Unassigned apps use the default profile
+    e.set_context("org.mozilla.firefox");
+    assert_eq!(e.profile(), "default");
+    assert_eq!(type_word(&mut e, "text "), "tẽt ");
+}
+
+#[test]
+fn profile_edits_are_kept() {
+    let mut e = engine();
+    e.set_context(CHAT);
+    e.shortcuts_mut().add(Shortcut::new("dc", "được"));
+    e.set_context(TERMINAL);
+    e.set_context(CHAT);
+    assert_eq!(e.shortcuts().len(), 2);
+    assert!(e.settings().auto_capitalize);
+}
+
+#[test]
+fn method_and_enabled_per_app() {
+    let mut e = engine();
+
+    e.set_context(TERMINAL);
+    e.set_method(1); // VNI
+    assert_eq!(type_word(&mut e, "a1 "), "á ");
+
+    // First visit starts from the current state
+    e.set_context(CHAT);
+    e.set_method(0); // Telex
+    e.set_enabled(false);
+    assert_eq!(type_word(&mut e, "as "), "as ");
+
+    e.set_context(TERMINAL);
+    assert_eq!(type_word(&mut e, "a1 "), "á ");
+    e.set_context(CHAT);
+    assert_eq!(type_word(&mut e, "as "), "as ");
+    e.set_enabled(true);
+    assert_eq!(type_word(&mut e, "as "), "á ");
+}
+
+#[test]
+fn reassign_and_remove() {
+    let mut e = engine();
+    e.set_context(TERMINAL);
+    assert!(
+        !e.remove_profile("code"),
+        "active profile cannot be removed"
+    );
+    assert!(!e.assign_profile(TERMINAL, "missing"));
+
+    // Reassigning the current app switches immediately
+    assert!(e.assign_profile(TERMINAL, ""));
+    assert_eq!(e.profile(), "default");
+    assert!(e.remove_profile("code"));
+    assert!(!e.remove_profile("default"));
+    assert!(!e.use_profile("code"));
+}
@@ -0,0 +1,70 @@
+//! Reconvert Tests
+//!
+//! Selected text between raw keystrokes and Vietnamese.
+
+use gonhanh_core::reconvert::{reconvert, to_keystrokes, to_vietnamese, Direction};
+
+const SENTENCES: &[&str] = &[
+    "Việt Nam",
+    "Tôi yêu tiếng Việt.",
+    "Người đẹp, đường xa!",
+    "Cộng hoà xã hội chủ nghĩa Việt Nam",
+    "ĐẠI HỌC QUỐC GIA",
+    "Hôm nay trời đẹp quá\nĐi chơi không?",
+];
+
+#[test]
+fn keys_to_vietnamese() {
+    assert_eq!(
+        to_vietnamese("Tooi yeeu tieengs Vieejt", 0),
+        "Tôi yêu tiếng Việt"
+    );
+    assert_eq!(
+        to_vietnamese("Toi6 yeu6 tie61ng Vie65t", 1),
+        "Tôi yêu tiếng Việt"
+    );
+    assert_eq!(to_vietnamese("Vie^.t Nam", 2), "Việt Nam");
+    // Punctuation, newlines and non-keyboard chars are kept in place
+    assert_eq!(
+        to_vietnamese("dduowcj, khoong?\n— ok", 0),
+        "được, không?\n— ok"
+    );
+}
+
+#[test]
+fn vietnamese_to_keys() {
+    assert_eq!(
+        to_keystrokes("Tôi yêu tiếng Việt", 0),
+        "Tooi yeeu tieengs Vieetj"
+    );
+    assert_eq!(
+        to_keystrokes("Tôi yêu tiếng Việt", 1),
+        "To6i ye6u tie6ng1 Vie6t5"
+    );
+    assert_eq!(to_keystrokes("Việt Nam.", 2), "Vie^t. Nam\\.");
+    assert_eq!(to_keystrokes("ĐẮK LẮK", 0), "DDAWKS LAWKS");
+    assert_eq!(to_keystrokes("trăm", 1), "tra8m");
+    // Decomposed input
+    assert_eq!(to_keystrokes("vie\u{323}\u{302}t", 0), "vieetj");
+}
+
+#[test]
+fn round_trip() {
+    for method in [0, 1, 2] {
+        for &text in SENTENCES {
+            let keys = to_keystrokes(text, method);
+            assert_eq!(
+                to_vietnamese(&keys, method),
+                text,
+                "method {method}: {keys}"
+            );
+        }
+    }
+}
+
+#[test]
+fn auto_direction() {
+    assert_eq!(reconvert("xin chaof", 0, Direction::Auto), "xin chào");
+    assert_eq!(reconvert("xin chào", 0, Direction::Auto), "xin chaof");
+    assert_eq!(reconvert("hello", 0, Direction::ToKeys), "hello");
+}
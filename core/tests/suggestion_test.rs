@@ -0,0 +1,102 @@
+//! Suggestion Tests - Candidate list for the current word
+
+mod common;
+use common::type_word;
+use gonhanh_core::data::encoding::Encoding;
+use gonhanh_core::engine::{Action, Engine, Result};
+
+fn engine_suggest() -> Engine {
+    let mut e = Engine::new();
+    e.set_suggestions(true);
+    e
+}
+
+/// Apply a replacement result to the screen text
+fn apply(screen: &mut String, r: &Result) {
+    assert_eq!(r.action, Action::Send as u8);
+    for _ in 0..r.backspace {
+        screen.pop();
+    }
+    screen.extend(
+        r.chars[..r.count as usize]
+            .iter()
+            .filter_map(|&c| char::from_u32(c)),
+    );
+}
+
+#[test]
+fn toneless_word_candidates() {
+    let mut e = engine_suggest();
+    type_word(&mut e, "duoc");
+    let list = e.candidates();
+    assert_eq!(list[0], "được");
+    assert!(list.contains(&"dược".to_string()));
+    assert!(list.contains(&"đuốc".to_string()));
+    assert!(!list.contains(&"duóc".to_string()));
+    assert!(list.len() <= 9);
+}
+
+#[test]
+fn candidates_ignore_typed_diacritics() {
+    let mut e = engine_suggest();
+    type_word(&mut e, "dduwowcj");
+    assert_eq!(e.candidates()[0], "được");
+
+    let mut other = engine_suggest();
+    type_word(&mut other, "duoc");
+    assert_eq!(e.candidates(), other.candidates());
+}
+
+#[test]
+fn pick_candidate_replaces_word() {
+    let mut e = engine_suggest();
+    let mut screen = type_word(&mut e, "duoc");
+    let idx = e.candidates().iter().position(|c| c == "dược").unwrap();
+    apply(&mut screen, &e.pick_candidate(idx));
+    assert_eq!(screen, "dược");
+    assert_eq!(e.get_buffer_string(), "dược");
+
+    // Typing continues on the picked word
+    let r = e.on_key(gonhanh_core::data::keys::S, false, false);
+    apply(&mut screen, &r);
+    assert_eq!(screen, "dước");
+}
+
+#[test]
+fn pick_candidate_keeps_case() {
+    let mut e = engine_suggest();
+    let mut screen = type_word(&mut e, "Khong");
+    apply(&mut screen, &e.pick_candidate(0));
+    assert_eq!(screen, "Không");
+}
+
+#[test]
+fn pick_candidate_out_of_range() {
+    let mut e = engine_suggest();
+    type_word(&mut e, "duoc");
+    assert_eq!(e.pick_candidate(99).action, Action::None as u8);
+}
+
+#[test]
+fn pick_candidate_legacy_encoding() {
+    let mut e = engine_suggest();
+    e.set_output_encoding(Encoding::VniWin);
+    let mut screen = type_word(&mut e, "dduwowcj"); // "ñöôïc" on screen
+    assert_eq!(screen, "ñöôïc");
+    let idx = e.candidates().iter().position(|c| c == "dược").unwrap();
+    apply(&mut screen, &e.pick_candidate(idx));
+    assert_eq!(screen, "döôïc");
+}
+
+#[test]
+fn no_candidates_when_disabled_or_cleared() {
+    let mut e = Engine::new();
+    type_word(&mut e, "duoc");
+    assert!(e.candidates().is_empty());
+
+    let mut e = engine_suggest();
+    type_word(&mut e, "duoc ");
+    assert!(e.candidates().is_empty());
+    type_word(&mut e, "xyz");
+    assert!(e.candidates().is_empty());
+}
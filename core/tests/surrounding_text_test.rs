@@ -0,0 +1,114 @@
+//! Surrounding Text Tests
+//!
+//! Editing a committed word after the platform reports the text around
+//! the cursor.
+
+use gonhanh_core::data::encoding::Encoding;
+use gonhanh_core::data::keys;
+use gonhanh_core::engine::{Action, Engine};
+use gonhanh_core::utils::type_word;
+
+/// Text after typing letters `input` with the cursor at `cursor` in `text`
+fn edit(e: &mut Engine, text: &str, cursor: usize, input: &str) -> String {
+    e.set_surrounding_text(text, cursor);
+    let mut before: Vec<char> = text.chars().take(cursor).collect();
+    let mut after: Vec<char> = text.chars().skip(cursor).collect();
+    for c in input.chars() {
+        let r = e.on_char(c, 0);
+        if r.action == Action::Send as u8 {
+            before.truncate(before.len() - r.backspace as usize);
+            after.drain(..r.delete_after as usize);
+            before.extend(
+                r.chars[..r.count as usize]
+                    .iter()
+                    .filter_map(|&c| char::from_u32(c)),
+            );
+            let moved = before.split_off(before.len() - r.cursor_left as usize);
+            after.splice(0..0, moved);
+        } else {
+            before.push(c);
+        }
+    }
+    before.into_iter().chain(after).collect()
+}
+
+#[test]
+fn adds_marks_to_committed_word() {
+    let mut e = Engine::new();
+    assert_eq!(edit(&mut e, "Viet Nam", 4, "ej"), "Việt Nam");
+    let mut e = Engine::new();
+    assert_eq!(edit(&mut e, "xin chao", 8, "f"), "xin chào");
+    // Changing an existing mark
+    let mut e = Engine::new();
+    assert_eq!(edit(&mut e, "việt", 4, "s"), "viết");
+}
+
+#[test]
+fn edits_inside_word() {
+    let mut e = Engine::new();
+    assert_eq!(edit(&mut e, "Viet Nam", 2, "j"), "Viẹt Nam");
+    // The cursor stays put: more keys edit the same word
+    let mut e = Engine::new();
+    assert_eq!(edit(&mut e, "Viet Nam", 2, "ej"), "Việt Nam");
+    let mut e = Engine::new();
+    assert_eq!(edit(&mut e, "Việt Nam", 1, "s"), "Viết Nam");
+    let mut e = Engine::new();
+    assert_eq!(edit(&mut e, "nguoi ta", 3, "wf"), "người ta");
+    // Other keys go in at the cursor and end the word
+    let mut e = Engine::new();
+    assert_eq!(edit(&mut e, "Viet Nam", 2, "1"), "Vi1et Nam");
+}
+
+#[test]
+fn result_reaches_into_word() {
+    let mut e = Engine::new();
+    e.set_surrounding_text("Chao ban", 4);
+    let r = e.on_key(keys::F, false, false);
+    assert_eq!(r.backspace, 2);
+    let out: String = r.chars[..r.count as usize]
+        .iter()
+        .filter_map(|&c| char::from_u32(c))
+        .collect();
+    assert_eq!(out, "ào");
+}
+
+#[test]
+fn no_word_before_cursor() {
+    for (text, cursor) in [
+        ("Viet Nam", 5), // after a space
+        ("Viet Nam", 0), // before a word
+        ("Viet", 9),     // past the end
+        ("Señor", 5),    // not Vietnamese
+        ("abc123", 6),   // after digits
+    ] {
+        let mut e = Engine::new();
+        e.set_surrounding_text(text, cursor);
+        assert_eq!(e.get_buffer_string(), "", "{text} at {cursor}");
+    }
+}
+
+#[test]
+fn keeps_word_being_typed() {
+    // Platforms report the text after every key: the typed word stays intact
+    let mut e = Engine::new();
+    e.set_english_auto_restore(true);
+    type_word(&mut e, "tex");
+    e.set_surrounding_text("tẽ", 2);
+    assert_eq!(type_word(&mut e, "t "), "text ");
+}
+
+#[test]
+fn encoded_text() {
+    let mut e = Engine::new();
+    e.set_output_encoding(Encoding::UnicodeNfd);
+    assert_eq!(edit(&mut e, "vie\u{302}t ", 5, "j"), "vie\u{323}\u{302}t ");
+    let mut e = Engine::new();
+    e.set_output_encoding(Encoding::UnicodeNfd);
+    assert_eq!(edit(&mut e, "vie\u{302}t ", 2, "j"), "vie\u{323}\u{302}t ");
+
+    let mut e = Engine::new();
+    e.set_output_encoding(Encoding::VniWin);
+    // "Vieät" is "Việt" in VNI-Windows
+    e.set_surrounding_text("Vieät", 5);
+    assert_eq!(e.get_buffer_string(), "Vieät");
+}
@@ -0,0 +1,71 @@
+//! Usage Tests
+//!
+//! Shortcut counts and suggested shortcuts from phrases typed in full.
+
+use gonhanh_core::engine::shortcut::Shortcut;
+use gonhanh_core::engine::Engine;
+use gonhanh_core::utils::type_word;
+
+fn engine() -> Engine {
+    let mut e = Engine::new();
+    e.usage_mut().set_tracking(true);
+    e
+}
+
+#[test]
+fn shortcut_fires_are_counted() {
+    let mut e = Engine::new();
+    e.shortcuts_mut().add(Shortcut::new("ko", "không"));
+    assert_eq!(type_word(&mut e, "ko ok ko "), "không ok không ");
+    assert_eq!(e.usage().shortcut_count("ko"), 2);
+    assert_eq!(e.usage().shortcut_count("KO"), 2);
+}
+
+#[test]
+fn typed_phrase_is_suggested() {
+    let mut e = engine();
+    for _ in 0..3 {
+        type_word(&mut e, "ddaji hojc quoocs gia, ");
+    }
+    let s = e.shortcut_suggestions(5);
+    assert_eq!(s.len(), 1);
+    assert_eq!(s[0].phrase, "đại học quốc gia");
+    assert_eq!(s[0].trigger, "dhqg");
+    assert_eq!(s[0].count, 3);
+
+    // Once a shortcut produces the phrase it is no longer suggested
+    e.shortcuts_mut()
+        .add(Shortcut::new("dhqg", "đại học quốc gia"));
+    assert!(e.shortcut_suggestions(5).is_empty());
+}
+
+#[test]
+fn punctuation_ends_phrase() {
+    let mut e = engine();
+    type_word(&mut e, "thanhf phoos. Hoof Chis Minh ");
+    assert_eq!(e.usage().phrase_count("Hồ Chí Minh"), 1);
+    assert_eq!(e.usage().phrase_count("phố. Hồ Chí"), 0);
+    assert_eq!(e.usage().phrase_count("thành phố Hồ Chí"), 0);
+}
+
+#[test]
+fn corrected_word_counts_once() {
+    let mut e = engine();
+    type_word(&mut e, "phats trieenr ");
+    type_word(&mut e, "<<<<<<<<trieenr ");
+    assert_eq!(e.usage().phrase_count("phát triển"), 1);
+}
+
+#[test]
+fn usage_round_trip() {
+    let mut e = engine();
+    for _ in 0..3 {
+        type_word(&mut e, "Hoof Chis Minh ");
+        e.clear_all();
+    }
+    let saved = e.usage().save();
+
+    let mut other = Engine::new();
+    assert_eq!(other.usage_mut().load(&saved), 1);
+    assert_eq!(other.shortcut_suggestions(5)[0].trigger, "hcm");
+}
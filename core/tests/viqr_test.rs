@@ -0,0 +1,123 @@
+//! VIQR Tests - Punctuation modifiers, literal symbols, restore
+
+mod common;
+use common::{engine_viqr, type_word, viqr};
+use gonhanh_core::engine::shortcut::{InputMethod, Shortcut};
+
+// ============================================================
+// MARKS & TONES
+// ============================================================
+
+const VIQR_MARKS: &[(&str, &str)] = &[
+    ("a'", "á"),
+    ("a`", "à"),
+    ("a?", "ả"),
+    ("a~", "ã"),
+    ("a.", "ạ"),
+    ("A'", "Á"),
+];
+
+const VIQR_TONES: &[(&str, &str)] = &[
+    ("a^", "â"),
+    ("e^", "ê"),
+    ("o^", "ô"),
+    ("a(", "ă"),
+    ("o+", "ơ"),
+    ("u+", "ư"),
+    ("dd", "đ"),
+    ("DD", "Đ"),
+];
+
+const VIQR_WORDS: &[(&str, &str)] = &[
+    ("Vie^.t Nam", "Việt Nam"),
+    ("tie^'ng Vie^.t", "tiếng Việt"),
+    ("ddu+o+`ng", "đường"),
+    ("nguo+`i", "người"),
+    ("hoa`", "hoà"),
+    ("a(n", "ăn"),
+    ("kho^ng", "không"),
+    // Mark before tone
+    ("vie.^t", "việt"),
+];
+
+// ============================================================
+// LITERAL SYMBOLS
+// ============================================================
+
+const VIQR_LITERAL: &[(&str, &str)] = &[
+    // Same symbol twice types it literally
+    ("to..", "to."),
+    ("a''", "a'"),
+    ("a^^", "a^"),
+    ("sao??", "sao?"),
+    // Backslash escape
+    ("to\\.", "to."),
+    ("a\\'", "a'"),
+    // Symbols without a word pass through
+    ("'", "'"),
+    ("?", "?"),
+    // Symbols that can't apply pass through
+    ("n'", "n'"),
+    ("hello.", "hello."),
+    // Different mark on the same key replaces the mark
+    ("a`~", "ã"),
+    ("a'`", "à"),
+];
+
+#[test]
+fn viqr_marks() {
+    viqr(VIQR_MARKS);
+}
+
+#[test]
+fn viqr_tones() {
+    viqr(VIQR_TONES);
+}
+
+#[test]
+fn viqr_words() {
+    viqr(VIQR_WORDS);
+}
+
+#[test]
+fn viqr_literal() {
+    viqr(VIQR_LITERAL);
+}
+
+#[test]
+fn viqr_symbol_key_consumed() {
+    let mut e = engine_viqr();
+    type_word(&mut e, "a");
+    let r = e.on_key_ext(gonhanh_core::data::keys::QUOTE, false, false, false);
+    assert!(
+        r.key_consumed(),
+        "applied modifier should consume the symbol"
+    );
+}
+
+#[test]
+fn viqr_esc_restore() {
+    let mut e = engine_viqr();
+    e.set_esc_restore(true);
+    assert_eq!(type_word(&mut e, "tie^'ng\x1b"), "tie^'ng");
+}
+
+#[test]
+fn viqr_auto_restore_apostrophe() {
+    let mut e = engine_viqr();
+    e.set_english_auto_restore(true);
+    assert_eq!(type_word(&mut e, "it's "), "it's ");
+}
+
+#[test]
+fn viqr_shortcut() {
+    let mut e = engine_viqr();
+    e.shortcuts_mut()
+        .add(Shortcut::new("vn", "Việt Nam").for_method(InputMethod::Viqr));
+    assert_eq!(type_word(&mut e, "vn "), "Việt Nam ");
+
+    let mut e = common::engine_telex();
+    e.shortcuts_mut()
+        .add(Shortcut::new("vn", "Việt Nam").for_method(InputMethod::Viqr));
+    assert_eq!(type_word(&mut e, "vn "), "vn ");
+}
@@ -132,23 +132,31 @@ void GoNhanhEngine::keyEvent(const fcitx::InputMethodEntry& entry,
                      << " shift=" << shift;
 
     // Process through Rust core
-    auto [backspace, text] = RustBridge::processKey(macKeycode, caps, ctrl, shift);
+    auto output = RustBridge::processKey(macKeycode, caps, ctrl, shift);
 
     // If no action needed, pass through
-    if (text.empty() && backspace == 0) {
+    if (output.text.empty() && output.backspace == 0) {
         return;
     }
 
-    GONHANH_DEBUG() << "Result: backspace=" << backspace << " text=\"" << text << "\"";
+    GONHANH_DEBUG() << "Result: backspace=" << output.backspace
+                     << " text=\"" << output.text << "\""
+                     << " cursorLeft=" << output.cursorLeft;
 
     // Delete characters (backspace)
-    if (backspace > 0) {
-        ic->deleteSurroundingText(-backspace, backspace);
+    if (output.backspace > 0) {
+        ic->deleteSurroundingText(-output.backspace, output.backspace);
     }
 
     // Commit new text
-    if (!text.empty()) {
-        ic->commitString(text);
+    if (!output.text.empty()) {
+        ic->commitString(output.text);
+    }
+
+    // Move the cursor back to the shortcut's {cursor} marker
+    for (int i = 0; i < output.cursorLeft; ++i) {
+        ic->forwardKey(fcitx::Key(FcitxKey_Left), false);
+        ic->forwardKey(fcitx::Key(FcitxKey_Left), true);
     }
 
     // Filter the key (don't let original key through)
@@ -10,7 +10,7 @@ void RustBridge::initialize() {
     initialized_ = true;
 }
 
-std::pair<int, std::string> RustBridge::processKey(
+ImeOutput RustBridge::processKey(
     uint16_t keyCode,
     bool caps,
     bool ctrl,
@@ -22,19 +22,27 @@ std::pair<int, std::string> RustBridge::processKey(
 
     ImeResult* result = ime_key_ext(keyCode, caps, ctrl, shift);
     if (!result) {
-        return {0, ""};
+        return {};
     }
 
-    std::pair<int, std::string> output = {0, ""};
+    ImeOutput output;
 
     if (result->action == static_cast<uint8_t>(ImeAction::Send)) {
-        output.first = result->backspace;
+        output.backspace = result->backspace;
+        output.cursorLeft = result->cursor_left;
+        appendChars(result, output.text);
 
-        // Convert UTF-32 chars to UTF-8 string
-        for (uint8_t i = 0; i < result->count && i < 32; ++i) {
-            if (result->chars[i] > 0) {
-                output.second += codePointToUtf8(result->chars[i]);
-            }
+        // Long output (e.g. shortcut expansions) arrives in chunks;
+        // the cursor move is on the last one
+        bool more = result->flags & IME_FLAG_MORE_OUTPUT;
+        while (more) {
+            ImeResult* next = ime_next_output();
+            if (!next) break;
+            more = next->action == static_cast<uint8_t>(ImeAction::Send) &&
+                   (next->flags & IME_FLAG_MORE_OUTPUT);
+            appendChars(next, output.text);
+            output.cursorLeft = next->cursor_left;
+            ime_free(next);
         }
     }
 
@@ -42,6 +50,15 @@ std::pair<int, std::string> RustBridge::processKey(
     return output;
 }
 
+void RustBridge::appendChars(const ImeResult* result, std::string& out) {
+    // Convert UTF-32 chars to UTF-8 string
+    for (uint8_t i = 0; i < result->count && i < 64; ++i) {
+        if (result->chars[i] > 0) {
+            out += codePointToUtf8(result->chars[i]);
+        }
+    }
+}
+
 void RustBridge::setMethod(InputMethod method) {
     ime_method(static_cast<uint8_t>(method));
 }
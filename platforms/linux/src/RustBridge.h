@@ -8,26 +8,33 @@
 // FFI Result structure - must match core/src/engine/mod.rs
 // #[repr(C)]
 // pub struct Result {
-//     pub chars: [u32; 32],
+//     pub chars: [u32; 64],
 //     pub action: u8,
 //     pub backspace: u8,
 //     pub count: u8,
-//     pub _pad: u8,
+//     pub flags: u8,
+//     pub cursor_left: u16,
+//     pub delete_after: u8,
 // }
 //
-// Note: Rust #[repr(C)] uses C ABI layout, which matches C++ struct layout
-// for this specific arrangement. The array (128 bytes) is followed by
-// 4 bytes of u8 fields = 132 bytes total with no implicit padding needed.
+// Note: Rust #[repr(C)] uses C ABI layout, which matches C++ struct layout.
+// The array (256 bytes) is followed by 4 bytes of u8 fields, a u16 and a u8,
+// padded to the 4-byte alignment of the array = 264 bytes total.
 struct ImeResult {
-    uint32_t chars[32];  // 128 bytes
-    uint8_t action;      // 1 byte
-    uint8_t backspace;   // 1 byte
-    uint8_t count;       // 1 byte
-    uint8_t _pad;        // 1 byte (explicit padding to 4-byte boundary)
+    uint32_t chars[64];    // 256 bytes
+    uint8_t action;        // 1 byte
+    uint8_t backspace;     // 1 byte
+    uint8_t count;         // 1 byte
+    uint8_t flags;         // 1 byte (bit 1: more output pending)
+    uint16_t cursor_left;  // 2 bytes
+    uint8_t delete_after;  // 1 byte (+1 byte trailing padding)
 };
 
 // Verify struct size matches Rust at compile time
-static_assert(sizeof(ImeResult) == 132, "ImeResult size mismatch with Rust core");
+static_assert(sizeof(ImeResult) == 264, "ImeResult size mismatch with Rust core");
+
+// Result flags
+constexpr uint8_t IME_FLAG_MORE_OUTPUT = 0x02;  // Fetch the rest with ime_next_output
 
 // Action types
 enum class ImeAction : uint8_t {
@@ -39,19 +46,28 @@ enum class ImeAction : uint8_t {
 // Input method types
 enum class InputMethod : uint8_t {
     Telex = 0,
-    VNI = 1
+    VNI = 1,
+    VIQR = 2
 };
 
 // FFI function declarations (from core/src/lib.rs)
 extern "C" {
     void ime_init();
     ImeResult* ime_key_ext(uint16_t key, bool caps, bool ctrl, bool shift);
+    ImeResult* ime_next_output();
     void ime_method(uint8_t method);
     void ime_enabled(bool enabled);
     void ime_clear();
     void ime_free(ImeResult* result);
 }
 
+// Edit to apply for one keystroke
+struct ImeOutput {
+    int backspace = 0;   // Characters to delete before the cursor
+    std::string text;    // Text to commit
+    int cursorLeft = 0;  // Characters to move the cursor left after committing
+};
+
 // C++ wrapper class for Rust bridge
 class RustBridge {
 public:
@@ -59,8 +75,8 @@ public:
     static void initialize();
 
     // Process a keystroke and return result
-    // Returns: (backspace_count, output_text) or empty if no action needed
-    static std::pair<int, std::string> processKey(
+    // Returns: the edit to apply, or an empty one if no action needed
+    static ImeOutput processKey(
         uint16_t keyCode,
         bool caps,
         bool ctrl,
@@ -80,6 +96,9 @@ public:
     static std::string codePointToUtf8(uint32_t cp);
 
 private:
+    // Append a result's UTF-32 chars to a UTF-8 string
+    static void appendChars(const ImeResult* result, std::string& out);
+
     static bool initialized_;
 };
 
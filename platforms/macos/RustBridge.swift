@@ -159,7 +159,8 @@ private class TextInjector {
     }
 
     /// Inject text replacement synchronously (blocks until complete)
-    func injectSync(bs: Int, text: String, method: InjectionMethod, delays: (UInt32, UInt32, UInt32), proxy: CGEventTapProxy) {
+    /// `cursorLeft`: chars to move the cursor back into the text (shortcut `{cursor}`)
+    func injectSync(bs: Int, text: String, cursorLeft: Int = 0, method: InjectionMethod, delays: (UInt32, UInt32, UInt32), proxy: CGEventTapProxy) {
         semaphore.wait()
         defer { semaphore.signal() }
 
@@ -170,15 +171,16 @@ private class TextInjector {
 
         switch method {
         case .selection:
-            injectViaSelection(bs: bs, text: text, delays: delays)
+            injectViaSelection(bs: bs, text: text, cursorLeft: cursorLeft, delays: delays)
         case .autocomplete:
-            injectViaAutocomplete(bs: bs, text: text, proxy: proxy)
+            injectViaAutocomplete(bs: bs, text: text, cursorLeft: cursorLeft, proxy: proxy)
         case .axDirect:
-            injectViaAXWithFallback(bs: bs, text: text, proxy: proxy)
+            injectViaAXWithFallback(bs: bs, text: text, cursorLeft: cursorLeft, proxy: proxy)
         case .selectAll:
+            // Retypes the whole session, which has no cursor inside it
             injectViaSelectAll(proxy: proxy)
         case .slow, .fast:
-            injectViaBackspace(bs: bs, text: text, delays: delays)
+            injectViaBackspace(bs: bs, text: text, cursorLeft: cursorLeft, delays: delays)
         case .passthrough:
             // Should not reach here - passthrough is handled in keyboard callback
             break
@@ -191,7 +193,7 @@ private class TextInjector {
     // MARK: - Injection Methods
 
     /// Standard backspace injection: delete N chars, then type replacement
-    private func injectViaBackspace(bs: Int, text: String, delays: (UInt32, UInt32, UInt32)) {
+    private func injectViaBackspace(bs: Int, text: String, cursorLeft: Int, delays: (UInt32, UInt32, UInt32)) {
         guard let src = CGEventSource(stateID: .privateState) else { return }
 
         for _ in 0..<bs {
@@ -201,13 +203,14 @@ private class TextInjector {
         if bs > 0 { usleep(delays.1) }
 
         postText(text, source: src, delay: delays.2)
+        postCursorLeft(cursorLeft, source: src, delay: delays.0)
         Log.send("bs", bs, text)
     }
 
     /// Selection injection: Shift+Left to select, then type replacement (for browser address bars)
     /// For backspace-only (text empty): use backspace to properly delete spaces/punctuation
     /// For text replacement: use Shift+Left to select (normal behavior)
-    private func injectViaSelection(bs: Int, text: String, delays: (UInt32, UInt32, UInt32)) {
+    private func injectViaSelection(bs: Int, text: String, cursorLeft: Int, delays: (UInt32, UInt32, UInt32)) {
         guard let src = CGEventSource(stateID: .privateState) else { return }
 
         let selDelay = delays.0 > 0 ? delays.0 : 1000
@@ -234,12 +237,13 @@ private class TextInjector {
         }
 
         postText(text, source: src, delay: textDelay)
+        postCursorLeft(cursorLeft, source: src, delay: selDelay)
         Log.send("sel", bs, text)
     }
 
     /// Autocomplete injection: Forward Delete to clear suggestion, then backspace + text via proxy
     /// Used for Spotlight where autocomplete auto-selects suggestion text after cursor
-    private func injectViaAutocomplete(bs: Int, text: String, proxy: CGEventTapProxy) {
+    private func injectViaAutocomplete(bs: Int, text: String, cursorLeft: Int, proxy: CGEventTapProxy) {
         guard let src = CGEventSource(stateID: .privateState) else { return }
 
         // Forward Delete clears auto-selected suggestion
@@ -255,6 +259,7 @@ private class TextInjector {
 
         // Type replacement text
         postText(text, source: src, proxy: proxy)
+        postCursorLeft(cursorLeft, source: src, delay: 1000, proxy: proxy)
         Log.send("auto", bs, text)
     }
 
@@ -283,7 +288,7 @@ private class TextInjector {
     /// AX API injection: Directly manipulate text field via Accessibility API
     /// Used for Spotlight/Arc where synthetic keyboard events are unreliable due to autocomplete
     /// Returns true if successful, false if caller should fallback to synthetic events
-    func injectViaAX(bs: Int, text: String) -> Bool {
+    func injectViaAX(bs: Int, text: String, cursorLeft: Int = 0) -> Bool {
         // Get focused element
         let systemWide = AXUIElementCreateSystemWide()
         var focusedRef: CFTypeRef?
@@ -336,8 +341,8 @@ private class TextInjector {
             return false
         }
 
-        // Update cursor to end of inserted text
-        var newCursor = CFRange(location: deleteStart + text.count, length: 0)
+        // Update cursor to end of inserted text (or its {cursor} marker)
+        var newCursor = CFRange(location: deleteStart + max(0, text.count - cursorLeft), length: 0)
         if let newRange = AXValueCreate(.cfRange, &newCursor) {
             AXUIElementSetAttributeValue(axEl, kAXSelectedTextRangeAttribute as CFString, newRange)
         }
@@ -348,24 +353,32 @@ private class TextInjector {
 
     /// Try AX injection with retries, fallback to synthetic events if all fail
     /// Spotlight can be busy searching, causing AX API to fail temporarily
-    func injectViaAXWithFallback(bs: Int, text: String, proxy: CGEventTapProxy) {
+    func injectViaAXWithFallback(bs: Int, text: String, cursorLeft: Int, proxy: CGEventTapProxy) {
         // Try AX API up to 3 times (Spotlight might be busy)
         for attempt in 0..<3 {
             if attempt > 0 {
                 usleep(5000)  // 5ms delay before retry
             }
-            if injectViaAX(bs: bs, text: text) {
+            if injectViaAX(bs: bs, text: text, cursorLeft: cursorLeft) {
                 return  // Success!
             }
         }
 
         // All AX attempts failed - fallback to autocomplete method
         Log.info("AX: fallback to autocomplete")
-        injectViaAutocomplete(bs: bs, text: text, proxy: proxy)
+        injectViaAutocomplete(bs: bs, text: text, cursorLeft: cursorLeft, proxy: proxy)
     }
 
     // MARK: - Helpers
 
+    /// Move the cursor left N chars (Left Arrow), e.g. back to a shortcut's `{cursor}`
+    private func postCursorLeft(_ count: Int, source: CGEventSource, delay: UInt32, proxy: CGEventTapProxy? = nil) {
+        for _ in 0..<count {
+            postKey(KeyCode.leftArrow, source: source, proxy: proxy)
+            usleep(delay)
+        }
+    }
+
     /// Post a single key press event
     private func postKey(_ keyCode: CGKeyCode, source: CGEventSource, flags: CGEventFlags = [], proxy: CGEventTapProxy? = nil) {
         guard let dn = CGEvent(keyboardEventSource: source, virtualKey: keyCode, keyDown: true),
@@ -415,8 +428,8 @@ private class TextInjector {
 // MARK: - FFI (Rust Bridge)
 
 /// FFI result struct - must match Rust `Result` struct layout exactly
-/// Size: 64 UInt32 chars (256 bytes) + 4 bytes = 260 bytes
-/// Max replacement: 63 UTF-32 codepoints (Vietnamese diacritics = 1 each)
+/// Size: 64 UInt32 chars (256 bytes) + 4 bytes + UInt16 + UInt8 (padded) = 264 bytes
+/// Longer output is split into chunks, fetched with `ime_next_output`
 private struct ImeResult {
     // 64 UInt32 values for UTF-32 codepoints (matches core/src/engine/buffer.rs MAX)
     var chars: (
@@ -432,13 +445,17 @@ private struct ImeResult {
     var action: UInt8
     var backspace: UInt8
     var count: UInt8
-    var flags: UInt8  // bit 0: key_consumed
+    var flags: UInt8  // bit 0: key_consumed, bit 1: more_output
+    var cursorLeft: UInt16
+    var deleteAfter: UInt8
 }
 
 private let FLAG_KEY_CONSUMED: UInt8 = 0x01  // Key was consumed by shortcut, don't pass through
+private let FLAG_MORE_OUTPUT: UInt8 = 0x02   // More chars pending, fetch with ime_next_output
 
 @_silgen_name("ime_init") private func ime_init()
 @_silgen_name("ime_key_ext") private func ime_key_ext(_ key: UInt16, _ caps: Bool, _ ctrl: Bool, _ shift: Bool) -> UnsafeMutablePointer<ImeResult>?
+@_silgen_name("ime_next_output") private func ime_next_output() -> UnsafeMutablePointer<ImeResult>?
 @_silgen_name("ime_method") private func ime_method(_ method: UInt8)
 @_silgen_name("ime_enabled") private func ime_enabled(_ enabled: Bool)
 @_silgen_name("ime_skip_w_shortcut") private func ime_skip_w_shortcut(_ skip: Bool)
@@ -474,21 +491,36 @@ class RustBridge {
         Log.info("Engine initialized")
     }
 
-    /// Process a keystroke. Returns (backspace, chars, keyConsumed) or nil if no action.
-    static func processKey(keyCode: UInt16, caps: Bool, ctrl: Bool, shift: Bool = false) -> (Int, [Character], Bool)? {
+    /// Process a keystroke. Returns (backspace, chars, cursorLeft, keyConsumed) or nil if no action.
+    static func processKey(keyCode: UInt16, caps: Bool, ctrl: Bool, shift: Bool = false) -> (Int, [Character], Int, Bool)? {
         guard isInitialized, let ptr = ime_key_ext(keyCode, caps, ctrl, shift) else { return nil }
         defer { ime_free(ptr) }
 
         let r = ptr.pointee
         guard r.action == 1 else { return nil }
 
-        let chars = withUnsafePointer(to: r.chars) { p in
+        var chars = resultChars(r)
+        var cursorLeft = Int(r.cursorLeft)
+        var more = (r.flags & FLAG_MORE_OUTPUT) != 0
+        // Long output (e.g. shortcut expansions) arrives in chunks; the cursor move is on the last one
+        while more, let next = ime_next_output() {
+            defer { ime_free(next) }
+            let n = next.pointee
+            guard n.action == 1 else { break }
+            chars += resultChars(n)
+            cursorLeft = Int(n.cursorLeft)
+            more = (n.flags & FLAG_MORE_OUTPUT) != 0
+        }
+        let keyConsumed = (r.flags & FLAG_KEY_CONSUMED) != 0
+        return (Int(r.backspace), chars, cursorLeft, keyConsumed)
+    }
+
+    private static func resultChars(_ r: ImeResult) -> [Character] {
+        withUnsafePointer(to: r.chars) { p in
             p.withMemoryRebound(to: UInt32.self, capacity: 64) { bound in
                 (0..<Int(r.count)).compactMap { Unicode.Scalar(bound[$0]).map(Character.init) }
             }
         }
-        let keyConsumed = (r.flags & FLAG_KEY_CONSUMED) != 0
-        return (Int(r.backspace), chars, keyConsumed)
     }
 
     static func setMethod(_ method: Int) {
@@ -1047,10 +1079,10 @@ private func keyboardCallback(
         }
 
         // First try Rust engine (handles immediate backspace-after-space)
-        if let (bs, chars, _) = RustBridge.processKey(keyCode: keyCode, caps: caps, ctrl: ctrl, shift: shift) {
+        if let (bs, chars, cursorLeft, _) = RustBridge.processKey(keyCode: keyCode, caps: caps, ctrl: ctrl, shift: shift) {
             let str = String(chars)
             Log.transform(bs, str)
-            sendReplacement(backspace: bs, chars: chars, method: method, delays: delays, proxy: proxy)
+            sendReplacement(backspace: bs, chars: chars, cursorLeft: cursorLeft, method: method, delays: delays, proxy: proxy)
             return nil
         }
 
@@ -1074,9 +1106,9 @@ private func keyboardCallback(
         skipWordRestoreAfterClick = false
     }
 
-    if let (bs, chars, keyConsumed) = RustBridge.processKey(keyCode: keyCode, caps: caps, ctrl: ctrl, shift: shift) {
+    if let (bs, chars, cursorLeft, keyConsumed) = RustBridge.processKey(keyCode: keyCode, caps: caps, ctrl: ctrl, shift: shift) {
         Log.transform(bs, String(chars))
-        sendReplacement(backspace: bs, chars: chars, method: method, delays: delays, proxy: proxy)
+        sendReplacement(backspace: bs, chars: chars, cursorLeft: cursorLeft, method: method, delays: delays, proxy: proxy)
 
         // Pass through break keys (punctuation) for auto-restore, except:
         // - Space: already handled by engine
@@ -1321,11 +1353,11 @@ private func detectMethod() -> (InjectionMethod, (UInt32, UInt32, UInt32)) {
     return (.fast, (1000, 3000, 1500))
 }
 
-private func sendReplacement(backspace bs: Int, chars: [Character], method: InjectionMethod, delays: (UInt32, UInt32, UInt32), proxy: CGEventTapProxy) {
+private func sendReplacement(backspace bs: Int, chars: [Character], cursorLeft: Int, method: InjectionMethod, delays: (UInt32, UInt32, UInt32), proxy: CGEventTapProxy) {
     let str = String(chars)
 
     // Use TextInjector for synchronized text injection
-    TextInjector.shared.injectSync(bs: bs, text: str, method: method, delays: delays, proxy: proxy)
+    TextInjector.shared.injectSync(bs: bs, text: str, cursorLeft: cursorLeft, method: method, delays: delays, proxy: proxy)
 }
 
 // MARK: - Per-App Mode Manager
@@ -85,7 +85,7 @@ public partial class App : System.Windows.Application
         if (result.Action == ImeAction.Send && result.Count > 0)
         {
             e.Handled = true;
-            TextSender.SendText(result.GetText(), result.Backspace);
+            TextSender.SendText(result.GetText(), result.Backspace, result.CursorLeft);
         }
         else if (result.Action == ImeAction.Restore)
         {
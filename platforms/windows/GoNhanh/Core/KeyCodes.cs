@@ -57,6 +57,7 @@ public static class KeyCodes
     public const ushort VK_CAPITAL = 0x14;   // Caps Lock
     public const ushort VK_ESCAPE = 0x1B;
     public const ushort VK_SPACE = 0x20;
+    public const ushort VK_LEFT = 0x25;      // Left arrow
 
     // Punctuation (US keyboard layout)
     public const ushort VK_OEM_1 = 0xBA;     // ;:
@@ -34,6 +34,9 @@ public static class RustBridge
     [DllImport(DllName, CallingConvention = CallingConvention.Cdecl)]
     private static extern IntPtr ime_key(ushort keycode, [MarshalAs(UnmanagedType.U1)] bool shift, [MarshalAs(UnmanagedType.U1)] bool capslock);
 
+    [DllImport(DllName, CallingConvention = CallingConvention.Cdecl)]
+    private static extern IntPtr ime_next_output();
+
     #endregion
 
     #region Public API
@@ -89,15 +92,36 @@ public static class RustBridge
             return ImeResult.Empty;
         }
 
+        NativeResult native;
         try
         {
-            var native = Marshal.PtrToStructure<NativeResult>(ptr);
-            return ImeResult.FromNative(native);
+            native = Marshal.PtrToStructure<NativeResult>(ptr);
         }
         finally
         {
             ime_free(ptr);
         }
+
+        var result = ImeResult.FromNative(native);
+        // Long output (e.g. shortcut expansions) arrives in chunks
+        bool more = (native.flags & NativeResult.FlagMoreOutput) != 0;
+        while (more)
+        {
+            IntPtr next = ime_next_output();
+            if (next == IntPtr.Zero) break;
+            try
+            {
+                var chunk = Marshal.PtrToStructure<NativeResult>(next);
+                if (chunk.action != (byte)ImeAction.Send) break;
+                result = result.Append(chunk);
+                more = (chunk.flags & NativeResult.FlagMoreOutput) != 0;
+            }
+            finally
+            {
+                ime_free(next);
+            }
+        }
+        return result;
     }
 
     #endregion
@@ -109,7 +133,8 @@ public static class RustBridge
 public enum InputMethod : byte
 {
     Telex = 0,
-    VNI = 1
+    VNI = 1,
+    VIQR = 2
 }
 
 /// <summary>
@@ -128,12 +153,17 @@ public enum ImeAction : byte
 [StructLayout(LayoutKind.Sequential)]
 internal struct NativeResult
 {
-    [MarshalAs(UnmanagedType.ByValArray, SizeConst = 32)]
+    /// <summary>Output continues in further chunks (fetch with ime_next_output)</summary>
+    public const byte FlagMoreOutput = 0x02;
+
+    [MarshalAs(UnmanagedType.ByValArray, SizeConst = 64)]
     public uint[] chars;
     public byte action;
     public byte backspace;
     public byte count;
-    public byte _pad;
+    public byte flags;
+    public ushort cursorLeft;
+    public byte deleteAfter;
 }
 
 /// <summary>
@@ -143,16 +173,19 @@ public readonly struct ImeResult
 {
     public readonly ImeAction Action;
     public readonly byte Backspace;
-    public readonly byte Count;
+    public readonly int Count;
+    /// <summary>Characters to move the cursor left after the text (shortcut {cursor})</summary>
+    public readonly int CursorLeft;
     private readonly uint[] _chars;
 
-    public static readonly ImeResult Empty = new(ImeAction.None, 0, 0, Array.Empty<uint>());
+    public static readonly ImeResult Empty = new(ImeAction.None, 0, 0, 0, Array.Empty<uint>());
 
-    private ImeResult(ImeAction action, byte backspace, byte count, uint[] chars)
+    private ImeResult(ImeAction action, byte backspace, int count, int cursorLeft, uint[] chars)
     {
         Action = action;
         Backspace = backspace;
         Count = count;
+        CursorLeft = cursorLeft;
         _chars = chars;
     }
 
@@ -162,10 +195,23 @@ public readonly struct ImeResult
             (ImeAction)native.action,
             native.backspace,
             native.count,
+            native.cursorLeft,
             native.chars ?? Array.Empty<uint>()
         );
     }
 
+    /// <summary>
+    /// Append the chars of a further output chunk (the cursor move is on the last one)
+    /// </summary>
+    internal ImeResult Append(NativeResult chunk)
+    {
+        var chars = new uint[Count + chunk.count];
+        Array.Copy(_chars, chars, Math.Min(Count, _chars.Length));
+        if (chunk.chars != null)
+            Array.Copy(chunk.chars, 0, chars, Count, Math.Min(chunk.count, chunk.chars.Length));
+        return new ImeResult(Action, Backspace, chars.Length, chunk.cursorLeft, chars);
+    }
+
     /// <summary>
     /// Get the result text as a string
     /// </summary>
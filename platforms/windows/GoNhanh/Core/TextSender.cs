@@ -11,6 +11,7 @@ public static class TextSender
     #region Win32 Constants
 
     private const uint INPUT_KEYBOARD = 1;
+    private const uint KEYEVENTF_EXTENDEDKEY = 0x0001;
     private const uint KEYEVENTF_KEYUP = 0x0002;
     private const uint KEYEVENTF_UNICODE = 0x0004;
 
@@ -55,7 +56,8 @@ public static class TextSender
     /// </summary>
     /// <param name="text">Text to insert</param>
     /// <param name="backspaces">Number of backspaces to send first</param>
-    public static void SendText(string text, int backspaces)
+    /// <param name="cursorLeft">Number of left arrows to send after the text (shortcut {cursor})</param>
+    public static void SendText(string text, int backspaces, int cursorLeft = 0)
     {
         if (string.IsNullOrEmpty(text) && backspaces == 0)
             return;
@@ -140,10 +142,36 @@ public static class TextSender
             });
         }
 
+        // Move the cursor back into the text
+        for (int i = 0; i < cursorLeft; i++)
+        {
+            AddKey(inputs, KeyCodes.VK_LEFT, KEYEVENTF_EXTENDEDKEY, marker);
+            AddKey(inputs, KeyCodes.VK_LEFT, KEYEVENTF_EXTENDEDKEY | KEYEVENTF_KEYUP, marker);
+        }
+
         if (inputs.Count > 0)
         {
             var inputArray = inputs.ToArray();
             SendInput((uint)inputArray.Length, inputArray, Marshal.SizeOf<INPUT>());
         }
     }
+
+    private static void AddKey(List<INPUT> inputs, ushort vk, uint flags, IntPtr marker)
+    {
+        inputs.Add(new INPUT
+        {
+            type = INPUT_KEYBOARD,
+            u = new INPUTUNION
+            {
+                ki = new KEYBDINPUT
+                {
+                    wVk = vk,
+                    wScan = 0,
+                    dwFlags = flags,
+                    time = 0,
+                    dwExtraInfo = marker
+                }
+            }
+        });
+    }
 }